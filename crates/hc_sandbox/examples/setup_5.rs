@@ -70,6 +70,7 @@ async fn main() -> anyhow::Result<()> {
             &mut cmd,
             EnableApp {
                 app_id: installed_app.installed_app_id,
+                timeout_secs: hc_sandbox::calls::DEFAULT_TIMEOUT_SECS,
             },
         )
         .await?;
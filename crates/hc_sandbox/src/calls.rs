@@ -6,17 +6,22 @@
 //! but if you want more control use [`CmdRunner::command`].
 use std::path::Path;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use anyhow::anyhow;
 use anyhow::bail;
 use anyhow::ensure;
 use holochain_conductor_api::AdminRequest;
 use holochain_conductor_api::AdminResponse;
+use holochain_conductor_api::AppRequest;
+use holochain_conductor_api::AppResponse;
 use holochain_conductor_api::AppStatusFilter;
 use holochain_conductor_api::InterfaceDriver;
+use holochain_conductor_api::ZomeCall;
 use holochain_conductor_api::{AdminInterfaceConfig, InstalledAppInfo};
 use holochain_p2p::kitsune_p2p::agent_store::AgentInfoSigned;
 use holochain_types::prelude::DnaHash;
+use holochain_types::prelude::ExternIO;
 use holochain_types::prelude::InstallAppDnaPayload;
 use holochain_types::prelude::InstallAppPayload;
 use holochain_types::prelude::RegisterDnaPayload;
@@ -29,10 +34,18 @@ use std::convert::TryFrom;
 use crate::cmds::Existing;
 use crate::expect_match;
 use crate::ports::get_admin_ports;
+use crate::ports::get_app_api;
 use crate::run::run_async;
 use crate::CmdRunner;
 use structopt::StructOpt;
 
+/// The default `--timeout-secs` for admin calls.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+lazy_static::lazy_static! {
+    static ref DEFAULT_TIMEOUT_SECS_ARG: String = DEFAULT_TIMEOUT_SECS.to_string();
+}
+
 #[doc(hidden)]
 #[derive(Debug, StructOpt)]
 pub struct Call {
@@ -43,11 +56,97 @@ pub struct Call {
     pub running: Vec<u16>,
     #[structopt(flatten)]
     pub existing: Existing,
+    #[structopt(long, default_value = "text", possible_values = &["text", "json"])]
+    /// How to print the result of the call.
+    /// `json` prints one serialized [`AdminCommandOutput`] per conductor.
+    pub output: OutputFormat,
+    #[structopt(long)]
+    /// Indent the output when using `--output json`.
+    pub pretty: bool,
     #[structopt(subcommand)]
     /// The admin request you want to make.
     pub call: AdminRequestCli,
 }
 
+/// The format results of admin calls are printed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable messages.
+    Text,
+    /// Machine readable json.
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(anyhow!(
+                "Bad output format: {}, only 'text' and 'json' are possible",
+                s
+            )),
+        }
+    }
+}
+
+/// The result of an admin call made from the CLI.
+///
+/// Serialized with a `command` tag naming the subcommand
+/// so scripts can consume it with `--output json`.
+// Fields are named after what they hold so documenting each is noise.
+#[allow(missing_docs)]
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+pub enum AdminCommandOutput {
+    /// Result of `add-admin-ws`.
+    AddAdminWs { port: u16 },
+    /// Result of `add-app-ws`.
+    AddAppWs { port: u16 },
+    /// Result of `list-app-ws`.
+    ListAppWs { ports: Vec<u16> },
+    /// Result of `register-dna`.
+    RegisterDna { dna: DnaHash },
+    /// Result of `install-app`.
+    InstallApp { app: InstalledAppInfo },
+    /// Result of `install-app-bundle`.
+    InstallAppBundle { app: InstalledAppInfo },
+    /// Result of `uninstall-app`.
+    UninstallApp { app_id: String },
+    /// Result of `list-dnas`.
+    ListDnas { dnas: Vec<DnaHash> },
+    /// Result of `new-agent`.
+    NewAgent { agent: AgentPubKey },
+    /// Result of `list-cells`.
+    ListCells { cells: Vec<CellId> },
+    /// Result of `list-active-apps`.
+    ListActiveApps { apps: Vec<String> },
+    /// Result of `list-apps`.
+    ListApps { apps: Vec<InstalledAppInfo> },
+    /// Result of `enable-app`.
+    EnableApp { app_id: String },
+    /// Result of `disable-app`.
+    DisableApp { app_id: String },
+    /// Result of `dump-state`.
+    DumpState {
+        state: serde_json::Value,
+        /// Where to write the state instead of stdout.
+        #[serde(skip)]
+        output_file: Option<PathBuf>,
+    },
+    /// Result of `call-zome`.
+    CallZome { response: serde_json::Value },
+    /// Result of `list-agents`.
+    ListAgents {
+        agents: Vec<AgentInfoSigned>,
+        /// Only used to label agents in text output.
+        #[serde(skip)]
+        cells: Vec<CellId>,
+    },
+}
+
 // Docs have different use for structopt
 // so documenting everything doesn't make sense.
 #[allow(missing_docs)]
@@ -79,6 +178,7 @@ pub enum AdminRequestCli {
     /// [Unimplemented].
     AddAgents,
     ListAgents(ListAgents),
+    CallZome(CallZome),
 }
 #[derive(Debug, StructOpt, Clone)]
 /// Calls AdminRequest::AddAdminInterfaces
@@ -177,6 +277,9 @@ pub struct UninstallApp {
 pub struct EnableApp {
     /// The InstalledAppId to activate.
     pub app_id: String,
+    #[structopt(long, default_value = DEFAULT_TIMEOUT_SECS_ARG.as_str())]
+    /// How long to wait for the conductor to respond.
+    pub timeout_secs: u64,
 }
 
 #[derive(Debug, StructOpt, Clone)]
@@ -185,12 +288,14 @@ pub struct EnableApp {
 pub struct DisableApp {
     /// The InstalledAppId to disable.
     pub app_id: String,
+    #[structopt(long, default_value = DEFAULT_TIMEOUT_SECS_ARG.as_str())]
+    /// How long to wait for the conductor to respond.
+    pub timeout_secs: u64,
 }
 
 #[derive(Debug, StructOpt, Clone)]
 /// Calls AdminRequest::DumpState
 /// and dumps the current cell's state.
-/// TODO: Add pretty print.
 /// TODO: Default to dumping all cell state.
 pub struct DumpState {
     #[structopt(parse(try_from_str = parse_dna_hash))]
@@ -199,6 +304,14 @@ pub struct DumpState {
     #[structopt(parse(try_from_str = parse_agent_key))]
     /// The agent half of the cell id to dump.
     pub agent_key: AgentPubKey,
+    #[structopt(long)]
+    /// Only dump the part of the state matching this JMESPath expression.
+    /// e.g. `[0].source_chain_dump.elements`
+    pub filter: Option<String>,
+    #[structopt(long)]
+    /// Write the state as json to this file instead of stdout.
+    /// Indented when `--pretty` is set.
+    pub output_file: Option<PathBuf>,
 }
 
 #[derive(Debug, StructOpt, Clone)]
@@ -216,12 +329,44 @@ pub struct ListAgents {
 
 #[derive(Debug, StructOpt, Clone)]
 /// Calls AdminRequest::ListApps
-/// and pretty prints the list of apps
+/// and prints the id and status of each app
 /// installed in this conductor.
 pub struct ListApps {
     #[structopt(short, long, parse(try_from_str = parse_status_filter))]
-    /// Optionally request agent info for a particular cell id.
+    /// Optionally only list apps that are `active` or `inactive`.
     pub status: Option<AppStatusFilter>,
+    #[structopt(long, default_value = DEFAULT_TIMEOUT_SECS_ARG.as_str())]
+    /// How long to wait for the conductor to respond.
+    pub timeout_secs: u64,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AppRequest::ZomeCall on an app interface
+/// and prints the response decoded as json.
+pub struct CallZome {
+    #[structopt(long)]
+    /// The InstalledAppId of the app to call.
+    pub app_id: String,
+    #[structopt(long)]
+    /// The AppRoleId of the cell to call within the app.
+    pub cell_role: String,
+    #[structopt(long)]
+    /// The zome containing the function.
+    pub zome: String,
+    #[structopt(long = "fn")]
+    /// The zome function to call.
+    pub fn_name: String,
+    #[structopt(long, default_value = "null")]
+    /// The json payload for the call.
+    /// Use `-` to read the payload from stdin.
+    pub payload: String,
+    #[structopt(long)]
+    /// The app interface port to call.
+    /// Defaults to the first app interface attached to the conductor.
+    pub app_port: Option<u16>,
+    #[structopt(long, default_value = DEFAULT_TIMEOUT_SECS_ARG.as_str())]
+    /// How long to wait for the call to complete.
+    pub timeout_secs: u64,
 }
 
 #[doc(hidden)]
@@ -229,8 +374,25 @@ pub async fn call(holochain_path: &Path, req: Call) -> anyhow::Result<()> {
     let Call {
         existing,
         running,
+        output,
+        pretty,
         call,
     } = req;
+    if output == OutputFormat::Json {
+        crate::send_messages_to_stderr();
+    }
+    // Stdin can only be read once, so read it before calling
+    // any conductors and give them all the same payload.
+    let call = match call {
+        AdminRequestCli::CallZome(mut args) if args.payload == "-" => {
+            use tokio::io::AsyncReadExt;
+            let mut payload = String::new();
+            tokio::io::stdin().read_to_string(&mut payload).await?;
+            args.payload = payload;
+            AdminRequestCli::CallZome(args)
+        }
+        call => call,
+    };
     let cmds = if running.is_empty() {
         let paths = if existing.is_empty() {
             crate::save::load(std::env::current_dir()?)?
@@ -268,129 +430,210 @@ pub async fn call(holochain_path: &Path, req: Call) -> anyhow::Result<()> {
         cmds
     };
     for mut cmd in cmds {
-        call_inner(&mut cmd.0, call.clone()).await?;
+        call_inner(&mut cmd.0, call.clone())
+            .await?
+            .print(output, pretty)?;
     }
     Ok(())
 }
 
-async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Result<()> {
-    match call {
-        AdminRequestCli::AddAdminWs(args) => {
-            let port = add_admin_interface(cmd, args).await?;
-            msg!("Added Admin port {}", port);
-        }
-        AdminRequestCli::AddAppWs(args) => {
-            let port = attach_app_interface(cmd, args).await?;
-            msg!("Added App port {}", port);
-        }
-        AdminRequestCli::ListAppWs => {
-            let ports = list_app_ws(cmd).await?;
-            msg!("Attached App Interfaces {:?}", ports);
-        }
-        AdminRequestCli::RegisterDna(args) => {
-            let dnas = register_dna(cmd, args).await?;
-            msg!("Registered Dna: {:?}", dnas);
-        }
-        AdminRequestCli::InstallApp(args) => {
-            let app_id = args.app_id.clone();
-            let _ = install_app(cmd, args).await?;
-            msg!("Installed App: {}", app_id);
-        }
-        AdminRequestCli::InstallAppBundle(args) => {
-            let app = install_app_bundle(cmd, args).await?;
-            msg!("Installed App: {}", app.installed_app_id,);
-        }
+async fn call_inner(
+    cmd: &mut CmdRunner,
+    call: AdminRequestCli,
+) -> anyhow::Result<AdminCommandOutput> {
+    let output = match call {
+        AdminRequestCli::AddAdminWs(args) => AdminCommandOutput::AddAdminWs {
+            port: add_admin_interface(cmd, args).await?,
+        },
+        AdminRequestCli::AddAppWs(args) => AdminCommandOutput::AddAppWs {
+            port: attach_app_interface(cmd, args).await?,
+        },
+        AdminRequestCli::ListAppWs => AdminCommandOutput::ListAppWs {
+            ports: list_app_ws(cmd).await?,
+        },
+        AdminRequestCli::RegisterDna(args) => AdminCommandOutput::RegisterDna {
+            dna: register_dna(cmd, args).await?,
+        },
+        AdminRequestCli::InstallApp(args) => AdminCommandOutput::InstallApp {
+            app: install_app(cmd, args).await?,
+        },
+        AdminRequestCli::InstallAppBundle(args) => AdminCommandOutput::InstallAppBundle {
+            app: install_app_bundle(cmd, args).await?,
+        },
         AdminRequestCli::UninstallApp(args) => {
             let app_id = args.app_id.clone();
             uninstall_app(cmd, args).await?;
-            msg!("Uninstalled App: {}", app_id,);
-        }
-        AdminRequestCli::ListDnas => {
-            let dnas = list_dnas(cmd).await?;
-            msg!("Dnas: {:?}", dnas);
-        }
-        AdminRequestCli::NewAgent => {
-            let agent = generate_agent_pub_key(cmd).await?;
-            msg!("Added agent {}", agent);
-        }
-        AdminRequestCli::ListCells => {
-            let cells = list_cell_ids(cmd).await?;
-            msg!("Cell Ids: {:?}", cells);
-        }
-        AdminRequestCli::ListActiveApps => {
-            let apps = list_running_apps(cmd).await?;
-            msg!("Active Apps: {:?}", apps);
-        }
-        AdminRequestCli::ListApps(args) => {
-            let apps = list_apps(cmd, args).await?;
-            msg!("List Apps: {:?}", apps);
+            AdminCommandOutput::UninstallApp { app_id }
         }
+        AdminRequestCli::ListDnas => AdminCommandOutput::ListDnas {
+            dnas: list_dnas(cmd).await?,
+        },
+        AdminRequestCli::NewAgent => AdminCommandOutput::NewAgent {
+            agent: generate_agent_pub_key(cmd).await?,
+        },
+        AdminRequestCli::ListCells => AdminCommandOutput::ListCells {
+            cells: list_cell_ids(cmd).await?,
+        },
+        AdminRequestCli::ListActiveApps => AdminCommandOutput::ListActiveApps {
+            apps: list_running_apps(cmd).await?,
+        },
+        AdminRequestCli::ListApps(args) => AdminCommandOutput::ListApps {
+            apps: with_timeout(args.timeout_secs, list_apps(cmd, args)).await?,
+        },
         AdminRequestCli::EnableApp(args) => {
             let app_id = args.app_id.clone();
-            enable_app(cmd, args).await?;
-            msg!("Activated app: {:?}", app_id);
+            with_timeout(args.timeout_secs, enable_app(cmd, args)).await?;
+            AdminCommandOutput::EnableApp { app_id }
         }
         AdminRequestCli::DisableApp(args) => {
             let app_id = args.app_id.clone();
-            disable_app(cmd, args).await?;
-            msg!("Deactivated app: {:?}", app_id);
+            with_timeout(args.timeout_secs, disable_app(cmd, args)).await?;
+            AdminCommandOutput::DisableApp { app_id }
         }
         AdminRequestCli::DumpState(args) => {
+            let filter = args.filter.clone();
+            let output_file = args.output_file.clone();
             let state = dump_state(cmd, args).await?;
-            msg!("DUMP STATE \n{}", state);
+            // The conductor dumps state as json but fall back to
+            // the raw string if that ever changes.
+            let state =
+                serde_json::from_str(&state).unwrap_or_else(|_| serde_json::Value::String(state));
+            let state = match filter {
+                Some(filter) => filter_json(state, &filter)?,
+                None => state,
+            };
+            AdminCommandOutput::DumpState { state, output_file }
         }
         AdminRequestCli::AddAgents => todo!("Adding agent info via cli is coming soon"),
-        AdminRequestCli::ListAgents(args) => {
-            use std::fmt::Write;
-            let agent_infos = request_agent_info(cmd, args).await?;
-            for info in agent_infos {
-                let mut out = String::new();
-                let cell_info = list_cell_ids(cmd).await?;
-                let agents = cell_info
-                    .iter()
-                    .map(|c| c.agent_pubkey().clone())
-                    .map(|a| (a.clone(), holochain_p2p::agent_holo_to_kit(a)))
-                    .collect::<Vec<_>>();
-
-                let dnas = cell_info
-                    .iter()
-                    .map(|c| c.dna_hash().clone())
-                    .map(|d| (d.clone(), holochain_p2p::space_holo_to_kit(d)))
-                    .collect::<Vec<_>>();
-
-                let this_agent = agents.iter().find(|a| *info.agent == a.1);
-                let this_dna = dnas.iter().find(|d| *info.space == d.1).unwrap();
-                if let Some(this_agent) = this_agent {
-                    writeln!(out, "This Agent {:?} is {:?}", this_agent.0, this_agent.1)?;
+        AdminRequestCli::ListAgents(args) => AdminCommandOutput::ListAgents {
+            agents: request_agent_info(cmd, args).await?,
+            cells: list_cell_ids(cmd).await?,
+        },
+        AdminRequestCli::CallZome(args) => AdminCommandOutput::CallZome {
+            response: call_zome(cmd, args).await?,
+        },
+    };
+    Ok(output)
+}
+
+impl AdminCommandOutput {
+    /// Print this output to stdout in the requested format.
+    pub fn print(&self, format: OutputFormat, pretty: bool) -> anyhow::Result<()> {
+        if let Self::DumpState {
+            state,
+            output_file: Some(path),
+        } = self
+        {
+            let out = if pretty {
+                serde_json::to_string_pretty(state)?
+            } else {
+                serde_json::to_string(state)?
+            };
+            std::fs::write(path, out)?;
+            if format == OutputFormat::Text {
+                msg!("Dumped state to {}", path.display());
+            }
+            return Ok(());
+        }
+        match format {
+            OutputFormat::Json => {
+                let out = if pretty {
+                    serde_json::to_string_pretty(self)?
+                } else {
+                    serde_json::to_string(self)?
+                };
+                println!("{}", out);
+            }
+            OutputFormat::Text => self.print_text()?,
+        }
+        Ok(())
+    }
+
+    fn print_text(&self) -> anyhow::Result<()> {
+        match self {
+            Self::AddAdminWs { port } => msg!("Added Admin port {}", port),
+            Self::AddAppWs { port } => msg!("Added App port {}", port),
+            Self::ListAppWs { ports } => msg!("Attached App Interfaces {:?}", ports),
+            Self::RegisterDna { dna } => msg!("Registered Dna: {:?}", dna),
+            Self::InstallApp { app } | Self::InstallAppBundle { app } => {
+                msg!("Installed App: {}", app.installed_app_id)
+            }
+            Self::UninstallApp { app_id } => msg!("Uninstalled App: {}", app_id),
+            Self::ListDnas { dnas } => msg!("Dnas: {:?}", dnas),
+            Self::NewAgent { agent } => msg!("Added agent {}", agent),
+            Self::ListCells { cells } => msg!("Cell Ids: {:?}", cells),
+            Self::ListActiveApps { apps } => msg!("Active Apps: {:?}", apps),
+            Self::ListApps { apps } => {
+                msg!("Installed Apps:");
+                for app in apps {
+                    println!("{} {:?}", app.installed_app_id, app.status);
+                }
+            }
+            Self::EnableApp { app_id } => msg!("Activated app: {:?}", app_id),
+            Self::DisableApp { app_id } => msg!("Deactivated app: {:?}", app_id),
+            Self::DumpState { state, .. } => {
+                msg!("DUMP STATE \n{}", serde_json::to_string_pretty(state)?)
+            }
+            Self::CallZome { response } => {
+                msg!(
+                    "Zome call response:\n{}",
+                    serde_json::to_string_pretty(response)?
+                )
+            }
+            Self::ListAgents { agents, cells } => {
+                for info in agents {
+                    msg!("{}\n", fmt_agent_info(info, cells)?);
                 }
-                writeln!(out, "This DNA {:?} is {:?}", this_dna.0, this_dna.1)?;
-
-                use chrono::{DateTime, Duration, NaiveDateTime, Utc};
-                let duration = Duration::milliseconds(info.signed_at_ms as i64);
-                let s = duration.num_seconds() as i64;
-                let n = duration.clone().to_std().unwrap().subsec_nanos();
-                let dt = DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp(s, n), Utc);
-                let duration = Duration::milliseconds(info.expires_at_ms as i64);
-                let s = duration.num_seconds() as i64;
-                let n = duration.clone().to_std().unwrap().subsec_nanos();
-                let exp = DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp(s, n), Utc);
-                let now = Utc::now();
-
-                writeln!(out, "signed at {}", dt)?;
-                writeln!(
-                    out,
-                    "expires at {} in {}mins",
-                    exp,
-                    (exp - now).num_minutes()
-                )?;
-                writeln!(out, "space: {:?}", info.space)?;
-                writeln!(out, "agent: {:?}", info.agent)?;
-                writeln!(out, "urls: {:?}", info.url_list)?;
-                msg!("{}\n", out);
             }
         }
+        Ok(())
     }
-    Ok(())
+}
+
+fn fmt_agent_info(info: &AgentInfoSigned, cell_info: &[CellId]) -> anyhow::Result<String> {
+    use std::fmt::Write;
+    let mut out = String::new();
+    let agents = cell_info
+        .iter()
+        .map(|c| c.agent_pubkey().clone())
+        .map(|a| (a.clone(), holochain_p2p::agent_holo_to_kit(a)))
+        .collect::<Vec<_>>();
+
+    let dnas = cell_info
+        .iter()
+        .map(|c| c.dna_hash().clone())
+        .map(|d| (d.clone(), holochain_p2p::space_holo_to_kit(d)))
+        .collect::<Vec<_>>();
+
+    let this_agent = agents.iter().find(|a| *info.agent == a.1);
+    let this_dna = dnas.iter().find(|d| *info.space == d.1).unwrap();
+    if let Some(this_agent) = this_agent {
+        writeln!(out, "This Agent {:?} is {:?}", this_agent.0, this_agent.1)?;
+    }
+    writeln!(out, "This DNA {:?} is {:?}", this_dna.0, this_dna.1)?;
+
+    use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+    let duration = Duration::milliseconds(info.signed_at_ms as i64);
+    let s = duration.num_seconds() as i64;
+    let n = duration.clone().to_std().unwrap().subsec_nanos();
+    let dt = DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp(s, n), Utc);
+    let duration = Duration::milliseconds(info.expires_at_ms as i64);
+    let s = duration.num_seconds() as i64;
+    let n = duration.clone().to_std().unwrap().subsec_nanos();
+    let exp = DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp(s, n), Utc);
+    let now = Utc::now();
+
+    writeln!(out, "signed at {}", dt)?;
+    writeln!(
+        out,
+        "expires at {} in {}mins",
+        exp,
+        (exp - now).num_minutes()
+    )?;
+    writeln!(out, "space: {:?}", info.space)?;
+    writeln!(out, "agent: {:?}", info.agent)?;
+    writeln!(out, "urls: {:?}", info.url_list)?;
+    Ok(out)
 }
 
 /// Calls [`AdminRequest::AddAdminInterfaces`] and adds another admin interface.
@@ -481,6 +724,7 @@ pub async fn install_app(
         cmd,
         EnableApp {
             app_id: installed_app.installed_app_id.clone(),
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
         },
     )
     .await?;
@@ -522,6 +766,7 @@ pub async fn install_app_bundle(
         cmd,
         EnableApp {
             app_id: installed_app.installed_app_id.clone(),
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
         },
     )
     .await?;
@@ -595,7 +840,11 @@ pub async fn enable_app(cmd: &mut CmdRunner, args: EnableApp) -> anyhow::Result<
             installed_app_id: args.app_id,
         })
         .await?;
-    assert!(matches!(resp, AdminResponse::AppEnabled { .. }));
+    ensure!(
+        matches!(resp, AdminResponse::AppEnabled { .. }),
+        "Failed to enable app, got: {:?}",
+        resp
+    );
     Ok(())
 }
 
@@ -631,7 +880,6 @@ pub async fn attach_app_interface(cmd: &mut CmdRunner, args: AddAppWs) -> anyhow
 }
 
 /// Calls [`AdminRequest::DumpState`] and dumps the current cell's state.
-// TODO: Add pretty print.
 // TODO: Default to dumping all cell state.
 pub async fn dump_state(cmd: &mut CmdRunner, args: DumpState) -> anyhow::Result<String> {
     let resp = cmd
@@ -668,6 +916,95 @@ pub async fn request_agent_info(
     Ok(expect_match!(resp => AdminResponse::AgentInfoRequested, "Failed to request agent info"))
 }
 
+/// Calls [`AppRequest::ZomeCall`] on an app interface
+/// and decodes the response as json.
+///
+/// The call is made with the cell's own agent as provenance
+/// and no cap secret.
+/// The payload must be json; reading it from stdin with `-`
+/// is only handled by the `call` command.
+pub async fn call_zome(cmd: &mut CmdRunner, args: CallZome) -> anyhow::Result<serde_json::Value> {
+    with_timeout(args.timeout_secs, call_zome_inner(cmd, args)).await
+}
+
+async fn call_zome_inner(cmd: &mut CmdRunner, args: CallZome) -> anyhow::Result<serde_json::Value> {
+    let CallZome {
+        app_id,
+        cell_role,
+        zome,
+        fn_name,
+        payload,
+        app_port,
+        timeout_secs: _,
+    } = args;
+    let payload: serde_json::Value = serde_json::from_str(&payload)?;
+    let payload = ExternIO::encode(payload)?;
+
+    let port = match app_port {
+        Some(port) => port,
+        None => *list_app_ws(cmd)
+            .await?
+            .first()
+            .ok_or_else(|| anyhow!("No app interfaces attached, add one with `add-app-ws`"))?,
+    };
+    let mut app_ws = get_app_api(port).await?;
+
+    let resp: AppResponse = app_ws
+        .request(AppRequest::AppInfo {
+            installed_app_id: app_id.clone(),
+        })
+        .await?;
+    let app_info = match resp {
+        AppResponse::AppInfo(Some(app_info)) => app_info,
+        AppResponse::AppInfo(None) => bail!("App {} is not installed", app_id),
+        _ => bail!("Failed to get app info, got: {:?}", resp),
+    };
+    let cell_id = app_info
+        .cell_data
+        .into_iter()
+        .find(|cell| *cell.as_role_id() == cell_role)
+        .map(|cell| cell.into_inner().0)
+        .ok_or_else(|| anyhow!("App {} has no cell with role {}", app_id, cell_role))?;
+
+    let call = ZomeCall {
+        provenance: cell_id.agent_pubkey().clone(),
+        cell_id,
+        zome_name: zome.into(),
+        fn_name: fn_name.into(),
+        payload,
+        cap_secret: None,
+    };
+    let resp: AppResponse = app_ws.request(AppRequest::ZomeCall(Box::new(call))).await?;
+    let output = match resp {
+        AppResponse::ZomeCall(output) => output,
+        _ => bail!("Zome call failed, got: {:?}", resp),
+    };
+    // Decode through a generic msgpack value because zome outputs
+    // can contain binary (e.g. hashes) which json values can't
+    // deserialize directly.
+    let output = rmpv::decode::read_value(&mut output.as_bytes())?;
+    Ok(serde_json::to_value(output)?)
+}
+
+/// Extract the parts of a json value matching a JMESPath expression.
+fn filter_json(value: serde_json::Value, filter: &str) -> anyhow::Result<serde_json::Value> {
+    let expr = jmespath::compile(filter)?;
+    let result = expr.search(value)?;
+    Ok(serde_json::to_value(&*result)?)
+}
+
+/// Fail a call that doesn't complete within `timeout_secs`.
+async fn with_timeout<T>(
+    timeout_secs: u64,
+    f: impl std::future::Future<Output = anyhow::Result<T>>,
+) -> anyhow::Result<T> {
+    let timeout = std::time::Duration::from_secs(timeout_secs);
+    match tokio::time::timeout(timeout, f).await {
+        Ok(r) => r,
+        Err(_) => bail!("Call timed out after {:?}", timeout),
+    }
+}
+
 fn parse_agent_key(arg: &str) -> anyhow::Result<AgentPubKey> {
     AgentPubKey::try_from(arg).map_err(|e| anyhow::anyhow!("{:?}", e))
 }
@@ -690,7 +1027,12 @@ fn parse_status_filter(arg: &str) -> anyhow::Result<AppStatusFilter> {
 impl From<CellId> for DumpState {
     fn from(cell_id: CellId) -> Self {
         let (dna, agent_key) = cell_id.into_dna_and_agent();
-        Self { dna, agent_key }
+        Self {
+            dna,
+            agent_key,
+            filter: None,
+            output_file: None,
+        }
     }
 }
 
@@ -122,14 +122,29 @@ pub use ports::force_admin_port;
 
 /// Print a msg with `hc-sandbox: ` pre-pended
 /// and ansi colors.
+/// Goes to stderr after [`send_messages_to_stderr`].
 macro_rules! msg {
     ($($arg:tt)*) => ({
         use ansi_term::Color::*;
-        print!("{} ", Blue.bold().paint("hc-sandbox:"));
-        println!($($arg)*);
+        if $crate::MESSAGES_TO_STDERR.load(std::sync::atomic::Ordering::Relaxed) {
+            eprint!("{} ", Blue.bold().paint("hc-sandbox:"));
+            eprintln!($($arg)*);
+        } else {
+            print!("{} ", Blue.bold().paint("hc-sandbox:"));
+            println!($($arg)*);
+        }
     })
 }
 
+static MESSAGES_TO_STDERR: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+/// Print all further messages to stderr so stdout
+/// only holds machine readable output.
+pub fn send_messages_to_stderr() {
+    MESSAGES_TO_STDERR.store(true, std::sync::atomic::Ordering::Relaxed);
+}
+
 pub mod bundles;
 pub mod calls;
 pub mod cli;
@@ -94,3 +94,220 @@ async fn run_multiple_on_same_port() {
     let _hc_admin2 = cmd.spawn().expect("Failed to spawn holochain");
     tokio::time::sleep(std::time::Duration::from_secs(4)).await;
 }
+
+const ELEMENTAL_CHAT_HAPP: &str = "../../../elemental-chat/elemental-chat.happ";
+
+/// Generates a sandbox with elemental chat installed as `test-app`
+/// and runs it on the given admin port.
+fn generate_and_run(port: u16, app_port: u16) -> tokio::process::Child {
+    let cmd = std::process::Command::cargo_bin("hc").unwrap();
+    let mut cmd = Command::from(cmd);
+    cmd.arg(format!("-f={}", port))
+        .arg("generate")
+        .arg(format!("-r={}", app_port))
+        .arg(ELEMENTAL_CHAT_HAPP)
+        .kill_on_drop(true);
+    cmd.spawn().expect("Failed to spawn holochain")
+}
+
+/// Makes an admin call with `--output json` against a running conductor
+/// and parses the result.
+/// Everything printed to stdout must be part of the json.
+async fn call_json(port: u16, args: &[&str]) -> serde_json::Value {
+    let stdout = call(port, args).await;
+    serde_json::from_str(&stdout).unwrap()
+}
+
+/// Makes an admin call with `--output json` against a running conductor
+/// and returns what was printed.
+async fn call(port: u16, args: &[&str]) -> String {
+    let cmd = std::process::Command::cargo_bin("hc").unwrap();
+    let mut cmd = Command::from(cmd);
+    cmd.arg("call")
+        .arg(format!("--running={}", port))
+        .arg("--output=json")
+        .args(args)
+        .kill_on_drop(true);
+    let output = cmd.output().await.expect("Failed to call holochain");
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Finds the status of an app in the output of `list-apps`.
+fn app_status<'a>(list_apps: &'a serde_json::Value, app_id: &str) -> &'a serde_json::Value {
+    list_apps["apps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|app| app["installed_app_id"] == app_id)
+        .map(|app| &app["status"])
+        .expect("App not listed")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "Figure out how to get holochain bin in CI"]
+async fn call_with_json_output() {
+    observability::test_run().ok();
+    let port: u16 = pick_unused_port().expect("No ports free");
+    let app_port: u16 = pick_unused_port().expect("No ports free");
+    let _hc_admin = generate_and_run(port, app_port);
+    tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+
+    let out = call_json(port, &["list-dnas"]).await;
+    assert_eq!(out["command"], "list-dnas");
+    assert_eq!(out["dnas"].as_array().map(|dnas| dnas.len()), Some(1));
+
+    let pretty = call_json(port, &["--pretty", "list-dnas"]).await;
+    assert_eq!(pretty, out);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "Figure out how to get holochain bin in CI"]
+async fn call_zome_from_cli() {
+    observability::test_run().ok();
+    let port: u16 = pick_unused_port().expect("No ports free");
+    let app_port: u16 = pick_unused_port().expect("No ports free");
+    let _hc_admin = generate_and_run(port, app_port);
+    tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+
+    let out = call_json(
+        port,
+        &[
+            "call-zome",
+            "--app-id=test-app",
+            "--cell-role=elemental-chat",
+            "--zome=chat",
+            "--fn=list_channels",
+            r#"--payload={"category":"General"}"#,
+        ],
+    )
+    .await;
+    assert_eq!(out["command"], "call-zome");
+    assert!(out["response"]["channels"].is_array());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "Figure out how to get holochain bin in CI"]
+async fn call_zome_reads_payload_from_stdin_once() {
+    use std::process::Stdio;
+    use tokio::io::AsyncWriteExt;
+
+    observability::test_run().ok();
+    let port: u16 = pick_unused_port().expect("No ports free");
+    let app_port: u16 = pick_unused_port().expect("No ports free");
+    let _hc_admin = generate_and_run(port, app_port);
+    tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+
+    // Calling the same conductor twice, both calls get the payload.
+    let cmd = std::process::Command::cargo_bin("hc").unwrap();
+    let mut cmd = Command::from(cmd);
+    cmd.arg("call")
+        .arg(format!("--running={},{}", port, port))
+        .arg("--output=json")
+        .args([
+            "call-zome",
+            "--app-id=test-app",
+            "--cell-role=elemental-chat",
+            "--zome=chat",
+            "--fn=list_channels",
+            "--payload=-",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = cmd.spawn().expect("Failed to call holochain");
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(br#"{"category":"General"}"#).await.unwrap();
+    drop(stdin);
+    let output = child.wait_with_output().await.unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let outs: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(outs.len(), 2);
+    for out in outs {
+        assert!(out["response"]["channels"].is_array());
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "Figure out how to get holochain bin in CI"]
+async fn list_installed_apps() {
+    observability::test_run().ok();
+    let port: u16 = pick_unused_port().expect("No ports free");
+    let app_port: u16 = pick_unused_port().expect("No ports free");
+    let _hc_admin = generate_and_run(port, app_port);
+    tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+
+    call_json(
+        port,
+        &[
+            "install-app-bundle",
+            "--app-id=other-app",
+            "--uid=other",
+            ELEMENTAL_CHAT_HAPP,
+        ],
+    )
+    .await;
+
+    let out = call_json(port, &["list-apps"]).await;
+    assert_eq!(out["command"], "list-apps");
+    assert_eq!(app_status(&out, "test-app"), "running");
+    assert_eq!(app_status(&out, "other-app"), "running");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "Figure out how to get holochain bin in CI"]
+async fn disable_and_enable_app() {
+    observability::test_run().ok();
+    let port: u16 = pick_unused_port().expect("No ports free");
+    let app_port: u16 = pick_unused_port().expect("No ports free");
+    let _hc_admin = generate_and_run(port, app_port);
+    tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+
+    call_json(port, &["disable-app", "test-app"]).await;
+    let out = call_json(port, &["list-apps"]).await;
+    assert!(app_status(&out, "test-app").get("disabled").is_some());
+
+    call_json(port, &["enable-app", "test-app"]).await;
+    let out = call_json(port, &["list-apps"]).await;
+    assert_eq!(app_status(&out, "test-app"), "running");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "Figure out how to get holochain bin in CI"]
+async fn dump_state_to_file() {
+    use holochain_types::prelude::{AgentPubKey, DnaHash};
+    observability::test_run().ok();
+    let port: u16 = pick_unused_port().expect("No ports free");
+    let app_port: u16 = pick_unused_port().expect("No ports free");
+    let _hc_admin = generate_and_run(port, app_port);
+    tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+
+    let out = call_json(port, &["list-cells"]).await;
+    let cell = out["cells"][0].clone();
+    let dna = DnaHash::from_raw_39(serde_json::from_value(cell[0].clone()).unwrap()).unwrap();
+    let agent = AgentPubKey::from_raw_39(serde_json::from_value(cell[1].clone()).unwrap()).unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("state.json");
+    call(
+        port,
+        &[
+            "dump-state",
+            &dna.to_string(),
+            &agent.to_string(),
+            "--filter=[0].peer_dump",
+            &format!("--output-file={}", path.display()),
+        ],
+    )
+    .await;
+
+    let peer_dump: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    assert_eq!(peer_dump["this_dna"][0], cell[0]);
+    assert_eq!(peer_dump["this_agent"][0], cell[1]);
+}
@@ -30,3 +30,40 @@ pub fn get_agent_activity(
 pub fn query(filter: ChainQueryFilter) -> ExternResult<Vec<Element>> {
     HDK.with(|h| h.borrow().query(filter))
 }
+
+/// Count the elements that [ `query` ] would return for this filter,
+/// ignoring its offset and limit, without fetching them.
+///
+/// Together with [ `ChainQueryFilter::offset` ] and [ `ChainQueryFilter::limit` ]
+/// this allows paging through a large source chain.
+pub fn query_count(filter: ChainQueryFilter) -> ExternResult<u32> {
+    HDK.with(|h| h.borrow().query_count(filter))
+}
+
+/// Returns at most `limit` elements matching the filter which come after the
+/// element with `after_hash` on the source chain, or from genesis if
+/// `after_hash` is `None`. The filter's offset and limit are ignored.
+///
+/// Passing the hash of the last element of each page as the next `after_hash`
+/// pages through the chain without gaps or duplicates, even if the chain grows
+/// between pages, which is not the case when paging with an offset.
+pub fn query_after(
+    filter: ChainQueryFilter,
+    after_hash: Option<HeaderHash>,
+    limit: u32,
+) -> ExternResult<Vec<Element>> {
+    HDK.with(|h| {
+        h.borrow()
+            .query_after(QueryAfterInput::new(filter, after_hash, limit))
+    })
+}
+
+/// The validation receipts this agent has received for one of its published ops.
+///
+/// Each receipt says how a remote validator judged the op, so comparing the
+/// number of receipts against the entry def's `required_validations` tells
+/// whether an op has been validated by enough peers yet.
+/// Ops which haven't been validated by anyone return no receipts.
+pub fn get_validation_receipts(op_hash: DhtOpHash) -> ExternResult<Vec<ValidationReceipt>> {
+    HDK.with(|h| h.borrow().get_validation_receipts(op_hash))
+}
@@ -29,6 +29,9 @@ pub trait HdkT: Send + Sync {
         get_agent_activity_input: GetAgentActivityInput,
     ) -> ExternResult<AgentActivity>;
     fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Element>>;
+    fn query_count(&self, filter: ChainQueryFilter) -> ExternResult<u32>;
+    fn query_after(&self, query_after_input: QueryAfterInput) -> ExternResult<Vec<Element>>;
+    fn get_validation_receipts(&self, op_hash: DhtOpHash) -> ExternResult<Vec<ValidationReceipt>>;
     // Ed25519
     fn sign(&self, sign: Sign) -> ExternResult<Signature>;
     fn sign_ephemeral(&self, sign_ephemeral: SignEphemeral) -> ExternResult<EphemeralSignatures>;
@@ -71,11 +74,14 @@ pub trait HdkT: Send + Sync {
     fn call(&self, call: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>>;
     fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
     fn remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()>;
+    fn get_peers(&self, dna_hash: Option<DnaHash>) -> ExternResult<Vec<AgentPubKey>>;
+    fn create_clone_cell(&self, input: CreateCloneCellInput) -> ExternResult<CellId>;
     // Random
     fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
     // Time
     fn sys_time(&self, sys_time_input: ()) -> ExternResult<Timestamp>;
     fn schedule(&self, scheduled_fn: String) -> ExternResult<()>;
+    fn unschedule(&self, scheduled_fn: String) -> ExternResult<()>;
     fn sleep(&self, wake_after: std::time::Duration) -> ExternResult<()>;
     // Trace
     fn trace(&self, trace_msg: TraceMsg) -> ExternResult<()>;
@@ -117,6 +123,15 @@ impl HdkT for ErrHdk {
     fn query(&self, _: ChainQueryFilter) -> ExternResult<Vec<Element>> {
         Self::err()
     }
+    fn query_count(&self, _: ChainQueryFilter) -> ExternResult<u32> {
+        Self::err()
+    }
+    fn query_after(&self, _: QueryAfterInput) -> ExternResult<Vec<Element>> {
+        Self::err()
+    }
+    fn get_validation_receipts(&self, _: DhtOpHash) -> ExternResult<Vec<ValidationReceipt>> {
+        Self::err()
+    }
     fn sign(&self, _: Sign) -> ExternResult<Signature> {
         Self::err()
     }
@@ -195,6 +210,12 @@ impl HdkT for ErrHdk {
     fn remote_signal(&self, _: RemoteSignal) -> ExternResult<()> {
         Self::err()
     }
+    fn get_peers(&self, _: Option<DnaHash>) -> ExternResult<Vec<AgentPubKey>> {
+        Self::err()
+    }
+    fn create_clone_cell(&self, _: CreateCloneCellInput) -> ExternResult<CellId> {
+        Self::err()
+    }
     // Random
     fn random_bytes(&self, _: u32) -> ExternResult<Bytes> {
         Self::err()
@@ -206,6 +227,9 @@ impl HdkT for ErrHdk {
     fn schedule(&self, _: String) -> ExternResult<()> {
         Self::err()
     }
+    fn unschedule(&self, _: String) -> ExternResult<()> {
+        Self::err()
+    }
     fn sleep(&self, _: std::time::Duration) -> ExternResult<()> {
         Self::err()
     }
@@ -264,6 +288,15 @@ impl HdkT for HostHdk {
     fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Element>> {
         host_call::<ChainQueryFilter, Vec<Element>>(__query, filter)
     }
+    fn query_count(&self, filter: ChainQueryFilter) -> ExternResult<u32> {
+        host_call::<ChainQueryFilter, u32>(__query_count, filter)
+    }
+    fn query_after(&self, query_after_input: QueryAfterInput) -> ExternResult<Vec<Element>> {
+        host_call::<QueryAfterInput, Vec<Element>>(__query_after, query_after_input)
+    }
+    fn get_validation_receipts(&self, op_hash: DhtOpHash) -> ExternResult<Vec<ValidationReceipt>> {
+        host_call::<DhtOpHash, Vec<ValidationReceipt>>(__get_validation_receipts, op_hash)
+    }
     fn sign(&self, sign: Sign) -> ExternResult<Signature> {
         host_call::<Sign, Signature>(__sign, sign)
     }
@@ -358,6 +391,12 @@ impl HdkT for HostHdk {
     fn remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()> {
         host_call::<RemoteSignal, ()>(__remote_signal, remote_signal)
     }
+    fn get_peers(&self, dna_hash: Option<DnaHash>) -> ExternResult<Vec<AgentPubKey>> {
+        host_call::<Option<DnaHash>, Vec<AgentPubKey>>(__get_peers, dna_hash)
+    }
+    fn create_clone_cell(&self, input: CreateCloneCellInput) -> ExternResult<CellId> {
+        host_call::<CreateCloneCellInput, CellId>(__create_clone_cell, input)
+    }
     fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes> {
         host_call::<u32, Bytes>(__random_bytes, number_of_bytes)
     }
@@ -367,6 +406,9 @@ impl HdkT for HostHdk {
     fn schedule(&self, scheduled_fn: String) -> ExternResult<()> {
         host_call::<String, ()>(__schedule, scheduled_fn)
     }
+    fn unschedule(&self, scheduled_fn: String) -> ExternResult<()> {
+        host_call::<String, ()>(__unschedule, scheduled_fn)
+    }
     fn sleep(&self, wake_after: std::time::Duration) -> ExternResult<()> {
         host_call::<std::time::Duration, ()>(__sleep, wake_after)
     }
@@ -141,3 +141,30 @@ where
         })
     })
 }
+
+/// ## Get Peers
+/// List the agents this cell knows about on the network.
+///
+/// These are the agents in the conductor's p2p agent store for the dna
+/// of the calling cell, not including the calling agent. Passing
+/// `Some(dna_hash)` for any other dna is an error.
+///
+/// The list is only as complete as the peer discovery done so far,
+/// so an empty list does not mean there are no other agents.
+pub fn get_peers(dna_hash: Option<DnaHash>) -> ExternResult<Vec<AgentPubKey>> {
+    HDK.with(|h| h.borrow().get_peers(dna_hash))
+}
+
+/// ## Create Clone Cell
+/// Clone the cell filling `role_id` in the calling app, returning the
+/// [`CellId`] of the new cell.
+///
+/// The clone uses the same agent as the role it is cloned from but gets a
+/// fresh uid, so it has its own dna hash and network. Pass `Some(properties)`
+/// in the input to also run the clone with different DNA properties.
+///
+/// The role must have been installed with a `clone_limit` that leaves room
+/// for another clone, otherwise the host returns an error.
+pub fn create_clone_cell(input: CreateCloneCellInput) -> ExternResult<CellId> {
+    HDK.with(|h| h.borrow().create_clone_cell(input))
+}
@@ -5,7 +5,10 @@ pub use crate::capability::delete_cap_grant;
 pub use crate::capability::generate_cap_secret;
 pub use crate::capability::update_cap_grant;
 pub use crate::chain::get_agent_activity;
+pub use crate::chain::get_validation_receipts;
 pub use crate::chain::query;
+pub use crate::chain::query_after;
+pub use crate::chain::query_count;
 pub use crate::countersigning::accept_countersigning_preflight_request;
 pub use crate::countersigning::session_times_from_millis;
 pub use crate::ed25519::sign;
@@ -52,13 +55,16 @@ pub use crate::map_extern::ExternResult;
 pub use crate::map_extern_infallible;
 pub use crate::p2p::call;
 pub use crate::p2p::call_remote;
+pub use crate::p2p::create_clone_cell;
 pub use crate::p2p::emit_signal;
+pub use crate::p2p::get_peers;
 pub use crate::p2p::remote_signal;
 pub use crate::random::*;
 pub use crate::register_entry;
 pub use crate::time::schedule;
 pub use crate::time::sleep;
 pub use crate::time::sys_time;
+pub use crate::time::unschedule;
 pub use crate::time::*;
 pub use crate::x_salsa20_poly1305::create_x25519_keypair;
 pub use crate::x_salsa20_poly1305::x_25519_x_salsa20_poly1305_decrypt;
@@ -70,6 +76,8 @@ pub use hdk_derive::hdk_extern;
 pub use holo_hash;
 pub use holo_hash::AgentPubKey;
 pub use holo_hash::AnyDhtHash;
+pub use holo_hash::DhtOpHash;
+pub use holo_hash::DnaHash;
 pub use holo_hash::EntryHash;
 pub use holo_hash::EntryHashes;
 pub use holo_hash::HasHash;
@@ -118,16 +126,22 @@ macro_rules! holochain_externs {
             __must_get_header,
             __accept_countersigning_preflight_request,
             __query,
+            __query_count,
+            __query_after,
+            __get_validation_receipts,
             __call_remote,
             __call,
             __create,
             __emit_signal,
             __remote_signal,
+            __get_peers,
+            __create_clone_cell,
             __create_link,
             __delete_link,
             __update,
             __delete,
             __schedule,
+            __unschedule,
             __sleep,
             __x_salsa20_poly1305_encrypt,
             __x_salsa20_poly1305_decrypt,
@@ -24,6 +24,8 @@ use crate::prelude::*;
 /// PRNG from there.
 ///
 /// See the rand rust crate
+///
+/// A single call can ask for at most 64 KiB, larger requests are an error.
 pub fn random_bytes(number_of_bytes: u32) -> ExternResult<Bytes> {
     HDK.with(|h| h.borrow().random_bytes(number_of_bytes))
 }
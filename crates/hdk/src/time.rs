@@ -65,11 +65,22 @@ pub fn sys_time() -> ExternResult<Timestamp> {
     HDK.with(|h| h.borrow().sys_time(()))
 }
 
-/// @todo Not implemented
+/// Schedule a function in the current zome to be called by the conductor.
+///
+/// The function is called with the `Option<Schedule>` it last returned and
+/// decides its own next run by what it returns, `None` stops it.
 pub fn schedule(scheduled_fn: &str) -> ExternResult<()> {
     HDK.with(|h| h.borrow().schedule(String::from(scheduled_fn)))
 }
 
+/// Unschedule a function in the current zome so the conductor no longer calls it.
+///
+/// This cancels both persisted and ephemeral schedules. Unscheduling a
+/// function that is not scheduled does nothing.
+pub fn unschedule(scheduled_fn: &str) -> ExternResult<()> {
+    HDK.with(|h| h.borrow().unschedule(String::from(scheduled_fn)))
+}
+
 /// @todo Not implemented
 pub fn sleep(wake_after: std::time::Duration) -> ExternResult<()> {
     HDK.with(|h| h.borrow().sleep(wake_after))
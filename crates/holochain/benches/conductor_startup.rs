@@ -0,0 +1,57 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+use holochain::sweettest::*;
+use holochain_wasm_test_utils::TestWasm;
+use tokio::runtime::Builder;
+use tokio::runtime::Runtime;
+
+criterion_group!(benches, conductor_startup);
+
+criterion_main!(benches);
+
+/// Compares starting a conductor and installing an app on disk and in memory.
+fn conductor_startup(bench: &mut Criterion) {
+    observability::test_run().ok();
+    let mut group = bench.benchmark_group("conductor_startup");
+    group.sample_size(
+        std::env::var_os("BENCH_SAMPLE_SIZE")
+            .and_then(|s| s.to_string_lossy().parse::<usize>().ok())
+            .unwrap_or(20),
+    );
+    let runtime = rt();
+
+    let (dna_file, _) = runtime
+        .block_on(SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]))
+        .unwrap();
+
+    group.bench_function("on_disk", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                let mut conductor = SweetConductor::from_standard_config().await;
+                conductor
+                    .setup_app("app", &[dna_file.clone()])
+                    .await
+                    .unwrap();
+            })
+        });
+    });
+    group.bench_function("in_memory", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                let mut conductor = SweetConductor::from_standard_config_in_memory().await;
+                conductor
+                    .setup_app("app", &[dna_file.clone()])
+                    .await
+                    .unwrap();
+            })
+        });
+    });
+    group.finish();
+    runtime.shutdown_background();
+}
+
+pub fn rt() -> Runtime {
+    Builder::new_multi_thread().enable_all().build().unwrap()
+}
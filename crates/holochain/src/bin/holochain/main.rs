@@ -61,6 +61,20 @@ struct Opt {
         help = "Display version information such as git revision and HDK version"
     )]
     build_info: bool,
+
+    #[structopt(
+        long,
+        help = "Check every database for corruption before starting the conductor,
+    and refuse to start if any check fails"
+    )]
+    verify_db_integrity: bool,
+
+    #[structopt(
+        long,
+        help = "The most gossip rounds to initiate per second.
+    Overrides max_gossip_rounds_per_second in the conductor config"
+    )]
+    max_gossip_rps: Option<f64>,
 }
 
 fn main() {
@@ -193,7 +207,7 @@ async fn conductor_handle_from_config_path(opt: &Opt) -> ConductorHandle {
     let config_path: ConfigFilePath = config_path.map(Into::into).unwrap_or_default();
     debug!("config_path: {}", config_path);
 
-    let config: ConductorConfig = if opt.interactive {
+    let mut config: ConductorConfig = if opt.interactive {
         // Load config, offer to create default config if missing
         interactive::load_config_or_prompt_for_default(config_path)
             .expect("Could not load conductor config")
@@ -204,6 +218,9 @@ async fn conductor_handle_from_config_path(opt: &Opt) -> ConductorHandle {
     } else {
         load_config(&config_path, config_path_default)
     };
+    if let Some(max_gossip_rps) = opt.max_gossip_rps {
+        config.max_gossip_rounds_per_second = Some(max_gossip_rps);
+    }
 
     // read the passphrase to prepare for usage,
     // but we don't have any keystore config types that use this yet.
@@ -241,6 +258,10 @@ async fn conductor_handle_from_config_path(opt: &Opt) -> ConductorHandle {
         }
     }
 
+    if opt.verify_db_integrity {
+        verify_db_integrity(&env_path);
+    }
+
     // Initialize the Conductor
     Conductor::builder()
         .config(config)
@@ -250,6 +271,24 @@ async fn conductor_handle_from_config_path(opt: &Opt) -> ConductorHandle {
         .expect("Could not initialize Conductor from configuration")
 }
 
+/// Run a quick integrity check on every database, exiting if any are corrupt.
+fn verify_db_integrity(env_path: &std::path::Path) {
+    match holochain_sqlite::conn::quick_check_all(env_path) {
+        Ok(failed) if failed.is_empty() => info!("All databases passed the integrity check."),
+        Ok(failed) => {
+            println!("Error: The following databases failed the integrity check:");
+            for path in failed {
+                println!("    {}", path.display());
+            }
+            std::process::exit(ERROR_CODE);
+        }
+        Err(e) => {
+            println!("Couldn't check database integrity: {}", e);
+            std::process::exit(ERROR_CODE);
+        }
+    }
+}
+
 /// Load config, throw friendly error on failure
 fn load_config(config_path: &ConfigFilePath, config_path_default: bool) -> ConductorConfig {
     match ConductorConfig::load_yaml(config_path.as_ref()) {
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use super::error::ConductorApiError;
 use super::error::ConductorApiResult;
+use crate::conductor::error::ConductorError;
 use crate::conductor::interface::SignalBroadcaster;
 use crate::conductor::ConductorHandle;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitArgs;
@@ -14,6 +15,7 @@ use holochain_conductor_api::ZomeCall;
 use holochain_keystore::MetaLairClient;
 use holochain_state::host_fn_workspace::SourceChainWorkspace;
 use holochain_types::prelude::*;
+use kitsune_p2p::agent_store::AgentInfoSigned;
 use tokio::sync::mpsc::error::SendError;
 use tokio::sync::mpsc::OwnedPermit;
 use tracing::*;
@@ -169,6 +171,12 @@ pub trait CellConductorReadHandleT: Send + Sync {
 
     /// Get a zome from this cell's Dna
     fn get_zome(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> ConductorApiResult<Zome>;
+
+    /// Get the agent infos in the p2p agent store for this cell's Dna
+    async fn get_agent_infos(&self) -> ConductorApiResult<Vec<AgentInfoSigned>>;
+
+    /// Clone a cell of the running app this cell belongs to
+    async fn create_clone_cell(&self, input: CreateCloneCellInput) -> ConductorApiResult<CellId>;
 }
 
 #[async_trait]
@@ -194,4 +202,40 @@ impl CellConductorReadHandleT for CellConductorApi {
     fn get_zome(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> ConductorApiResult<Zome> {
         CellConductorApiT::get_zome(self, dna_hash, zome_name)
     }
+
+    async fn get_agent_infos(&self) -> ConductorApiResult<Vec<AgentInfoSigned>> {
+        self.conductor_handle
+            .get_space_agent_infos(self.cell_id.dna_hash())
+            .await
+    }
+
+    async fn create_clone_cell(&self, input: CreateCloneCellInput) -> ConductorApiResult<CellId> {
+        let CreateCloneCellInput {
+            role_id,
+            properties,
+        } = input;
+        let properties = properties
+            .map(YamlProperties::try_from)
+            .transpose()
+            .map_err(super::error::SerializationError::from)?;
+        let state = self.conductor_handle.get_state_from_handle().await?;
+        let (installed_app_id, app) = state
+            .running_apps()
+            .find(|(_, app)| app.all_cells().any(|cell_id| *cell_id == self.cell_id))
+            .ok_or_else(|| ConductorError::CellMissing(self.cell_id.clone()))?;
+        let role = app.role(&role_id)?;
+        let payload = CreateCloneCellPayload {
+            properties,
+            dna_hash: role.dna_hash().clone(),
+            agent_key: role.agent_key().clone(),
+            installed_app_id: installed_app_id.clone(),
+            role_id,
+            membrane_proof: None,
+        };
+        Ok(self
+            .conductor_handle
+            .clone()
+            .create_clone_cell(payload)
+            .await?)
+    }
 }
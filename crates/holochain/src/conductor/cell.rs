@@ -728,22 +728,11 @@ impl Cell {
         self.space
             .dht_env
             .async_commit(move |txn| {
-                // Get the current count for this dhtop.
-                let receipt_count: usize = txn.query_row(
-                    "SELECT COUNT(rowid) FROM ValidationReceipt WHERE op_hash = :op_hash",
-                    named_params! {
-                        ":op_hash": receipt.receipt.dht_op_hash,
-                    },
-                    |row| row.get(0),
-                )?;
-
-                // If we have enough receipts then set receipts to complete.
-                if receipt_count >= required_validation_count as usize {
-                    set_receipts_complete(txn, &receipt.receipt.dht_op_hash, true)?;
-                }
-
-                // Add to receipts db
-                validation_receipts::add_if_unique(txn, receipt)
+                validation_receipts::add_and_check_complete(
+                    txn,
+                    receipt,
+                    required_validation_count as usize,
+                )
             })
             .await?;
 
@@ -47,6 +47,7 @@ use crate::core::ribosome::guest_callback::post_commit::PostCommitArgs;
 use crate::core::ribosome::guest_callback::post_commit::POST_COMMIT_CHANNEL_BOUND;
 use crate::core::ribosome::guest_callback::post_commit::POST_COMMIT_CONCURRENT_LIMIT;
 use crate::core::ribosome::RibosomeT;
+use crate::core::workflow::sys_validation_workflow::PendingRetryConfig;
 use crate::{
     conductor::api::error::ConductorApiResult, core::ribosome::real_ribosome::RealRibosome,
 };
@@ -72,6 +73,7 @@ use holochain_state::mutations;
 use holochain_state::prelude::from_blob;
 use holochain_state::prelude::StateMutationResult;
 use holochain_state::prelude::StateQueryResult;
+use holochain_state::source_chain::SourceChain;
 use holochain_types::prelude::*;
 pub use holochain_types::share;
 use rusqlite::{OptionalExtension, Transaction};
@@ -179,6 +181,12 @@ where
     queue_consumer_map: QueueConsumerMap,
 
     post_commit: tokio::sync::mpsc::Sender<PostCommitArgs>,
+
+    /// Check each cell's source chain for forks before starting it.
+    verify_chain_integrity_on_startup: bool,
+
+    /// Whether source chains may be pruned.
+    pub(super) allow_pruning: bool,
 }
 
 impl Conductor {
@@ -460,8 +468,8 @@ where
         self.dna_store.share_mut(|d| d.add_entry_defs(entry_defs));
     }
 
-    pub(super) fn register_phenotype(&self, dna: DnaFile) {
-        self.dna_store.share_mut(|d| d.add_dna(dna));
+    pub(super) fn register_phenotype(&self, dna: DnaFile) -> Result<(), DnaError> {
+        self.dna_store.share_mut(|d| d.add_dna(dna))
     }
 
     pub(super) fn get_queue_consumer_workflows(&self) -> QueueConsumerMap {
@@ -666,6 +674,20 @@ where
                     .map_err(|e| CellError::FailedToCreateDnaSpace(e.into()))
                     .map_err(|err| (cell_id.clone(), err))?;
 
+                if self.verify_chain_integrity_on_startup {
+                    SourceChain::new(
+                        space.authored_env.clone(),
+                        space.dht_env.clone(),
+                        self.keystore.clone(),
+                        cell_id.agent_pubkey().clone(),
+                    )
+                    .await
+                    .map_err(|err| (cell_id.clone(), err.into()))?
+                    .verify_integrity()
+                    .await
+                    .map_err(|err| (cell_id.clone(), err.into()))?;
+                }
+
                 Cell::create(
                     cell_id.clone(),
                     conductor_handle,
@@ -798,7 +820,7 @@ where
                 .modify_phenotype(random_uid(), properties)
         })?;
         let child_dna_hash = child_dna.dna_hash().to_owned();
-        self.register_phenotype(child_dna);
+        self.register_phenotype(child_dna)?;
         let (_, cell_id) = self
             .update_state_prime(move |mut state| {
                 if let Some(app) = state.installed_apps_mut().get_mut(&app_id) {
@@ -1277,6 +1299,10 @@ where
         root_env_dir: EnvironmentRootPath,
         holochain_p2p: holochain_p2p::HolochainP2pRef,
         db_sync_level: DbSyncStrategy,
+        wal_checkpoint_interval: Option<std::time::Duration>,
+        pending_retry: PendingRetryConfig,
+        verify_chain_integrity_on_startup: bool,
+        allow_pruning: bool,
         post_commit: tokio::sync::mpsc::Sender<PostCommitArgs>,
     ) -> ConductorResult<Self> {
         let queue_consumer_map = QueueConsumerMap::new();
@@ -1286,6 +1312,8 @@ where
             spaces: Spaces::new(
                 root_env_dir.clone(),
                 db_sync_level,
+                wal_checkpoint_interval,
+                pending_retry,
                 queue_consumer_map.clone(),
             ),
             cells: RwShare::new(HashMap::new()),
@@ -1299,24 +1327,13 @@ where
             holochain_p2p,
             queue_consumer_map,
             post_commit,
+            verify_chain_integrity_on_startup,
+            allow_pruning,
         })
     }
 
     pub(super) async fn get_state(&self) -> ConductorResult<ConductorState> {
-        self.conductor_env
-            .async_reader(|txn| {
-                let state = txn
-                    .query_row("SELECT blob FROM ConductorState WHERE id = 1", [], |row| {
-                        row.get("blob")
-                    })
-                    .optional()?;
-                let state = match state {
-                    Some(state) => from_blob(state)?,
-                    None => ConductorState::default(),
-                };
-                Ok(state)
-            })
-            .await
+        read_conductor_state(&self.conductor_env).await
     }
 
     /// Update the internal state with a pure function mapping old state to new
@@ -1376,6 +1393,26 @@ where
     }
 }
 
+/// Read the conductor state from a conductor database,
+/// which doesn't require the conductor to be running.
+pub(crate) async fn read_conductor_state(
+    env: &DbWrite<DbKindConductor>,
+) -> ConductorResult<ConductorState> {
+    env.async_reader(|txn| {
+        let state = txn
+            .query_row("SELECT blob FROM ConductorState WHERE id = 1", [], |row| {
+                row.get("blob")
+            })
+            .optional()?;
+        let state = match state {
+            Some(state) => from_blob(state)?,
+            None => ConductorState::default(),
+        };
+        Ok(state)
+    })
+    .await
+}
+
 mod builder {
     use super::*;
     use crate::conductor::dna_store::RealDnaStore;
@@ -1499,10 +1536,7 @@ mod builder {
                 dna_store, config, ..
             } = self;
 
-            let network_config = match &config.network {
-                None => holochain_p2p::kitsune_p2p::KitsuneP2pConfig::default(),
-                Some(config) => config.clone(),
-            };
+            let network_config = network_config(&config);
             let (cert_digest, cert, cert_priv_key) =
                 keystore.get_or_create_first_tls_cert().await?;
             let tls_config =
@@ -1525,6 +1559,12 @@ mod builder {
                 env_path,
                 holochain_p2p,
                 config.db_sync_strategy,
+                config
+                    .wal_checkpoint_interval_secs
+                    .map(std::time::Duration::from_secs),
+                (&config).into(),
+                config.verify_chain_integrity_on_startup,
+                config.allow_pruning,
                 post_commit_sender,
             )
             .await?;
@@ -1548,6 +1588,10 @@ mod builder {
                 p2p_batch_senders: Arc::new(parking_lot::Mutex::new(HashMap::new())),
                 p2p_metrics_env: Arc::new(parking_lot::Mutex::new(HashMap::new())),
 
+                #[cfg(any(test, feature = "test_utils"))]
+                p2p_blocked_agents: Default::default(),
+                #[cfg(any(test, feature = "test_utils"))]
+                in_memory_dbs: false,
                 #[cfg(any(test, feature = "test_utils"))]
                 dev_settings: parking_lot::RwLock::new(DevSettings::default()),
             });
@@ -1668,13 +1712,13 @@ mod builder {
             self.config.environment_path = envs.path().to_path_buf().into();
 
             let (holochain_p2p, p2p_evt) =
-                holochain_p2p::spawn_holochain_p2p(self.config.network.clone().unwrap_or_default(), holochain_p2p::kitsune_p2p::dependencies::kitsune_p2p_types::tls::TlsConfig::new_ephemeral().await.unwrap())
+                holochain_p2p::spawn_holochain_p2p(network_config(&self.config), holochain_p2p::kitsune_p2p::dependencies::kitsune_p2p_types::tls::TlsConfig::new_ephemeral().await.unwrap())
                     .await?;
 
             let (post_commit_sender, post_commit_receiver) =
                 tokio::sync::mpsc::channel(POST_COMMIT_CHANNEL_BOUND);
 
-            let conductor = Conductor::new(
+            let mut conductor = Conductor::new(
                 envs.conductor(),
                 envs.wasm(),
                 self.dna_store,
@@ -1682,10 +1726,22 @@ mod builder {
                 self.config.environment_path.clone(),
                 holochain_p2p,
                 self.config.db_sync_strategy,
+                self.config
+                    .wal_checkpoint_interval_secs
+                    .map(std::time::Duration::from_secs),
+                (&self.config).into(),
+                self.config.verify_chain_integrity_on_startup,
+                self.config.allow_pruning,
                 post_commit_sender,
             )
             .await?;
 
+            // With in-memory environments the per-DNA databases are kept in memory too.
+            // They don't survive a restart of the conductor.
+            if envs.is_in_memory() {
+                conductor.spaces.keep_dbs_in_memory();
+            }
+
             let conductor = Self::update_fake_state(self.state, conductor).await?;
 
             // Get data before handle
@@ -1700,9 +1756,12 @@ mod builder {
                 holochain_p2p,
                 p2p_env: envs.p2p(),
                 p2p_batch_senders: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+                p2p_blocked_agents: Default::default(),
                 p2p_metrics_env: envs.p2p_metrics(),
                 db_sync_strategy: self.config.db_sync_strategy,
                 #[cfg(any(test, feature = "test_utils"))]
+                in_memory_dbs: envs.is_in_memory(),
+                #[cfg(any(test, feature = "test_utils"))]
                 dev_settings: parking_lot::RwLock::new(DevSettings::default()),
             });
 
@@ -1719,6 +1778,18 @@ mod builder {
             Self::finish(handle, self.config, p2p_evt, post_commit_receiver).await
         }
     }
+
+    /// The network config of this conductor, with the conductor level
+    /// overrides of the tuning params applied.
+    fn network_config(config: &ConductorConfig) -> holochain_p2p::kitsune_p2p::KitsuneP2pConfig {
+        let mut network_config = config.network.clone().unwrap_or_default();
+        if let Some(max_rounds) = config.max_gossip_rounds_per_second {
+            let mut tuning_params = (*network_config.tuning_params).clone();
+            tuning_params.gossip_max_rounds_per_second = max_rounds;
+            network_config.tuning_params = Arc::new(tuning_params);
+        }
+        network_config
+    }
 }
 
 #[instrument(skip(p2p_evt, handle))]
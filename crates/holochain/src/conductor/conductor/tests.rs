@@ -41,6 +41,10 @@ async fn can_update_state() {
         envs.path().to_path_buf().into(),
         holochain_p2p,
         DbSyncStrategy::default(),
+        None,
+        Default::default(),
+        false,
+        false,
         post_commit_sender,
     )
     .await
@@ -90,6 +94,10 @@ async fn can_add_clone_cell_to_app() {
         envs.path().to_path_buf().into(),
         holochain_p2p,
         DbSyncStrategy::default(),
+        None,
+        Default::default(),
+        false,
+        false,
         post_commit_sender,
     )
     .await
@@ -108,7 +116,7 @@ async fn can_add_clone_cell_to_app() {
         vec![&"role_id".to_string()]
     );
 
-    conductor.register_phenotype(dna);
+    conductor.register_phenotype(dna).unwrap();
     conductor
         .update_state(move |mut state| {
             state
@@ -165,6 +173,10 @@ async fn app_ids_are_unique() {
         environments.path().to_path_buf().into(),
         holochain_p2p,
         DbSyncStrategy::default(),
+        None,
+        Default::default(),
+        false,
+        false,
         post_commit_sender,
     )
     .await
@@ -982,3 +994,43 @@ async fn test_init_concurrency() {
     assert_eq!(num_calls_clone.fetch_add(0, Ordering::SeqCst), 100);
     assert_eq!(num_inits_clone.fetch_add(0, Ordering::SeqCst), 1);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn prune_source_chain_requires_allow_pruning() {
+    observability::test_run().ok();
+    let (dna, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create])
+        .await
+        .unwrap();
+
+    // Pruning is refused by default.
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (cell,) = conductor
+        .setup_app("app", &[dna.clone()])
+        .await
+        .unwrap()
+        .into_tuple();
+    let result = conductor
+        .handle()
+        .prune_source_chain(cell.cell_id(), Timestamp::now())
+        .await;
+    assert_matches!(
+        result,
+        Err(ConductorApiError::ConductorError(e)) if matches!(*e, ConductorError::PruningNotAllowed)
+    );
+
+    let mut conductor = SweetConductor::from_config(ConductorConfig {
+        allow_pruning: true,
+        ..Default::default()
+    })
+    .await;
+    let (cell,) = conductor
+        .setup_app("app", &[dna])
+        .await
+        .unwrap()
+        .into_tuple();
+    conductor
+        .handle()
+        .prune_source_chain(cell.cell_id(), Timestamp::now())
+        .await
+        .unwrap();
+}
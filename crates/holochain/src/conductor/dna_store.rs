@@ -1,6 +1,6 @@
 use holochain_types::prelude::*;
 use holochain_zome_types::entry_def::EntryDef;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::*;
 
 /// Placeholder for real dna store
@@ -8,12 +8,30 @@ use tracing::*;
 pub struct RealDnaStore {
     dnas: HashMap<DnaHash, DnaFile>,
     entry_defs: HashMap<EntryDefBufferKey, EntryDef>,
+    add_hook: Option<AddHook>,
+}
+
+/// A check run on every DnaFile before it is added to a [`RealDnaStore`]
+struct AddHook(Box<dyn Fn(&DnaFile) -> Result<(), DnaError> + Send + Sync>);
+
+impl std::fmt::Debug for AddHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AddHook").finish()
+    }
 }
 
 impl DnaStore for RealDnaStore {
     #[instrument]
-    fn add_dna(&mut self, dna: DnaFile) {
+    fn add_dna(&mut self, dna: DnaFile) -> Result<(), DnaError> {
+        self.validate_on_add(&dna)?;
         self.dnas.insert(dna.dna_hash().clone(), dna);
+        Ok(())
+    }
+    fn validate_on_add(&self, dna: &DnaFile) -> Result<(), DnaError> {
+        match &self.add_hook {
+            Some(AddHook(hook)) => hook(dna),
+            None => Ok(()),
+        }
     }
     fn add_dnas<T: IntoIterator<Item = (DnaHash, DnaFile)> + 'static>(&mut self, dnas: T) {
         self.dnas.extend(dnas);
@@ -42,6 +60,37 @@ impl DnaStore for RealDnaStore {
     fn get_entry_def(&self, k: &EntryDefBufferKey) -> Option<EntryDef> {
         self.entry_defs.get(k).cloned()
     }
+    #[instrument]
+    fn remove_dna(&mut self, hash: &DnaHash) {
+        if let Some(removed) = self.dnas.remove(hash) {
+            // Zomes can be shared between DNAs so only remove
+            // entry defs that no remaining DNA refers to.
+            let still_used: HashSet<&ZomeDef> = self
+                .dnas
+                .values()
+                .flat_map(|dna| dna.dna_def().zomes.iter().map(|(_, zome)| zome))
+                .collect();
+            let removed_zomes: HashSet<&ZomeDef> = removed
+                .dna_def()
+                .zomes
+                .iter()
+                .map(|(_, zome)| zome)
+                .filter(|zome| !still_used.contains(zome))
+                .collect();
+            self.entry_defs
+                .retain(|k, _| !removed_zomes.contains(&k.zome));
+        }
+    }
+    fn clear(&mut self) {
+        self.dnas.clear();
+        self.entry_defs.clear();
+    }
+    fn list_entry_defs(&self) -> Vec<(EntryDefBufferKey, EntryDef)> {
+        self.entry_defs
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
 }
 
 impl RealDnaStore {
@@ -49,6 +98,229 @@ impl RealDnaStore {
         RealDnaStore {
             dnas: HashMap::new(),
             entry_defs: HashMap::new(),
+            add_hook: None,
         }
     }
+
+    /// Check every DnaFile with `hook` before it is added with `add_dna`.
+    /// A DnaFile the hook returns an error for is not added, and `add_dna`
+    /// returns the error. This replaces any previous hook.
+    pub fn set_add_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&DnaFile) -> Result<(), DnaError> + Send + Sync + 'static,
+    {
+        self.add_hook = Some(AddHook(Box::new(hook)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_types::test_utils::fake_dna_zomes;
+
+    fn entry_def_key(dna: &DnaFile) -> EntryDefBufferKey {
+        EntryDefBufferKey::new(dna.dna_def().zomes[0].1.clone(), 0.into())
+    }
+
+    #[test]
+    fn remove_dna_keeps_others() {
+        let a = fake_dna_zomes("a", vec![("zome_a".into(), vec![1].into())]);
+        let b = fake_dna_zomes("b", vec![("zome_b".into(), vec![2].into())]);
+        let mut store = RealDnaStore::new();
+        store.add_dna(a.clone()).unwrap();
+        store.add_dna(b.clone()).unwrap();
+        store.add_entry_def(entry_def_key(&a), EntryDef::default_with_id("a"));
+        store.add_entry_def(entry_def_key(&b), EntryDef::default_with_id("b"));
+
+        store.remove_dna(a.dna_hash());
+
+        assert_eq!(DnaStore::list(&store), vec![b.dna_hash().clone()]);
+        assert!(DnaStore::get_dna_file(&store, a.dna_hash()).is_none());
+        assert!(store.get_entry_def(&entry_def_key(&a)).is_none());
+        assert!(store.get_entry_def(&entry_def_key(&b)).is_some());
+
+        store.clear();
+
+        assert!(DnaStore::list(&store).is_empty());
+        assert!(store.get_entry_def(&entry_def_key(&b)).is_none());
+    }
+
+    #[test]
+    fn zome_defs_and_entry_defs() {
+        let a = fake_dna_zomes("a", vec![("zome_a".into(), vec![1].into())]);
+        let b = fake_dna_zomes(
+            "b",
+            vec![
+                ("zome_b1".into(), vec![2].into()),
+                ("zome_b2".into(), vec![3].into()),
+            ],
+        );
+        let mut store = RealDnaStore::new();
+        store.add_dna(a.clone()).unwrap();
+        store.add_dna(b.clone()).unwrap();
+        store.add_entry_def(entry_def_key(&a), EntryDef::default_with_id("a"));
+        store.add_entry_def(entry_def_key(&b), EntryDef::default_with_id("b"));
+
+        let zome_b2 = DnaStore::get_zome_def(&store, b.dna_hash(), &"zome_b2".into());
+        assert_eq!(zome_b2.as_ref(), Some(&b.dna_def().zomes[1].1));
+        assert!(DnaStore::get_zome_def(&store, a.dna_hash(), &"zome_b2".into()).is_none());
+
+        let mut entry_defs = DnaStore::list_entry_defs(&store);
+        entry_defs.sort_by_key(|(k, _)| k.clone());
+        let mut expected = vec![
+            (entry_def_key(&a), EntryDef::default_with_id("a")),
+            (entry_def_key(&b), EntryDef::default_with_id("b")),
+        ];
+        expected.sort_by_key(|(k, _)| k.clone());
+        assert_eq!(entry_defs, expected);
+
+        // The mock serves the same zome defs.
+        let mock = MockDnaStore::multi_dna(vec![a, b.clone()], 0, 0);
+        assert_eq!(DnaStore::list(&mock).len(), 2);
+        assert_eq!(
+            DnaStore::get_zome_def(&mock, b.dna_hash(), &"zome_b2".into()),
+            zome_b2
+        );
+    }
+
+    #[test]
+    fn remove_dna_keeps_shared_entry_defs() {
+        let a = fake_dna_zomes("a", vec![("zome".into(), vec![1].into())]);
+        let b = fake_dna_zomes("b", vec![("zome".into(), vec![1].into())]);
+        let mut store = RealDnaStore::new();
+        store.add_dna(a.clone()).unwrap();
+        store.add_dna(b.clone()).unwrap();
+        store.add_entry_def(entry_def_key(&a), EntryDef::default_with_id("shared"));
+
+        store.remove_dna(a.dna_hash());
+
+        assert!(store.get_entry_def(&entry_def_key(&b)).is_some());
+    }
+
+    #[test]
+    fn get_or_fetch_fetches_each_dna_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let a = fake_dna_zomes("a", vec![("zome_a".into(), vec![1].into())]);
+        let b = fake_dna_zomes("b", vec![("zome_b".into(), vec![2].into())]);
+        let mut store = RealDnaStore::new();
+        store.add_dna(a.clone()).unwrap();
+
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let fetch = |dna: Option<DnaFile>| {
+            let fetches = fetches.clone();
+            move |_: &DnaHash| {
+                fetches.fetch_add(1, Ordering::SeqCst);
+                dna.map(|dna| {
+                    let entry_defs = vec![(entry_def_key(&dna), EntryDef::default_with_id("b"))];
+                    (dna, entry_defs)
+                })
+            }
+        };
+
+        // Already in the store.
+        assert_eq!(store.get_or_fetch(a.dna_hash(), fetch(None)), Some(a));
+        assert_eq!(fetches.load(Ordering::SeqCst), 0);
+
+        // A failed fetch adds nothing.
+        assert_eq!(store.get_or_fetch(b.dna_hash(), fetch(None)), None);
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert!(DnaStore::get_dna_file(&store, b.dna_hash()).is_none());
+        assert!(store.get_entry_def(&entry_def_key(&b)).is_none());
+
+        for _ in 0..3 {
+            assert_eq!(
+                store.get_or_fetch(b.dna_hash(), fetch(Some(b.clone()))),
+                Some(b.clone())
+            );
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+        // The fetched entry defs are registered along with the DnaFile.
+        assert_eq!(
+            store.get_entry_def(&entry_def_key(&b)),
+            Some(EntryDef::default_with_id("b"))
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_or_fetch_async_fetches_each_dna_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let a = fake_dna_zomes("a", vec![("zome_a".into(), vec![1].into())]);
+        let mut store = RealDnaStore::new();
+
+        let fetches = Arc::new(AtomicUsize::new(0));
+        for _ in 0..3 {
+            let fetches = fetches.clone();
+            let dna = a.clone();
+            let fetched = store
+                .get_or_fetch_async(a.dna_hash(), move |_| async move {
+                    fetches.fetch_add(1, Ordering::SeqCst);
+                    let entry_defs = vec![(entry_def_key(&dna), EntryDef::default_with_id("a"))];
+                    Some((dna, entry_defs))
+                })
+                .await;
+            assert_eq!(fetched, Some(a.clone()));
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            store.get_entry_def(&entry_def_key(&a)),
+            Some(EntryDef::default_with_id("a"))
+        );
+    }
+
+    #[test]
+    fn mock_multi_dna_lists_every_dna() {
+        let a = fake_dna_zomes("a", vec![("zome_a".into(), vec![1].into())]);
+        let b = fake_dna_zomes("b", vec![("zome_b".into(), vec![2].into())]);
+        let mut mock = MockDnaStore::multi_dna(vec![a.clone(), b.clone()], 1, 1);
+
+        let mut hashes = DnaStore::list(&mock);
+        hashes.sort();
+        let mut expected = vec![a.dna_hash().clone(), b.dna_hash().clone()];
+        expected.sort();
+        assert_eq!(hashes, expected);
+        assert_eq!(DnaStore::get_dna_file(&mock, a.dna_hash()), Some(a.clone()));
+        assert_eq!(
+            DnaStore::get_dna_def(&mock, b.dna_hash()).as_ref(),
+            Some(b.dna_def())
+        );
+
+        // The mock checks the expected number of calls when it is dropped.
+        mock.add_dnas(vec![(a.dna_hash().clone(), a.clone())]);
+        mock.add_entry_defs(vec![(entry_def_key(&a), EntryDef::default_with_id("a"))]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn add_hook_rejects_dna_missing_required_properties() {
+        let mut store = RealDnaStore::new();
+        store.set_add_hook(|dna| {
+            let properties = YamlProperties::try_from(dna.dna_def().properties.clone())
+                .map_err(|e| DnaError::Invalid(e.to_string()))?;
+            match properties.into_inner().get("admin") {
+                Some(_) => Ok(()),
+                None => Err(DnaError::Invalid("missing admin property".into())),
+            }
+        });
+        let without_admin = fake_dna_zomes("a", vec![("zome".into(), vec![1].into())]);
+        let with_admin = fake_dna_zomes("b", vec![("zome".into(), vec![1].into())])
+            .with_properties(
+                YamlProperties::new(serde_yaml::from_str("admin: alice").unwrap())
+                    .try_into()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            store.add_dna(without_admin.clone()),
+            Err(DnaError::Invalid(_))
+        ));
+        assert!(DnaStore::get_dna_file(&store, without_admin.dna_hash()).is_none());
+
+        store.add_dna(with_admin.clone()).unwrap();
+        assert_eq!(DnaStore::list(&store), vec![with_admin.dna_hash().clone()]);
+    }
 }
@@ -88,6 +88,9 @@ pub enum ConductorError {
     #[error("Tried to perform an operation on an app that was not running: {0}")]
     AppNotRunning(InstalledAppId),
 
+    #[error("Tried to prune a source chain but pruning is not allowed by the conductor config")]
+    PruningNotAllowed,
+
     #[error(transparent)]
     HolochainP2pError(#[from] holochain_p2p::HolochainP2pError),
 
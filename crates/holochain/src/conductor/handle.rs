@@ -39,6 +39,7 @@ use super::api::error::ConductorApiResult;
 use super::api::ZomeCall;
 use super::conductor::CellStatus;
 use super::config::AdminInterfaceConfig;
+use super::error::ConductorError;
 use super::error::ConductorResult;
 use super::interface::SignalBroadcaster;
 use super::manager::spawn_task_manager;
@@ -327,6 +328,12 @@ pub trait ConductorHandleT: Send + Sync {
         cell_id: Option<CellId>,
     ) -> ConductorApiResult<Vec<AgentInfoSigned>>;
 
+    /// Get every signed agent info stored for a dna's network space
+    async fn get_space_agent_infos(
+        &self,
+        dna_hash: &DnaHash,
+    ) -> ConductorApiResult<Vec<AgentInfoSigned>>;
+
     /// Print the current setup in a machine readable way.
     fn print_setup(&self);
 
@@ -334,6 +341,15 @@ pub trait ConductorHandleT: Send + Sync {
     /// allowing individual Cells to be shut down.
     async fn remove_cells(&self, cell_ids: &[CellId]);
 
+    /// Prune headers authored before the cutoff from a cell's source chain,
+    /// returning how many were removed.
+    /// Fails unless `allow_pruning` is set in the conductor config.
+    async fn prune_source_chain(
+        &self,
+        cell_id: &CellId,
+        cutoff: Timestamp,
+    ) -> ConductorApiResult<u32>;
+
     /// Inject elements into a source chain for a cell.
     async fn insert_elements_into_source_chain(
         self: Arc<Self>,
@@ -351,6 +367,13 @@ pub trait ConductorHandleT: Send + Sync {
     #[cfg(any(test, feature = "test_utils"))]
     fn get_dht_env(&self, cell_id: &DnaHash) -> ConductorApiResult<DbWrite<DbKindDht>>;
 
+    /// Retrieve the validation override for this dna. FOR TESTING ONLY.
+    #[cfg(any(test, feature = "test_utils"))]
+    fn get_validation_override(
+        &self,
+        dna_hash: &DnaHash,
+    ) -> ConductorApiResult<crate::core::ValidationOverride>;
+
     /// Retrieve the database for this cell. FOR TESTING ONLY.
     #[cfg(any(test, feature = "test_utils"))]
     fn get_cache_env(&self, cell_id: &CellId) -> ConductorApiResult<DbWrite<DbKindCache>>;
@@ -359,6 +382,11 @@ pub trait ConductorHandleT: Send + Sync {
     #[cfg(any(test, feature = "test_utils"))]
     fn get_p2p_env(&self, space: Arc<KitsuneSpace>) -> DbWrite<DbKindP2pAgentStore>;
 
+    /// Stop storing agent infos for these agents in the p2p databases, replacing
+    /// any previous set. Infos which are already stored are kept. FOR TESTING ONLY.
+    #[cfg(any(test, feature = "test_utils"))]
+    fn set_p2p_blocked_agents(&self, agents: HashSet<Arc<kitsune_p2p::KitsuneAgent>>);
+
     /// Retrieve Senders for triggering workflows. FOR TESTING ONLY.
     #[cfg(any(test, feature = "test_utils"))]
     fn get_cell_triggers(&self, cell_id: &CellId) -> ConductorApiResult<QueueTriggers>;
@@ -489,6 +517,14 @@ pub struct ConductorHandleImpl<DS: DnaStore + 'static> {
     pub(super) p2p_batch_senders:
         Arc<parking_lot::Mutex<HashMap<Arc<KitsuneSpace>, tokio::sync::mpsc::Sender<P2pBatch>>>>,
 
+    /// Agents whose info is not written to the p2p database. FOR TESTING ONLY.
+    #[cfg(any(test, feature = "test_utils"))]
+    pub(super) p2p_blocked_agents: p2p_agent_store::BlockedAgents,
+
+    /// Keep the p2p databases in memory. FOR TESTING ONLY.
+    #[cfg(any(test, feature = "test_utils"))]
+    pub(super) in_memory_dbs: bool,
+
     // This is only available in tests currently, but could be extended to
     // normal usage.
     #[cfg(any(test, feature = "test_utils"))]
@@ -558,8 +594,12 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
     }
 
     async fn register_dna(&self, dna: DnaFile) -> ConductorResult<()> {
+        // Check the dna before its wasm is stored.
+        self.conductor
+            .dna_store()
+            .share_ref(|ds| ds.validate_on_add(&dna))?;
         self.register_genotype(dna.clone()).await?;
-        self.conductor.register_phenotype(dna);
+        self.conductor.register_phenotype(dna)?;
         Ok(())
     }
 
@@ -1270,6 +1310,14 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         }
     }
 
+    async fn get_space_agent_infos(
+        &self,
+        dna_hash: &DnaHash,
+    ) -> ConductorApiResult<Vec<AgentInfoSigned>> {
+        let env = self.p2p_env(dna_hash.to_kitsune());
+        Ok(all_agent_infos(env.into()).await?)
+    }
+
     fn print_setup(&self) {
         self.conductor.print_setup()
     }
@@ -1278,6 +1326,25 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         self.conductor.remove_cells(cell_ids.to_vec()).await
     }
 
+    async fn prune_source_chain(
+        &self,
+        cell_id: &CellId,
+        cutoff: Timestamp,
+    ) -> ConductorApiResult<u32> {
+        if !self.conductor.allow_pruning {
+            return Err(ConductorError::PruningNotAllowed.into());
+        }
+        let space = self.conductor.get_or_create_space(cell_id.dna_hash())?;
+        let chain = source_chain::SourceChain::new(
+            space.authored_env.clone(),
+            space.dht_env.clone(),
+            self.keystore.clone(),
+            cell_id.agent_pubkey().clone(),
+        )
+        .await?;
+        Ok(chain.prune_before(cutoff).await?)
+    }
+
     async fn insert_elements_into_source_chain(
         self: Arc<Self>,
         cell_id: CellId,
@@ -1449,6 +1516,17 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         Ok(self.conductor.get_or_create_dht_env(dna_hash)?)
     }
 
+    #[cfg(any(test, feature = "test_utils"))]
+    fn get_validation_override(
+        &self,
+        dna_hash: &DnaHash,
+    ) -> ConductorApiResult<crate::core::ValidationOverride> {
+        Ok(self
+            .conductor
+            .get_or_create_space(dna_hash)?
+            .validation_override)
+    }
+
     #[cfg(any(test, feature = "test_utils"))]
     fn get_cache_env(&self, cell_id: &CellId) -> ConductorApiResult<DbWrite<DbKindCache>> {
         let cell = self.cell_by_id(cell_id)?;
@@ -1460,6 +1538,11 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         self.p2p_env(space)
     }
 
+    #[cfg(any(test, feature = "test_utils"))]
+    fn set_p2p_blocked_agents(&self, agents: HashSet<Arc<kitsune_p2p::KitsuneAgent>>) {
+        *self.p2p_blocked_agents.write() = agents;
+    }
+
     #[cfg(any(test, feature = "test_utils"))]
     fn get_cell_triggers(&self, cell_id: &CellId) -> ConductorApiResult<QueueTriggers> {
         let cell = self.cell_by_id(cell_id)?;
@@ -1659,6 +1742,11 @@ impl<DS: DnaStore + 'static> ConductorHandleImpl<DS> {
         p2p_env
             .entry(space.clone())
             .or_insert_with(move || {
+                #[cfg(any(test, feature = "test_utils"))]
+                if self.in_memory_dbs {
+                    return DbWrite::open_in_memory(DbKindP2pAgentStore(space))
+                        .expect("failed to open p2p_agent_store database");
+                }
                 let root_env_dir = self.root_env_dir.as_ref();
                 DbWrite::open_with_sync_level(
                     root_env_dir,
@@ -1683,7 +1771,11 @@ impl<DS: DnaStore + 'static> ConductorHandleImpl<DS> {
             .or_insert_with(|| {
                 let (tx, rx) = tokio::sync::mpsc::channel(100);
                 let env = { self.p2p_env(space) };
-                tokio::spawn(p2p_agent_store::p2p_put_all_batch(env, rx));
+                #[cfg(any(test, feature = "test_utils"))]
+                let blocked = Some(self.p2p_blocked_agents.clone());
+                #[cfg(not(any(test, feature = "test_utils")))]
+                let blocked = None;
+                tokio::spawn(p2p_agent_store::p2p_put_all_batch(env, rx, blocked));
                 tx
             })
             .clone()
@@ -1695,6 +1787,11 @@ impl<DS: DnaStore + 'static> ConductorHandleImpl<DS> {
         p2p_metrics_env
             .entry(space.clone())
             .or_insert_with(move || {
+                #[cfg(any(test, feature = "test_utils"))]
+                if self.in_memory_dbs {
+                    return DbWrite::open_in_memory(DbKindP2pMetrics(space))
+                        .expect("failed to open p2p_metrics database");
+                }
                 let root_env_dir = self.root_env_dir.as_ref();
                 DbWrite::open_with_sync_level(
                     root_env_dir,
@@ -19,6 +19,11 @@ use thiserror::Error;
 
 use super::error::ConductorResult;
 
+/// Agents whose information must not be stored in the peer store.
+/// Only ever set in tests.
+pub type BlockedAgents =
+    Arc<parking_lot::RwLock<std::collections::HashSet<Arc<kitsune_p2p::KitsuneAgent>>>>;
+
 /// A set of agent information that are to be committed
 /// with any other active batches.
 pub struct P2pBatch {
@@ -46,24 +51,33 @@ pub async fn inject_agent_infos<'iter, I: IntoIterator<Item = &'iter AgentInfoSi
 }
 
 /// Inject multiple agent info entries into the peer store in batches.
+/// Entries for `blocked` agents are skipped.
 pub async fn p2p_put_all_batch(
     env: DbWrite<DbKindP2pAgentStore>,
     rx: tokio::sync::mpsc::Receiver<P2pBatch>,
+    blocked: Option<BlockedAgents>,
 ) {
     let stream = tokio_stream::wrappers::ReceiverStream::new(rx);
     let mut stream = stream.ready_chunks(100);
     while let Some(batch) = stream.next().await {
         let mut responses = Vec::with_capacity(batch.len());
         let (tx, rx) = tokio::sync::oneshot::channel();
+        let blocked = blocked.clone();
         let result = env
             .async_commit(move |txn| {
+                // The block is checked inside the write transaction so an agent
+                // can't be stored after it is blocked and its info removed.
+                let blocked = blocked.as_ref().map(|b| b.read());
                 'batch: for P2pBatch {
                     peer_data: batch,
                     result_sender: response,
                 } in batch
                 {
-                    for info in batch {
-                        match p2p_put_single(txn, &info) {
+                    for info in batch.iter() {
+                        if blocked.as_ref().map_or(false, |b| b.contains(&info.agent)) {
+                            continue;
+                        }
+                        match p2p_put_single(txn, info) {
                             Ok(_) => (),
                             Err(e) => {
                                 responses.push((Err(e), response));
@@ -2,14 +2,14 @@
 //! at the level of a [`DnaHash`] space.
 //! Multiple [`Cell`]'s could share the same space.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use holo_hash::{DhtOpHash, DnaHash};
 use holochain_conductor_api::conductor::EnvironmentRootPath;
 use holochain_p2p::dht_arc::{ArcInterval, DhtArcSet};
 use holochain_sqlite::{
-    conn::{DbSyncLevel, DbSyncStrategy},
-    db::{DbKindAuthored, DbKindCache, DbKindDht, DbWrite, ReadAccess},
+    conn::{DbSyncLevel, DbSyncStrategy, WalCheckpointMode},
+    db::{DbKindAuthored, DbKindCache, DbKindDht, DbKindT, DbWrite, ReadAccess},
     prelude::DatabaseResult,
 };
 use holochain_state::prelude::{from_blob, StateQueryResult};
@@ -29,6 +29,7 @@ use crate::core::{
         incoming_dht_ops_workflow::{
             incoming_dht_ops_workflow, IncomingOpHashes, IncomingOpsBatch,
         },
+        sys_validation_workflow::PendingRetryConfig,
     },
 };
 
@@ -43,8 +44,13 @@ pub struct Spaces {
     map: RwShare<HashMap<DnaHash, Space>>,
     root_env_dir: Arc<EnvironmentRootPath>,
     db_sync_level: DbSyncStrategy,
+    wal_checkpoint_interval: Option<Duration>,
+    pending_retry: PendingRetryConfig,
     /// The map of running queue consumer workflows.
     queue_consumer_map: QueueConsumerMap,
+    /// Keep the databases of new spaces in memory. FOR TESTING ONLY.
+    #[cfg(any(test, feature = "test_utils"))]
+    in_memory_dbs: bool,
 }
 
 #[derive(Clone)]
@@ -79,6 +85,13 @@ pub struct Space {
 
     /// Incoming ops batch for this space.
     pub incoming_ops_batch: IncomingOpsBatch,
+
+    /// How sys validation retries ops that are pending on a dependency.
+    pub pending_retry: PendingRetryConfig,
+
+    /// Forces the validation status of ops in this space. FOR TESTING ONLY.
+    #[cfg(any(test, feature = "test_utils"))]
+    pub validation_override: crate::core::ValidationOverride,
 }
 
 #[cfg(test)]
@@ -98,14 +111,36 @@ impl Spaces {
     pub fn new(
         root_env_dir: EnvironmentRootPath,
         db_sync_level: DbSyncStrategy,
+        wal_checkpoint_interval: Option<Duration>,
+        pending_retry: PendingRetryConfig,
         queue_consumer_map: QueueConsumerMap,
     ) -> Self {
         Spaces {
             map: RwShare::new(HashMap::new()),
             root_env_dir: Arc::new(root_env_dir),
             db_sync_level,
+            wal_checkpoint_interval,
+            pending_retry,
             queue_consumer_map,
+            #[cfg(any(test, feature = "test_utils"))]
+            in_memory_dbs: false,
+        }
+    }
+
+    /// Keep the databases of spaces created from now on in memory. FOR TESTING ONLY.
+    #[cfg(any(test, feature = "test_utils"))]
+    pub(crate) fn keep_dbs_in_memory(&mut self) {
+        self.in_memory_dbs = true;
+    }
+
+    /// The directory the databases of new spaces are created in,
+    /// or `None` if they are kept in memory.
+    fn space_env_dir(&self) -> Option<&EnvironmentRootPath> {
+        #[cfg(any(test, feature = "test_utils"))]
+        if self.in_memory_dbs {
+            return None;
         }
+        Some(&self.root_env_dir)
     }
 
     /// Get the space if it exists or create it if it doesn't.
@@ -129,8 +164,10 @@ impl Spaces {
                     std::collections::hash_map::Entry::Vacant(entry) => {
                         let space = Space::new(
                             Arc::new(dna_hash.clone()),
-                            &self.root_env_dir,
+                            self.space_env_dir(),
                             self.db_sync_level,
+                            self.wal_checkpoint_interval,
+                            self.pending_retry,
                         )?;
 
                         let r = f(&space);
@@ -378,29 +415,36 @@ impl Spaces {
 impl Space {
     fn new(
         dna_hash: Arc<DnaHash>,
-        root_env_dir: &EnvironmentRootPath,
+        root_env_dir: Option<&EnvironmentRootPath>,
         db_sync_strategy: DbSyncStrategy,
+        wal_checkpoint_interval: Option<Duration>,
+        pending_retry: PendingRetryConfig,
     ) -> ConductorResult<Self> {
-        let cache = DbWrite::open_with_sync_level(
-            root_env_dir.as_ref(),
+        let cache = open_space_db(
+            root_env_dir,
             DbKindCache(dna_hash.clone()),
             match db_sync_strategy {
                 DbSyncStrategy::Fast => DbSyncLevel::Off,
                 DbSyncStrategy::Resilient => DbSyncLevel::Normal,
             },
+            wal_checkpoint_interval,
         )?;
-        let authored_env = DbWrite::open_with_sync_level(
-            root_env_dir.as_ref(),
-            DbKindAuthored(dna_hash.clone()),
-            DbSyncLevel::Normal,
+        let authored_kind = DbKindAuthored(dna_hash.clone());
+        let authored_sync_level = authored_kind.preferred_sync_level();
+        let authored_env = open_space_db(
+            root_env_dir,
+            authored_kind,
+            authored_sync_level,
+            wal_checkpoint_interval,
         )?;
-        let dht_env = DbWrite::open_with_sync_level(
-            root_env_dir.as_ref(),
+        let dht_env = open_space_db(
+            root_env_dir,
             DbKindDht(dna_hash.clone()),
             match db_sync_strategy {
                 DbSyncStrategy::Fast => DbSyncLevel::Off,
                 DbSyncStrategy::Resilient => DbSyncLevel::Normal,
             },
+            wal_checkpoint_interval,
         )?;
         let countersigning_workspace = CountersigningWorkspace::new();
         let incoming_op_hashes = IncomingOpHashes::default();
@@ -415,11 +459,38 @@ impl Space {
             incoming_op_hashes,
             incoming_ops_batch,
             dht_query_cache,
+            pending_retry,
+            #[cfg(any(test, feature = "test_utils"))]
+            validation_override: Default::default(),
         };
         Ok(r)
     }
 }
 
+/// Open a database for a space, checkpointing its write-ahead log
+/// in the background if an interval is given.
+/// Without a root directory the database is kept in memory.
+fn open_space_db<Kind: DbKindT>(
+    root_env_dir: Option<&EnvironmentRootPath>,
+    kind: Kind,
+    sync_level: DbSyncLevel,
+    wal_checkpoint_interval: Option<Duration>,
+) -> DatabaseResult<DbWrite<Kind>> {
+    let root_env_dir = match root_env_dir {
+        Some(root_env_dir) => root_env_dir,
+        None => return DbWrite::open_in_memory(kind),
+    };
+    match wal_checkpoint_interval {
+        Some(interval) => {
+            let pool_config = kind
+                .pool_config()
+                .with_checkpoint_interval(interval, WalCheckpointMode::Passive);
+            DbWrite::open_with_pool_config(root_env_dir.as_ref(), kind, sync_level, pool_config)
+        }
+        None => DbWrite::open_with_sync_level(root_env_dir.as_ref(), kind, sync_level),
+    }
+}
+
 #[cfg(test)]
 impl TestSpaces {
     pub fn new(dna_hashes: impl IntoIterator<Item = DnaHash>) -> Self {
@@ -442,6 +513,8 @@ impl TestSpaces {
         let spaces = Spaces::new(
             temp_dir.path().to_path_buf().into(),
             Default::default(),
+            None,
+            Default::default(),
             queue_consumer_map.clone(),
         );
         spaces.map.share_mut(|map| {
@@ -470,7 +543,9 @@ impl TestSpace {
         Self {
             space: Space::new(
                 Arc::new(dna_hash),
-                &temp_dir.path().to_path_buf().into(),
+                Some(&temp_dir.path().to_path_buf().into()),
+                Default::default(),
+                None,
                 Default::default(),
             )
             .unwrap(),
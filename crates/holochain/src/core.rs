@@ -12,3 +12,5 @@ pub mod workflow;
 mod sys_validate;
 
 pub use sys_validate::*;
+#[cfg(any(test, feature = "test_utils"))]
+pub use validation::ValidationOverride;
@@ -166,15 +166,18 @@ pub async fn spawn_queue_consumer_tasks(
     // App validation
     // One per space.
     let (tx_app, handle) = queue_consumer_map.spawn_once_app_validation(dna_hash.clone(), || {
+        let workspace = AppValidationWorkspace::new(
+            authored_env.clone().into(),
+            dht_env.clone(),
+            cache.clone(),
+            keystore.clone(),
+            Arc::new(dna_def),
+        );
+        #[cfg(any(test, feature = "test_utils"))]
+        let workspace = workspace.with_validation_override(space.validation_override.clone());
         spawn_app_validation_consumer(
             dna_hash.clone(),
-            AppValidationWorkspace::new(
-                authored_env.clone().into(),
-                dht_env.clone(),
-                cache.clone(),
-                keystore.clone(),
-                Arc::new(dna_def),
-            ),
+            workspace,
             conductor_handle.clone(),
             stop.subscribe(),
             tx_integration.clone(),
@@ -24,7 +24,12 @@ pub fn spawn_sys_validation_consumer(
     trigger_app_validation: TriggerSender,
     network: HolochainP2pDna,
 ) -> (TriggerSender, JoinHandle<ManagedTaskResult>) {
-    let (tx, mut rx) = TriggerSender::new();
+    // If pending ops are retried with a backoff then loop at that interval
+    // while there are pending ops left. The workflow pauses the loop otherwise.
+    let (tx, mut rx) = match space.pending_retry.backoff {
+        Some(backoff) => TriggerSender::new_with_loop(backoff..backoff, false),
+        None => TriggerSender::new(),
+    };
     let trigger_self = tx.clone();
     let workspace = Arc::new(workspace);
     let space = Arc::new(space);
@@ -49,6 +49,12 @@ impl From<&PostCommitHostAccess> for HostFnAccess {
         // If you need to trigger some more writes, try a `call_remote` back
         // into the current cell.
         access.write_workspace = Permission::Deny;
+        // Scheduling is a workspace write too.
+        access.scheduling = Permission::Deny;
+        // Post commit has no handle back to the conductor to read peers from.
+        access.network = Permission::Deny;
+        // Nor does it have a handle to provision new cells with.
+        access.cell_provisioning = Permission::Deny;
         access
     }
 }
@@ -138,6 +144,9 @@ mod test {
             .unwrap();
         let mut expected = HostFnAccess::all();
         expected.write_workspace = Permission::Deny;
+        expected.scheduling = Permission::Deny;
+        expected.network = Permission::Deny;
+        expected.cell_provisioning = Permission::Deny;
         assert_eq!(HostFnAccess::from(&post_commit_host_access), expected);
     }
 
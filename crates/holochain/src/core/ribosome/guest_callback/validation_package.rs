@@ -186,12 +186,16 @@ mod test {
                 read_workspace: Deny,
                 read_workspace_deterministic: Allow,
                 write_workspace: Deny,
+                scheduling: Deny,
                 write_network: Deny,
+                network: Deny,
                 bindings: Deny,
                 bindings_deterministic: Deny,
+                random: Deny,
                 non_determinism: Deny,
                 keystore: Deny,
                 keystore_deterministic: Deny,
+                cell_provisioning: Deny,
             }
         );
     }
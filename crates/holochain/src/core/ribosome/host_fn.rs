@@ -73,6 +73,9 @@ host_fn_api_impls! {
     // Returns HeaderHash of the newly created element.
     fn create (zt::entry::CreateInput) -> holo_hash::HeaderHash;
 
+    // Clone a cell of the calling app, returning the CellId of the new clone.
+    fn create_clone_cell (zt::cell::CreateCloneCellInput) -> zt::cell::CellId;
+
     fn create_x25519_keypair(()) -> holochain_zome_types::x_salsa20_poly1305::x25519::X25519PubKey;
 
     fn x_salsa20_poly1305_encrypt(
@@ -111,6 +114,12 @@ host_fn_api_impls! {
 
     fn get_agent_activity (zt::agent_activity::GetAgentActivityInput) -> zt::query::AgentActivity;
 
+    // The validation receipts received for one of this agent's published ops.
+    fn get_validation_receipts (holo_hash::DhtOpHash) -> Vec<zt::validate::ValidationReceipt>;
+
+    // The agents known to the p2p agent store for a dna, defaulting to the current one.
+    fn get_peers (Option<holo_hash::DnaHash>) -> Vec<holo_hash::AgentPubKey>;
+
     fn get_details (Vec<zt::entry::GetInput>) -> Vec<Option<zt::metadata::Details>>;
 
     // Get links by entry hash from the cascade.
@@ -136,6 +145,12 @@ host_fn_api_impls! {
     // Query the source chain for data.
     fn query (zt::query::ChainQueryFilter) -> Vec<Element>;
 
+    // Count the elements a source chain query would return, ignoring paging.
+    fn query_count (zt::query::ChainQueryFilter) -> u32;
+
+    // Query the source chain for the elements after a header.
+    fn query_after (zt::query::QueryAfterInput) -> Vec<Element>;
+
     // the length of random bytes to create
     fn random_bytes (u32) -> zt::bytes::Bytes;
 
@@ -145,9 +160,12 @@ host_fn_api_impls! {
     // // @todo
     // fn send (()) -> ();
 
-    // @todo
+    // Schedule a schedulable function if it is not already.
     fn schedule (String) -> ();
 
+    // Unschedule a scheduled function so it is not dispatched again.
+    fn unschedule (String) -> ();
+
     // @todo
     fn sleep (core::time::Duration) -> ();
 
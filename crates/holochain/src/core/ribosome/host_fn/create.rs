@@ -269,6 +269,108 @@ pub mod wasm_test {
         assert_eq!(output.into_vec(), expected);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn count_entries_by_type_and_zome() {
+        observability::test_run().ok();
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let (dna, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create, TestWasm::Crd])
+            .await
+            .unwrap();
+
+        let app = conductor.setup_app("app", &[dna.clone()]).await.unwrap();
+        let (cell,) = app.into_tuple();
+
+        for _ in 0..3 {
+            let _: HeaderHash = conductor
+                .call(&cell.zome(TestWasm::Create), "create_entry", ())
+                .await;
+        }
+        let _: HeaderHash = conductor
+            .call(&cell.zome(TestWasm::Create), "create_msg", ())
+            .await;
+        for _ in 0..2 {
+            let _: HeaderHash = conductor
+                .call(&cell.zome(TestWasm::Crd), "create", ())
+                .await;
+        }
+
+        let chain = holochain_state::source_chain::SourceChain::new(
+            cell.authored_env().clone(),
+            cell.dht_env().clone(),
+            conductor.keystore(),
+            cell.agent_pubkey().clone(),
+        )
+        .await
+        .unwrap();
+
+        let by_type = chain.count_entries_by_type().await.unwrap();
+        assert_eq!(by_type.get(&EntryType::AgentPubKey), Some(&1));
+        assert_eq!(by_type.get(&EntryType::CapGrant), Some(&1));
+        let mut app_counts: Vec<u32> = by_type
+            .iter()
+            .filter(|(t, _)| matches!(t, EntryType::App(_)))
+            .map(|(_, c)| *c)
+            .collect();
+        app_counts.sort_unstable();
+        assert_eq!(app_counts, vec![1, 2, 3]);
+
+        let by_zome = chain.count_entries_by_zome(dna.dna_def()).await.unwrap();
+        assert_eq!(by_zome.len(), 2);
+        assert_eq!(by_zome.get(&TestWasm::Create.into()), Some(&4));
+        assert_eq!(by_zome.get(&TestWasm::Crd.into()), Some(&2));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_entries_for_type_only_returns_that_type() {
+        #[derive(Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
+        struct Post(String);
+
+        observability::test_run().ok();
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let (dna, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create])
+            .await
+            .unwrap();
+
+        let app = conductor.setup_app("app", &[dna]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let zome = cell.zome(TestWasm::Create);
+
+        let post_hash: HeaderHash = conductor.call(&zome, "create_entry", ()).await;
+        let _: HeaderHash = conductor.call(&zome, "create_entry", ()).await;
+        let _: HeaderHash = conductor.call(&zome, "create_msg", ()).await;
+        let _: HeaderHash = conductor.call(&zome, "create_priv_msg", ()).await;
+
+        let chain = holochain_state::source_chain::SourceChain::new(
+            cell.authored_env().clone(),
+            cell.dht_env().clone(),
+            conductor.keystore(),
+            cell.agent_pubkey().clone(),
+        )
+        .await
+        .unwrap();
+
+        let post_type = chain
+            .query(QueryFilter::new())
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|el| el.header_address() == &post_hash)
+            .and_then(|el| el.header().entry_type().cloned())
+            .unwrap();
+
+        let elements = chain
+            .get_entry_elements_for_type(post_type.clone())
+            .await
+            .unwrap();
+        assert_eq!(elements.len(), 2);
+        assert!(elements
+            .iter()
+            .all(|el| el.header().entry_type() == Some(&post_type)));
+
+        let posts: Vec<Post> = chain.get_entries_for_type(post_type).await.unwrap();
+        assert_eq!(posts, vec![Post("foo".into()), Post("foo".into())]);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_serialize_bytes_hash() {
         observability::test_run().ok();
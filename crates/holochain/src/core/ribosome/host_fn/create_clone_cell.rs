@@ -0,0 +1,125 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
+
+pub fn create_clone_cell(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: CreateCloneCellInput,
+) -> Result<CellId, WasmError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            cell_provisioning: Permission::Allow,
+            ..
+        } => {
+            let call_zome_handle = call_context.host_context.call_zome_handle();
+            tokio_helper::block_forever_on(call_zome_handle.create_clone_cell(input))
+                .map_err(|conductor_api_error| WasmError::Host(conductor_api_error.to_string()))
+        }
+        _ => Err(WasmError::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "create_clone_cell".into(),
+            )
+            .to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::conductor::api::error::ConductorApiResult;
+    use crate::sweettest::*;
+    use hdk::prelude::*;
+    use holochain_types::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use std::path::PathBuf;
+
+    /// Install an app with a single role which may be cloned once.
+    /// SweetConductor installs apps without a manifest, which disables cloning,
+    /// so this goes through an app bundle instead.
+    async fn install_cloneable_app(
+        conductor: &SweetConductor,
+        agent_key: AgentPubKey,
+        dna_file: DnaFile,
+    ) -> ConductorApiResult<CellId> {
+        let path = PathBuf::from(format!("{}", dna_file.dna_hash()));
+        let manifest = AppManifestCurrent {
+            name: "app".into(),
+            description: None,
+            roles: vec![AppRoleManifest {
+                id: "role".into(),
+                provisioning: Some(CellProvisioning::Create { deferred: false }),
+                dna: AppRoleDnaManifest {
+                    location: Some(DnaLocation::Bundled(path.clone())),
+                    properties: None,
+                    uid: None,
+                    version: None,
+                    clone_limit: 1,
+                },
+            }],
+        };
+        let resources = vec![(path, DnaBundle::from_dna_file(dna_file.clone()).await?)];
+        let bundle = AppBundle::new(manifest.into(), resources, PathBuf::from(".")).await?;
+        conductor
+            .inner_handle()
+            .install_app_bundle(InstallAppBundlePayload {
+                source: AppBundleSource::Bundle(bundle),
+                agent_key: agent_key.clone(),
+                installed_app_id: Some("app".into()),
+                membrane_proofs: Default::default(),
+                uid: None,
+            })
+            .await?;
+        conductor.enable_app("app".into()).await?;
+        conductor
+            .inner_handle()
+            .reconcile_cell_status_with_app_status()
+            .await?;
+        Ok(CellId::new(dna_file.dna_hash().clone(), agent_key))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn create_clone_cell_adds_clone_to_app() {
+        observability::test_run().ok();
+        let conductor = SweetConductor::from_standard_config().await;
+        let (dna_file, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::CloneCell])
+            .await
+            .unwrap();
+        let agent = SweetAgents::one(conductor.keystore()).await;
+        let cell_id = install_cloneable_app(&conductor, agent.clone(), dna_file)
+            .await
+            .unwrap();
+        let zome = SweetZome::new(cell_id.clone(), TestWasm::CloneCell.into());
+
+        let clone_id: CellId = conductor
+            .call(
+                &zome,
+                "create_clone_cell",
+                CreateCloneCellInput::new("role".into(), None),
+            )
+            .await;
+
+        assert_eq!(clone_id.agent_pubkey(), &agent);
+        assert_ne!(clone_id, cell_id);
+        let state = conductor.get_state_from_handle().await.unwrap();
+        let app = state.get_app(&"app".to_string()).unwrap();
+        assert_eq!(app.cloned_cells().collect::<Vec<_>>(), vec![&clone_id]);
+
+        // The role only allows a single clone.
+        let result: Result<CellId, _> = conductor
+            .call_fallible(
+                &zome,
+                "create_clone_cell",
+                CreateCloneCellInput::new("role".into(), None),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
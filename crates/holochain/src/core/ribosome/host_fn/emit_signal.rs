@@ -29,3 +29,38 @@ pub fn emit_signal(
         ).to_string()))
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use futures::StreamExt;
+    use hdk::prelude::*;
+    use holochain_types::signal::Signal;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    /// a signal emitted by a zome reaches the conductor's signal stream
+    async fn emit_signal_reaches_conductor_stream() {
+        observability::test_run().ok();
+        let RibosomeTestFixture {
+            mut conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::EmitSignal).await;
+        let mut signals = Box::pin(conductor.signals());
+
+        let payload = "hello clients".to_string();
+        let _: () = conductor.call(&alice, "emit_payload", payload.clone()).await;
+
+        let signal = tokio::time::timeout(std::time::Duration::from_secs(10), signals.next())
+            .await
+            .expect("Timed out waiting for the signal")
+            .expect("Signal stream ended");
+        assert_eq!(
+            Signal::App(
+                alice.cell_id().clone(),
+                AppSignal::new(ExternIO::encode(payload).unwrap())
+            ),
+            signal,
+        );
+    }
+}
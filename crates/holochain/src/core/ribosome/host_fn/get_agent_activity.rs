@@ -1,3 +1,4 @@
+use super::query::resolve_entry_type_str;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
 use holochain_cascade::Cascade;
@@ -9,7 +10,7 @@ use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
 
 pub fn get_agent_activity(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     input: GetAgentActivityInput,
 ) -> Result<AgentActivity, WasmError> {
@@ -17,9 +18,10 @@ pub fn get_agent_activity(
         HostFnAccess{ read_workspace: Permission::Allow, .. } => {
             let GetAgentActivityInput {
                 agent_pubkey,
-                chain_query_filter,
+                mut chain_query_filter,
                 activity_request,
             } = input;
+            resolve_entry_type_str(ribosome, call_context.clone(), &mut chain_query_filter)?;
             let options = match activity_request {
                 ActivityRequest::Status => GetActivityOptions {
                     include_valid_activity: false,
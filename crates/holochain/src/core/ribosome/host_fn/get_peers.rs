@@ -0,0 +1,103 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_p2p::AgentPubKeyExt;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
+
+pub fn get_peers(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: Option<DnaHash>,
+) -> Result<Vec<AgentPubKey>, WasmError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            network: Permission::Allow,
+            ..
+        } => {
+            let call_zome_handle = call_context.host_context.call_zome_handle();
+            let (dna_hash, agent) = call_zome_handle.cell_id().clone().into_dna_and_agent();
+            // A zome may only list the peers of its own dna.
+            if let Some(other) = input.filter(|filter| *filter != dna_hash) {
+                return Err(WasmError::Host(format!(
+                    "get_peers can only list the peers of the calling cell's dna, not {}",
+                    other
+                )));
+            }
+            let agent_infos = tokio_helper::block_forever_on(call_zome_handle.get_agent_infos())
+                .map_err(|conductor_api_error| WasmError::Host(conductor_api_error.to_string()))?;
+            Ok(agent_infos
+                .iter()
+                .map(|info| AgentPubKey::from_kitsune(&info.agent))
+                .filter(|peer| *peer != agent)
+                .collect())
+        }
+        _ => Err(WasmError::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_peers".into(),
+            )
+            .to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::sweettest::*;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_peers_after_exchange_peer_info() {
+        observability::test_run().ok();
+        let mut conductors = SweetConductorBatch::from_standard_config(3).await;
+        let (dna_file, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::GetPeers])
+            .await
+            .unwrap();
+        let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+        let ((alice,), (bob,), (carol,)) = apps.into_tuples();
+
+        // Each conductor only knows about its own agent before peer discovery.
+        let peers: Vec<AgentPubKey> = conductors[0]
+            .call(&alice.zome(TestWasm::GetPeers), "get_peers", ())
+            .await;
+        assert!(peers.is_empty());
+
+        conductors.exchange_peer_info().await;
+
+        let mut peers: Vec<AgentPubKey> = conductors[0]
+            .call(&alice.zome(TestWasm::GetPeers), "get_peers", ())
+            .await;
+        peers.sort();
+        let mut expected = vec![bob.agent_pubkey().clone(), carol.agent_pubkey().clone()];
+        expected.sort();
+        assert_eq!(peers, expected);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_peers_rejects_other_dnas() {
+        observability::test_run().ok();
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let (dna_file, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::GetPeers])
+            .await
+            .unwrap();
+        let app = conductor.setup_app("app", &[dna_file]).await.unwrap();
+        let (alice,) = app.into_tuple();
+        let zome = alice.zome(TestWasm::GetPeers);
+
+        let own_dna = alice.cell_id().dna_hash().clone();
+        let peers: Vec<AgentPubKey> = conductor.call(&zome, "get_peers_of", own_dna).await;
+        assert!(peers.is_empty());
+
+        let other_dna = DnaHash::from_raw_32(vec![0xdb; 32]);
+        let result: Result<Vec<AgentPubKey>, _> = conductor
+            .call_fallible(&zome, "get_peers_of", other_dna)
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,97 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
+
+pub fn get_validation_receipts(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: DhtOpHash,
+) -> Result<Vec<ValidationReceipt>, WasmError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            let receipts = call_context
+                .host_context
+                .workspace()
+                .validation_receipts(input)
+                .await
+                .map_err(|state_query_error| WasmError::Host(state_query_error.to_string()))?;
+            Ok(receipts
+                .into_iter()
+                .map(|signed_receipt| signed_receipt.receipt)
+                .collect())
+        }),
+        _ => Err(WasmError::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_validation_receipts".into(),
+            )
+            .to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::sweettest::*;
+    use crate::test_utils::consistency_10s;
+    use hdk::prelude::*;
+    use holochain_types::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use std::time::Duration;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_validation_receipts_for_authored_ops() {
+        observability::test_run().ok();
+        let (dna_file, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create])
+            .await
+            .unwrap();
+
+        let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+        let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+        conductors.exchange_peer_info().await;
+        let ((alice,), (bob,)) = apps.into_tuples();
+        let alice_zome = alice.zome(TestWasm::Create);
+
+        let hash: HeaderHash = conductors[0].call(&alice_zome, "create_entry", ()).await;
+        consistency_10s(&[&alice, &bob]).await;
+
+        let element: Option<Element> = conductors[0].call(&alice_zome, "get_post", hash).await;
+        let op_hashes: Vec<DhtOpHash> = produce_ops_from_element(&element.unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|op| DhtOpHash::with_data_sync(&op))
+            .collect();
+
+        // Receipts are sent after bob has integrated the ops, so they can lag behind consistency.
+        let mut receipts: Vec<ValidationReceipt> = Vec::new();
+        for _ in 0..100 {
+            receipts.clear();
+            for op_hash in op_hashes.iter() {
+                let op_receipts: Vec<ValidationReceipt> = conductors[0]
+                    .call(&alice_zome, "get_validation_receipts", op_hash.clone())
+                    .await;
+                receipts.extend(op_receipts);
+            }
+            if !receipts.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        assert!(!receipts.is_empty());
+        for receipt in receipts {
+            assert!(op_hashes.contains(&receipt.dht_op_hash));
+            assert_eq!(receipt.validation_status, ValidationStatus::Valid);
+            assert_eq!(receipt.validators, vec![bob.agent_pubkey().clone()]);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::create::extract_entry_def;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
@@ -7,15 +8,16 @@ use holochain_wasmer_host::prelude::WasmError;
 use std::sync::Arc;
 
 pub fn query(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
-    input: ChainQueryFilter,
+    mut input: ChainQueryFilter,
 ) -> Result<Vec<Element>, WasmError> {
     match HostFnAccess::from(&call_context.host_context()) {
         HostFnAccess {
             read_workspace: Permission::Allow,
             ..
         } => tokio_helper::block_forever_on(async move {
+            resolve_entry_type_str(ribosome, call_context.clone(), &mut input)?;
             let elements: Vec<Element> = call_context
                 .host_context
                 .workspace()
@@ -38,6 +40,35 @@ pub fn query(
     }
 }
 
+/// Resolve the entry def id of a query into the app entry type of the calling zome.
+pub(crate) fn resolve_entry_type_str(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: &mut ChainQueryFilter,
+) -> Result<(), WasmError> {
+    if let Some(entry_type_str) = input.entry_type_str.take() {
+        if input.entry_type.is_some() {
+            return Err(WasmError::Host(
+                "Cannot filter a query on both entry_type and entry_type_str".to_string(),
+            ));
+        }
+        let (entry_def_index, visibility) = extract_entry_def(
+            ribosome.clone(),
+            call_context.clone(),
+            EntryDefId::App(entry_type_str),
+        )?;
+        let zome_id = ribosome
+            .zome_to_id(&call_context.zome)
+            .expect("Failed to get ID for current zome");
+        input.entry_type = Some(EntryType::App(AppEntryType::new(
+            entry_def_index,
+            zome_id,
+            visibility,
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 #[cfg(feature = "slow_tests")]
 pub mod slow_tests {
@@ -62,4 +93,69 @@ pub mod slow_tests {
 
         assert_eq!(elements.len(), 6);
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn query_entry_type_str() {
+        observability::test_run().ok();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Query).await;
+
+        let _hash_a: EntryHash = conductor.call(&alice, "add_path", "a".to_string()).await;
+        let post_a: EntryHash = conductor.call(&alice, "add_post", "a".to_string()).await;
+        let _hash_b: EntryHash = conductor.call(&alice, "add_path", "b".to_string()).await;
+        let post_b: EntryHash = conductor.call(&alice, "add_post", "b".to_string()).await;
+
+        let posts: Vec<Element> = conductor
+            .call(
+                &alice,
+                "query",
+                ChainQueryFilter::new().entry_type_str("post"),
+            )
+            .await;
+        let post_hashes: Vec<EntryHash> = posts
+            .iter()
+            .filter_map(|el| el.header().entry_hash().cloned())
+            .collect();
+        assert_eq!(post_hashes, vec![post_a, post_b]);
+
+        let paths: Vec<Element> = conductor
+            .call(
+                &alice,
+                "query",
+                ChainQueryFilter::new().entry_type_str("hdk.path_entry"),
+            )
+            .await;
+        assert_eq!(paths.len(), 2);
+        assert!(paths
+            .iter()
+            .all(|el| el.header().entry_type() != posts[0].header().entry_type()));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_agent_activity_entry_type_str() {
+        observability::test_run().ok();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Query).await;
+
+        let _: AgentActivity = conductor
+            .call(
+                &alice,
+                "get_activity",
+                ChainQueryFilter::new().entry_type_str("post"),
+            )
+            .await;
+
+        // The id is resolved against the zome's entry defs,
+        // not passed on to the authorities as is.
+        let unknown: Result<AgentActivity, _> = conductor
+            .call_fallible(
+                &alice,
+                "get_activity",
+                ChainQueryFilter::new().entry_type_str("not_an_entry_type"),
+            )
+            .await;
+        assert!(unknown.is_err());
+    }
 }
@@ -0,0 +1,95 @@
+use super::query::resolve_entry_type_str;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
+
+pub fn query_after(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: QueryAfterInput,
+) -> Result<Vec<Element>, WasmError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            let QueryAfterInput {
+                mut filter,
+                after_hash,
+                limit,
+            } = input;
+            resolve_entry_type_str(ribosome, call_context.clone(), &mut filter)?;
+            call_context
+                .host_context
+                .workspace()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain to query the source chain")
+                .query_after(filter, after_hash, limit)
+                .await
+                .map_err(|source_chain_error| WasmError::Host(source_chain_error.to_string()))
+        }),
+        _ => Err(WasmError::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "query_after".into(),
+            )
+            .to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn query_after_pages() {
+        observability::test_run().ok();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Query).await;
+
+        let mut posts = Vec::new();
+        for i in 0..25 {
+            let hash: EntryHash = conductor.call(&alice, "add_post", i.to_string()).await;
+            posts.push(hash);
+        }
+
+        let filter = ChainQueryFilter::new().entry_type_str("post");
+        let mut paged = Vec::new();
+        let mut after_hash = None;
+        loop {
+            let elements: Vec<Element> = conductor
+                .call(
+                    &alice,
+                    "query_after",
+                    QueryAfterInput::new(filter.clone(), after_hash, 10),
+                )
+                .await;
+            // new posts between pages are picked up without skipping any
+            if paged.is_empty() {
+                let hash: EntryHash = conductor.call(&alice, "add_post", "new".to_string()).await;
+                posts.push(hash);
+            }
+            match elements.last() {
+                Some(last) => after_hash = Some(last.header_address().clone()),
+                None => break,
+            }
+            paged.extend(
+                elements
+                    .iter()
+                    .filter_map(|el| el.header().entry_hash().cloned()),
+            );
+        }
+        assert_eq!(paged, posts);
+    }
+}
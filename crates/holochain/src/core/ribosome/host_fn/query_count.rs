@@ -0,0 +1,80 @@
+use super::query::resolve_entry_type_str;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
+
+pub fn query_count(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    mut input: ChainQueryFilter,
+) -> Result<u32, WasmError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            resolve_entry_type_str(ribosome, call_context.clone(), &mut input)?;
+            call_context
+                .host_context
+                .workspace()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain to query the source chain")
+                .query_count(input)
+                .await
+                .map_err(|source_chain_error| WasmError::Host(source_chain_error.to_string()))
+        }),
+        _ => Err(WasmError::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "query_count".into(),
+            )
+            .to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn query_count_pages() {
+        observability::test_run().ok();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Query).await;
+
+        let mut posts = Vec::new();
+        for i in 0..100 {
+            let hash: EntryHash = conductor.call(&alice, "add_post", i.to_string()).await;
+            posts.push(hash);
+        }
+
+        let filter = ChainQueryFilter::new().entry_type_str("post");
+        let count: u32 = conductor.call(&alice, "query_count", filter.clone()).await;
+        assert_eq!(count, 100);
+
+        let mut paged = Vec::new();
+        for page in 0..10 {
+            let elements: Vec<Element> = conductor
+                .call(&alice, "query", filter.clone().offset(page * 10).limit(10))
+                .await;
+            assert_eq!(elements.len(), 10);
+            paged.extend(
+                elements
+                    .iter()
+                    .filter_map(|el| el.header().entry_hash().cloned()),
+            );
+        }
+        assert_eq!(paged, posts);
+    }
+}
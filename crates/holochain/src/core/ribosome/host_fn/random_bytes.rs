@@ -7,6 +7,9 @@ use std::sync::Arc;
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
 
+/// The most random bytes a single call can ask for.
+pub const MAX_RANDOM_BYTES: u32 = 64 * 1024;
+
 /// return n crypto secure random bytes from the standard holochain crypto lib
 pub fn random_bytes(
     _ribosome: Arc<impl RibosomeT>,
@@ -14,7 +17,13 @@ pub fn random_bytes(
     input: u32,
 ) -> Result<Bytes, WasmError> {
     match HostFnAccess::from(&call_context.host_context()) {
-        HostFnAccess{ non_determinism: Permission::Allow, .. } => {
+        HostFnAccess{ random: Permission::Allow, .. } => {
+            if input > MAX_RANDOM_BYTES {
+                return Err(WasmError::Host(format!(
+                    "Requested {} random bytes but the limit is {}",
+                    input, MAX_RANDOM_BYTES
+                )));
+            }
             let system_random = ring::rand::SystemRandom::new();
             let mut bytes = vec![0; input as _];
             system_random
@@ -35,6 +44,7 @@ pub fn random_bytes(
 #[cfg(feature = "slow_tests")]
 pub mod wasm_test {
     use crate::core::ribosome::host_fn::random_bytes::random_bytes;
+    use crate::core::ribosome::host_fn::random_bytes::MAX_RANDOM_BYTES;
 
     use crate::fixt::CallContextFixturator;
     use crate::fixt::RealRibosomeFixturator;
@@ -65,6 +75,25 @@ pub mod wasm_test {
         assert_ne!(&[0; LEN as usize], output.as_ref(),);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    /// asking for more than the limit is an error rather than a huge allocation
+    async fn random_bytes_over_limit_test() {
+        let ribosome = RealRibosomeFixturator::new(crate::fixt::curve::Zomes(vec![]))
+            .next()
+            .unwrap();
+        let mut call_context = CallContextFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        call_context.host_context = HostContext::ZomeCall(fixt!(ZomeCallHostAccess));
+        let ribosome = Arc::new(ribosome);
+        let call_context = Arc::new(call_context);
+
+        let output = random_bytes(ribosome.clone(), call_context.clone(), MAX_RANDOM_BYTES).unwrap();
+        assert_eq!(MAX_RANDOM_BYTES as usize, output.len());
+
+        assert!(random_bytes(ribosome, call_context, MAX_RANDOM_BYTES + 1).is_err());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     /// we can get some random data out of the fn via. a wasm call
     async fn ribosome_random_bytes_test() {
@@ -77,4 +106,21 @@ pub mod wasm_test {
 
         assert_ne!(&vec![0; LEN as usize], &output.to_vec());
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    /// two calls for the same number of bytes give different bytes
+    async fn ribosome_random_bytes_differ_test() {
+        observability::test_run().ok();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::RandomBytes).await;
+        const LEN: u32 = 32;
+        let first: hdk::prelude::Bytes = conductor.call(&alice, "random_bytes", LEN).await;
+        let second: hdk::prelude::Bytes = conductor.call(&alice, "random_bytes", LEN).await;
+
+        assert_eq!(LEN as usize, first.len());
+        assert_eq!(LEN as usize, second.len());
+        // 256 bits colliding by chance is not going to happen.
+        assert_ne!(first, second);
+    }
 }
@@ -12,7 +12,7 @@ pub fn schedule(
 ) -> Result<(), WasmError> {
     match HostFnAccess::from(&call_context.host_context()) {
         HostFnAccess {
-            write_workspace: Permission::Allow,
+            scheduling: Permission::Allow,
             ..
         } => {
             call_context
@@ -20,7 +20,7 @@ pub fn schedule(
                 .workspace_write()
                 .source_chain()
                 .as_ref()
-                .expect("Must have source chain if write_workspace access is given")
+                .expect("Must have source chain if scheduling access is given")
                 .scratch()
                 .apply(|scratch| {
                     scratch.add_scheduled_fn(ScheduledFn::new(
@@ -0,0 +1,106 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
+
+pub fn unschedule(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: String,
+) -> Result<(), WasmError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            scheduling: Permission::Allow,
+            ..
+        } => {
+            call_context
+                .host_context()
+                .workspace_write()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain if scheduling access is given")
+                .scratch()
+                .apply(|scratch| {
+                    scratch.add_unscheduled_fn(ScheduledFn::new(
+                        call_context.zome.zome_name().clone(),
+                        input.into(),
+                    ));
+                })
+                .map_err(|e| WasmError::Host(e.to_string()))?;
+            Ok(())
+        }
+        _ => Err(WasmError::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "unschedule".into(),
+            )
+            .to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_state::schedule::fn_is_scheduled;
+    use holochain_wasm_test_utils::TestWasm;
+    use rusqlite::Transaction;
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "test_utils")]
+    async fn unschedule_test() {
+        observability::test_run().ok();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_pubkey,
+            alice_host_fn_caller,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Schedule).await;
+
+        let is_scheduled = |fn_name: &'static str| {
+            let alice_pubkey = alice_pubkey.clone();
+            alice_host_fn_caller
+                .authored_env
+                .async_reader(move |txn: Transaction| {
+                    fn_is_scheduled(
+                        &txn,
+                        ScheduledFn::new(TestWasm::Schedule.into(), fn_name.into()),
+                        &alice_pubkey,
+                    )
+                })
+        };
+
+        let _: () = conductor.call(&alice, "schedule", ()).await;
+        assert!(is_scheduled("cron_scheduled_fn").await.unwrap());
+
+        let _: () = conductor.call(&alice, "unschedule", ()).await;
+        assert!(!is_scheduled("cron_scheduled_fn").await.unwrap());
+        assert!(!is_scheduled("scheduled_fn").await.unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "test_utils")]
+    async fn schedule_after_delay_fires() {
+        observability::test_run().ok();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Schedule).await;
+
+        let _: () = conductor.call(&alice, "schedule_delayed", ()).await;
+
+        // Nothing is due yet.
+        conductor.handle().dispatch_scheduled_fns().await;
+        let query_tick: Vec<Element> = conductor.call(&alice, "query_tick", ()).await;
+        assert!(query_tick.is_empty());
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        conductor.handle().dispatch_scheduled_fns().await;
+        let query_tick: Vec<Element> = conductor.call(&alice, "query_tick", ()).await;
+        assert_eq!(query_tick.len(), 1);
+    }
+}
@@ -5,6 +5,8 @@ use super::guest_callback::post_commit::PostCommitHostAccess;
 use super::guest_callback::validate::ValidateHostAccess;
 use super::guest_callback::validation_package::ValidationPackageHostAccess;
 use super::host_fn::get_agent_activity::get_agent_activity;
+use super::host_fn::get_peers::get_peers;
+use super::host_fn::get_validation_receipts::get_validation_receipts;
 use super::host_fn::HostFnApi;
 use super::HostContext;
 use super::ZomeCallHostAccess;
@@ -33,6 +35,7 @@ use crate::core::ribosome::host_fn::capability_claims::capability_claims;
 use crate::core::ribosome::host_fn::capability_grants::capability_grants;
 use crate::core::ribosome::host_fn::capability_info::capability_info;
 use crate::core::ribosome::host_fn::create::create;
+use crate::core::ribosome::host_fn::create_clone_cell::create_clone_cell;
 use crate::core::ribosome::host_fn::create_link::create_link;
 use crate::core::ribosome::host_fn::create_x25519_keypair::create_x25519_keypair;
 use crate::core::ribosome::host_fn::delete::delete;
@@ -48,6 +51,8 @@ use crate::core::ribosome::host_fn::must_get_entry::must_get_entry;
 use crate::core::ribosome::host_fn::must_get_header::must_get_header;
 use crate::core::ribosome::host_fn::must_get_valid_element::must_get_valid_element;
 use crate::core::ribosome::host_fn::query::query;
+use crate::core::ribosome::host_fn::query_after::query_after;
+use crate::core::ribosome::host_fn::query_count::query_count;
 use crate::core::ribosome::host_fn::random_bytes::random_bytes;
 use crate::core::ribosome::host_fn::remote_signal::remote_signal;
 use crate::core::ribosome::host_fn::schedule::schedule;
@@ -56,6 +61,7 @@ use crate::core::ribosome::host_fn::sign_ephemeral::sign_ephemeral;
 use crate::core::ribosome::host_fn::sleep::sleep;
 use crate::core::ribosome::host_fn::sys_time::sys_time;
 use crate::core::ribosome::host_fn::trace::trace;
+use crate::core::ribosome::host_fn::unschedule::unschedule;
 use crate::core::ribosome::host_fn::update::update;
 use crate::core::ribosome::host_fn::verify_signature::verify_signature;
 use crate::core::ribosome::host_fn::version::version;
@@ -410,6 +416,8 @@ impl RealRibosome {
             .with_host_function(&mut ns, "__get_links", get_links)
             .with_host_function(&mut ns, "__get_link_details", get_link_details)
             .with_host_function(&mut ns, "__get_agent_activity", get_agent_activity)
+            .with_host_function(&mut ns, "__get_peers", get_peers)
+            .with_host_function(&mut ns, "__create_clone_cell", create_clone_cell)
             .with_host_function(&mut ns, "__must_get_entry", must_get_entry)
             .with_host_function(&mut ns, "__must_get_header", must_get_header)
             .with_host_function(&mut ns, "__must_get_valid_element", must_get_valid_element)
@@ -419,6 +427,13 @@ impl RealRibosome {
                 accept_countersigning_preflight_request,
             )
             .with_host_function(&mut ns, "__query", query)
+            .with_host_function(&mut ns, "__query_count", query_count)
+            .with_host_function(&mut ns, "__query_after", query_after)
+            .with_host_function(
+                &mut ns,
+                "__get_validation_receipts",
+                get_validation_receipts,
+            )
             .with_host_function(&mut ns, "__remote_signal", remote_signal)
             .with_host_function(&mut ns, "__call", call)
             .with_host_function(&mut ns, "__create", create)
@@ -427,7 +442,8 @@ impl RealRibosome {
             .with_host_function(&mut ns, "__delete_link", delete_link)
             .with_host_function(&mut ns, "__update", update)
             .with_host_function(&mut ns, "__delete", delete)
-            .with_host_function(&mut ns, "__schedule", schedule);
+            .with_host_function(&mut ns, "__schedule", schedule)
+            .with_host_function(&mut ns, "__unschedule", unschedule);
 
         imports.register("env", ns);
 
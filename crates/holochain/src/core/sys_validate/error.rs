@@ -121,6 +121,8 @@ pub enum ValidationOutcome {
     NotNewEntry(Header),
     #[error("The dependency {0:?} is not held")]
     NotHoldingDep(AnyDhtHash),
+    #[error("Validation is pending: {0}")]
+    Pending(String),
     #[error("The PreflightResponse signature was not valid {0:?}")]
     PreflightResponseSignature(PreflightResponse),
     #[error(transparent)]
@@ -99,3 +99,28 @@ impl OutcomeOrError<ValidationOutcome, SysValidationError> {
         Err(SourceChainError::InvalidCommit(ValidationOutcome::try_from(self)?.to_string()).into())
     }
 }
+
+/// Forces the validation status of every op in a [`DnaHash`](holo_hash::DnaHash)
+/// space instead of running validation. FOR TESTING ONLY.
+///
+/// This is shared by the sys and app validation workflows of a space.
+/// Sys validation accepts every op while an override is set and
+/// app validation then gives the op the overridden status.
+#[cfg(any(test, feature = "test_utils"))]
+#[derive(Clone, Default)]
+pub struct ValidationOverride(
+    std::sync::Arc<parking_lot::RwLock<Option<holochain_zome_types::ValidationStatus>>>,
+);
+
+#[cfg(any(test, feature = "test_utils"))]
+impl ValidationOverride {
+    /// Give every op this status, or run validation again if `None`.
+    pub fn set(&self, status: Option<holochain_zome_types::ValidationStatus>) {
+        *self.0.write() = status;
+    }
+
+    /// The status ops are currently given, if any.
+    pub fn get(&self) -> Option<holochain_zome_types::ValidationStatus> {
+        *self.0.read()
+    }
+}
@@ -116,6 +116,18 @@ async fn app_validation_workflow_inner(
                     )
                 });
 
+                #[cfg(any(test, feature = "test_utils"))]
+                if let Some(status) = workspace.validation_override.get() {
+                    let outcome = match status {
+                        ValidationStatus::Valid => Outcome::Accepted,
+                        ValidationStatus::Rejected => {
+                            Outcome::Rejected("Validation overridden to Rejected".to_string())
+                        }
+                        ValidationStatus::Abandoned => Outcome::Abandoned,
+                    };
+                    return (op_hash, dependency, op_light, Ok(outcome), activity);
+                }
+
                 // Validate this op
                 let mut cascade = workspace.full_cascade(network.clone());
                 let r = match dhtop_to_op(op, &mut cascade).await {
@@ -178,8 +190,11 @@ async fn app_validation_workflow_inner(
 
                     // Collect all agent activity.
                     if let Some(activity) = activity {
-                        // If the activity is accepted or rejected then it's ready to integrate.
-                        if matches!(&outcome, Outcome::Accepted | Outcome::Rejected(_)) {
+                        // If the activity is accepted, rejected or abandoned then it's ready to integrate.
+                        if matches!(
+                            &outcome,
+                            Outcome::Accepted | Outcome::Rejected(_) | Outcome::Abandoned
+                        ) {
                             agent_activity.push(activity);
                         }
                     }
@@ -204,6 +219,7 @@ async fn app_validation_workflow_inner(
                             awaiting += 1;
                             let status = ValidationLimboStatus::AwaitingAppDeps(deps);
                             put_validation_limbo(txn, &op_hash, status)?;
+                            set_validation_retry(txn, &op_hash)?;
                         }
                         Outcome::Rejected(_) => {
                             rejected += 1;
@@ -214,6 +230,13 @@ async fn app_validation_workflow_inner(
                                 put_integration_limbo(txn, &op_hash, ValidationStatus::Rejected)?;
                             }
                         }
+                        Outcome::Abandoned => {
+                            if let Dependency::Null = dependency {
+                                put_integrated(txn, &op_hash, ValidationStatus::Abandoned)?;
+                            } else {
+                                put_integration_limbo(txn, &op_hash, ValidationStatus::Abandoned)?;
+                            }
+                        }
                     }
                 }
                 WorkflowResult::Ok((total, awaiting, rejected, agent_activity))
@@ -557,6 +580,8 @@ pub struct AppValidationWorkspace {
     cache: DbWrite<DbKindCache>,
     keystore: MetaLairClient,
     dna_def: Arc<DnaDef>,
+    #[cfg(any(test, feature = "test_utils"))]
+    validation_override: crate::core::ValidationOverride,
 }
 
 impl AppValidationWorkspace {
@@ -573,9 +598,21 @@ impl AppValidationWorkspace {
             cache,
             keystore,
             dna_def,
+            #[cfg(any(test, feature = "test_utils"))]
+            validation_override: Default::default(),
         }
     }
 
+    /// Share a validation override with this workspace. FOR TESTING ONLY.
+    #[cfg(any(test, feature = "test_utils"))]
+    pub fn with_validation_override(
+        mut self,
+        validation_override: crate::core::ValidationOverride,
+    ) -> Self {
+        self.validation_override = validation_override;
+        self
+    }
+
     pub async fn validation_workspace(&self) -> AppValidationResult<HostFnWorkspaceRead> {
         Ok(HostFnWorkspace::new(
             self.authored_env.clone(),
@@ -17,6 +17,9 @@ pub enum Outcome {
     AwaitingDeps(Vec<AnyDhtHash>),
     /// Moves to integration with status rejected
     Rejected(String),
+    /// Moves to integration with status abandoned
+    /// without being validated
+    Abandoned,
 }
 
 impl Outcome {
@@ -273,6 +273,9 @@ fn map_outcome(
         app_validation_workflow::Outcome::AwaitingDeps(hashes) => {
             return Err(SourceChainError::InvalidCommit(format!("{:?}", hashes)).into());
         }
+        app_validation_workflow::Outcome::Abandoned => {
+            return Err(SourceChainError::InvalidCommit("Validation was abandoned".into()).into());
+        }
     }
     Ok(())
 }
@@ -36,6 +36,30 @@ pub mod validation_query;
 
 const NUM_CONCURRENT_OPS: usize = 50;
 
+/// How sys validation retries ops that are pending on a dependency.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PendingRetryConfig {
+    /// Abandon a pending op once it has been retried this many times.
+    /// If `None` pending ops are never abandoned.
+    pub max_retries: Option<u32>,
+    /// Wait at least this long between attempts at a pending op.
+    /// Sys validation is triggered again after this long while there are
+    /// pending ops left to retry.
+    /// If `None` pending ops are attempted on every run.
+    pub backoff: Option<std::time::Duration>,
+}
+
+impl From<&holochain_conductor_api::config::conductor::ConductorConfig> for PendingRetryConfig {
+    fn from(config: &holochain_conductor_api::config::conductor::ConductorConfig) -> Self {
+        Self {
+            max_retries: config.max_pending_retries,
+            backoff: config
+                .pending_retry_backoff_ms
+                .map(std::time::Duration::from_millis),
+        }
+    }
+}
+
 #[cfg(test)]
 mod chain_test;
 #[cfg(test)]
@@ -84,7 +108,12 @@ async fn sys_validation_workflow_inner(
     sys_validation_trigger: TriggerSender,
 ) -> WorkflowResult<WorkComplete> {
     let env = workspace.dht_env.clone();
-    let sorted_ops = validation_query::get_ops_to_sys_validate(&env).await?;
+    let pending_retry = space.pending_retry;
+    // Pending ops attempted more recently than this are left until next time.
+    let retry_before = pending_retry
+        .backoff
+        .and_then(|backoff| Timestamp::now().checked_sub(&backoff));
+    let sorted_ops = validation_query::get_ops_to_sys_validate(&env, retry_before).await?;
     let start_len = sorted_ops.len();
     tracing::debug!("Validating {} ops", start_len);
     let start = (start_len >= NUM_CONCURRENT_OPS).then(std::time::Instant::now);
@@ -93,6 +122,7 @@ async fn sys_validation_workflow_inner(
     // Process each op
     let iter = sorted_ops.into_iter().map({
         let space = space.clone();
+        let sys_validation_trigger = sys_validation_trigger.clone();
         move |so| {
             // Create an incoming ops sender for any dependencies we find
             // that we are meant to be holding but aren't.
@@ -102,6 +132,8 @@ async fn sys_validation_workflow_inner(
             let network = network.clone();
             let workspace = workspace.clone();
             let conductor_handle = conductor_handle.clone();
+            #[cfg(any(test, feature = "test_utils"))]
+            let overridden = space.validation_override.get().is_some();
             async move {
                 let (op, op_hash) = so.into_inner();
                 let op_type = op.get_type();
@@ -109,6 +141,12 @@ async fn sys_validation_workflow_inner(
 
                 let dependency = get_dependency(op_type, &header);
 
+                // Leave the outcome of an overridden op to app validation.
+                #[cfg(any(test, feature = "test_utils"))]
+                if overridden {
+                    return Ok((op_hash, Outcome::Accepted, dependency));
+                }
+
                 let r = validate_op(
                     &op,
                     &(*workspace),
@@ -154,13 +192,14 @@ async fn sys_validation_workflow_inner(
     while let Some(chunk) = iter.next().await {
         let num_ops: usize = chunk.iter().map(|c| c.len()).sum();
         tracing::debug!("Committing {} ops", num_ops);
-        let (t, a, m, r) = space
+        let (t, a, p, r, ab) = space
             .dht_env
             .async_commit(move |txn| {
                 let mut total = 0;
                 let mut awaiting = 0;
-                let mut missing = 0;
+                let mut pending = 0;
                 let mut rejected = 0;
+                let mut abandoned = 0;
                 for outcome in chunk.into_iter().flatten() {
                     let (op_hash, outcome, dependency) = outcome?;
                     match outcome {
@@ -185,11 +224,14 @@ async fn sys_validation_workflow_inner(
                             // RegisterAgentActivity or RegisterAddLink.
                             let status = ValidationLimboStatus::AwaitingSysDeps(missing_dep);
                             put_validation_limbo(txn, &op_hash, status)?;
+                            set_validation_retry(txn, &op_hash)?;
                         }
-                        Outcome::MissingDhtDep => {
-                            missing += 1;
-                            // TODO: Not sure what missing dht dep is. Check if we need this.
-                            put_validation_limbo(txn, &op_hash, ValidationLimboStatus::Pending)?;
+                        Outcome::Pending(reason) => {
+                            if put_pending(txn, &op_hash, dependency, &reason, pending_retry)? {
+                                abandoned += 1;
+                            } else {
+                                pending += 1;
+                            }
                         }
                         Outcome::Rejected => {
                             rejected += 1;
@@ -201,7 +243,7 @@ async fn sys_validation_workflow_inner(
                         }
                     }
                 }
-                WorkflowResult::Ok((total, awaiting, missing, rejected))
+                WorkflowResult::Ok((total, awaiting, pending, rejected, abandoned))
             })
             .await?;
 
@@ -218,9 +260,22 @@ async fn sys_validation_workflow_inner(
                 ops_ps
             );
         }
-        tracing::debug!("{} committed, {} awaiting sys dep, {} missing dht dep, {} rejected. {} committed this round", t, a, m, r, total);
+        tracing::debug!("{} committed, {} awaiting sys dep, {} pending, {} rejected, {} abandoned. {} committed this round", t, a, p, r, ab, total);
     }
     jh.await?;
+
+    // If there are pending ops left to retry then keep looping
+    // so they are picked up once their backoff has passed.
+    if pending_retry.backoff.is_some() {
+        let pending_left = env
+            .async_reader(|txn| validation_query::num_pending_retries(&txn))
+            .await?;
+        if pending_left > 0 {
+            sys_validation_trigger.resume_loop();
+        } else {
+            sys_validation_trigger.pause_loop();
+        }
+    }
     tracing::debug!("Accepted {} ops", total);
     Ok(if saturated {
         WorkComplete::Incomplete
@@ -280,7 +335,9 @@ fn handle_failed(error: ValidationOutcome) -> Outcome {
             unreachable!("Counterfeit ops are dropped before sys validation")
         }
         ValidationOutcome::HeaderNotInCounterSigningSession(_, _) => Rejected,
-        ValidationOutcome::DepMissingFromDht(_) => MissingDhtDep,
+        ValidationOutcome::DepMissingFromDht(dep) => {
+            Pending(format!("The dependency {:?} was not found on the DHT", dep))
+        }
         ValidationOutcome::EntryDefId(_) => Rejected,
         ValidationOutcome::EntryHash => Rejected,
         ValidationOutcome::EntryTooLarge(_, _) => Rejected,
@@ -290,6 +347,7 @@ fn handle_failed(error: ValidationOutcome) -> Outcome {
         ValidationOutcome::NotCreateLink(_) => Rejected,
         ValidationOutcome::NotNewEntry(_) => Rejected,
         ValidationOutcome::NotHoldingDep(dep) => AwaitingOpDep(dep),
+        ValidationOutcome::Pending(reason) => Pending(reason),
         ValidationOutcome::PrevHeaderError(PrevHeaderError::MissingMeta(dep)) => {
             AwaitingOpDep(dep.into())
         }
@@ -952,6 +1010,47 @@ fn put_validation_limbo(
     Ok(())
 }
 
+/// How many times validation of this op has been retried.
+fn validation_attempts(txn: &mut Transaction<'_>, hash: &DhtOpHash) -> WorkflowResult<u32> {
+    Ok(txn.query_row(
+        "SELECT IFNULL(num_validation_attempts, 0) FROM DhtOp WHERE hash = ?",
+        [hash],
+        |row| row.get(0),
+    )?)
+}
+
+/// Put an op that is pending back into limbo to be retried,
+/// or abandon it if it has run out of retries.
+/// Returns true if the op was abandoned.
+fn put_pending(
+    txn: &mut Transaction<'_>,
+    hash: &DhtOpHash,
+    dependency: Dependency,
+    reason: &str,
+    pending_retry: PendingRetryConfig,
+) -> WorkflowResult<bool> {
+    let out_of_retries = match pending_retry.max_retries {
+        Some(max_retries) => validation_attempts(txn, hash)? >= max_retries,
+        None => false,
+    };
+    if out_of_retries {
+        info!(
+            ?hash,
+            %reason,
+            "Abandoning DhtOp that is still pending after the maximum number of retries"
+        );
+        if let Dependency::Null = dependency {
+            put_integrated(txn, hash, ValidationStatus::Abandoned)?;
+        } else {
+            put_integration_limbo(txn, hash, ValidationStatus::Abandoned)?;
+        }
+    } else {
+        put_validation_limbo(txn, hash, ValidationLimboStatus::Pending)?;
+        set_validation_retry(txn, hash)?;
+    }
+    Ok(out_of_retries)
+}
+
 fn put_integration_limbo(
     txn: &mut Transaction<'_>,
     hash: &DhtOpHash,
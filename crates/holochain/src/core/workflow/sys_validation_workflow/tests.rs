@@ -1,3 +1,5 @@
+use super::put_pending;
+use super::PendingRetryConfig;
 use crate::sweettest::SweetConductorBatch;
 use crate::sweettest::SweetDnaFile;
 use crate::test_utils::host_fn_caller::*;
@@ -346,6 +348,83 @@ async fn dodgy_bob(bob_cell_id: &CellId, handle: &ConductorHandle, dna_file: &Dn
 // ## Expected
 // The Delete header should be invalid for all authorities.
 
+#[test]
+/// A pending op goes back into limbo until it runs out of retries
+/// and is then abandoned.
+fn pending_op_is_abandoned_after_max_retries() {
+    use holochain_state::prelude::*;
+
+    let env = test_dht_env();
+    let op = DhtOpHashed::from_content_sync(DhtOp::RegisterAgentActivity(
+        fixt!(Signature),
+        fixt!(Header),
+    ));
+    let hash = op.to_hash();
+    let pending_retry = PendingRetryConfig {
+        max_retries: Some(2),
+        backoff: None,
+    };
+    let status = |txn: &mut Transaction| -> (Option<ValidationStatus>, bool) {
+        txn.query_row(
+            "SELECT validation_status, when_integrated IS NOT NULL FROM DhtOp WHERE hash = ?",
+            [&hash],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap()
+    };
+
+    env.env()
+        .conn()
+        .unwrap()
+        .with_commit_sync(|txn| {
+            insert_op(txn, &op).unwrap();
+            // Moving between validation stages is not a retry.
+            for _ in 0..3 {
+                set_validation_stage(txn, &hash, ValidationLimboStatus::SysValidated).unwrap();
+            }
+            for _ in 0..2 {
+                let abandoned =
+                    put_pending(txn, &hash, Dependency::Null, "missing", pending_retry).unwrap();
+                assert!(!abandoned);
+                assert_eq!(status(txn), (None, false));
+            }
+            let abandoned =
+                put_pending(txn, &hash, Dependency::Null, "missing", pending_retry).unwrap();
+            assert!(abandoned);
+            assert_eq!(status(txn), (Some(ValidationStatus::Abandoned), true));
+            DatabaseResult::Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+/// Without a maximum a pending op is never abandoned.
+fn pending_op_is_kept_without_max_retries() {
+    use holochain_state::prelude::*;
+
+    let env = test_dht_env();
+    let op = DhtOpHashed::from_content_sync(DhtOp::RegisterAgentActivity(
+        fixt!(Signature),
+        fixt!(Header),
+    ));
+    let hash = op.to_hash();
+
+    env.env()
+        .conn()
+        .unwrap()
+        .with_commit_sync(|txn| {
+            insert_op(txn, &op).unwrap();
+            for _ in 0..10 {
+                let abandoned =
+                    put_pending(txn, &hash, Dependency::Null, "missing", Default::default())
+                        .unwrap();
+                assert!(!abandoned);
+            }
+            DatabaseResult::Ok(())
+        })
+        .unwrap();
+}
+
 fn show_limbo(txn: &Transaction) -> Vec<DhtOpLight> {
     txn.prepare(
         "
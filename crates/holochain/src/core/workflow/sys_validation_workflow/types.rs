@@ -9,9 +9,12 @@ pub(super) enum Outcome {
     /// dependency needs to be validated first
     AwaitingOpDep(AnyDhtHash),
     /// Stays in limbo because a dependency could not
-    /// be found currently on the DHT.
+    /// be found currently on the DHT, or validation was deferred
+    /// for another reason.
     /// Note this is not proof it doesn't exist.
-    MissingDhtDep,
+    /// Abandoned if it is still pending after the
+    /// configured maximum number of retries.
+    Pending(String),
     /// Moves to integration with status rejected
     Rejected,
 }
@@ -7,6 +7,8 @@ use holochain_types::dht_op::DhtOpType;
 use holochain_types::env::DbRead;
 use holochain_zome_types::Entry;
 use holochain_zome_types::SignedHeader;
+use holochain_zome_types::Timestamp;
+use rusqlite::Transaction;
 
 use crate::core::workflow::error::WorkflowResult;
 
@@ -14,19 +16,26 @@ use crate::core::workflow::error::WorkflowResult;
 /// - Sys validated or awaiting app dependencies.
 /// - Ordered by type then timestamp (See [`DhtOpOrder`])
 pub async fn get_ops_to_app_validate(env: &DbRead<DbKindDht>) -> WorkflowResult<Vec<DhtOpHashed>> {
-    get_ops_to_validate(env, false).await
+    get_ops_to_validate(env, false, None).await
 }
 
 /// Get all ops that need to sys or app validated in order.
 /// - Pending or awaiting sys dependencies.
 /// - Ordered by type then timestamp (See [`DhtOpOrder`])
-pub async fn get_ops_to_sys_validate(env: &DbRead<DbKindDht>) -> WorkflowResult<Vec<DhtOpHashed>> {
-    get_ops_to_validate(env, true).await
+///
+/// If `retry_before` is set then pending ops that were last
+/// attempted after that time are skipped.
+pub async fn get_ops_to_sys_validate(
+    env: &DbRead<DbKindDht>,
+    retry_before: Option<Timestamp>,
+) -> WorkflowResult<Vec<DhtOpHashed>> {
+    get_ops_to_validate(env, true, retry_before).await
 }
 
 async fn get_ops_to_validate(
     env: &DbRead<DbKindDht>,
     system: bool,
+    retry_before: Option<Timestamp>,
 ) -> WorkflowResult<Vec<DhtOpHashed>> {
     let mut sql = "
         SELECT 
@@ -51,6 +60,12 @@ async fn get_ops_to_validate(
                 DhtOp.validation_stage IS NULL
                 OR DhtOp.validation_stage = 0
             )
+            AND (
+                :retry_before IS NULL
+                OR DhtOp.validation_stage IS NOT NULL
+                OR DhtOp.last_validation_attempt IS NULL
+                OR DhtOp.last_validation_attempt <= :retry_before
+            )
             ",
         );
     } else {
@@ -80,26 +95,57 @@ async fn get_ops_to_validate(
     );
     env.async_reader(move |txn| {
         let mut stmt = txn.prepare(&sql)?;
-        let r = stmt.query_and_then([], |row| {
-            let header = from_blob::<SignedHeader>(row.get("header_blob")?)?;
-            let op_type: DhtOpType = row.get("dht_type")?;
-            let hash: DhtOpHash = row.get("dht_hash")?;
-            let entry: Option<Vec<u8>> = row.get("entry_blob")?;
-            let entry = match entry {
-                Some(entry) => Some(from_blob::<Entry>(entry)?),
-                None => None,
-            };
-            WorkflowResult::Ok(DhtOpHashed::with_pre_hashed(
-                DhtOp::from_type(op_type, header, entry)?,
-                hash,
-            ))
-        })?;
-        let r = r.collect();
+        let r = if system {
+            stmt.query_and_then(
+                named_params! {
+                    ":retry_before": retry_before,
+                },
+                op_from_row,
+            )?
+            .collect()
+        } else {
+            stmt.query_and_then([], op_from_row)?.collect()
+        };
         WorkflowResult::Ok(r)
     })
     .await?
 }
 
+/// Get the number of pending ops that have been
+/// put back to be retried by sys validation.
+pub fn num_pending_retries(txn: &Transaction) -> WorkflowResult<usize> {
+    let count = txn.query_row(
+        "
+        SELECT
+        COUNT(DhtOp.rowid) as num_ops
+        FROM DhtOp
+        WHERE
+        DhtOp.when_integrated IS NULL
+        AND DhtOp.validation_status IS NULL
+        AND DhtOp.validation_stage IS NULL
+        AND DhtOp.last_validation_attempt IS NOT NULL
+        ",
+        [],
+        |row| row.get("num_ops"),
+    )?;
+    Ok(count)
+}
+
+fn op_from_row(row: &Row) -> WorkflowResult<DhtOpHashed> {
+    let header = from_blob::<SignedHeader>(row.get("header_blob")?)?;
+    let op_type: DhtOpType = row.get("dht_type")?;
+    let hash: DhtOpHash = row.get("dht_hash")?;
+    let entry: Option<Vec<u8>> = row.get("entry_blob")?;
+    let entry = match entry {
+        Some(entry) => Some(from_blob::<Entry>(entry)?),
+        None => None,
+    };
+    Ok(DhtOpHashed::with_pre_hashed(
+        DhtOp::from_type(op_type, header, entry)?,
+        hash,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use arbitrary::Arbitrary;
@@ -137,7 +183,9 @@ mod tests {
         observability::test_run().ok();
         let env = test_dht_env();
         let expected = test_data(&env.env().into());
-        let r = get_ops_to_validate(&env.env().into(), true).await.unwrap();
+        let r = get_ops_to_validate(&env.env().into(), true, None)
+            .await
+            .unwrap();
         let mut r_sorted = r.clone();
         // Sorted by OpOrder
         r_sorted.sort_by_key(|d| {
@@ -218,6 +266,63 @@ mod tests {
         Expected { results }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    /// Pending ops are skipped until they are due to be retried.
+    async fn sys_validation_query_backoff() {
+        observability::test_run().ok();
+        let env = test_dht_env();
+        let fresh = create_and_insert_op(
+            &env.env(),
+            Facts {
+                pending: true,
+                awaiting_sys_deps: false,
+                has_validation_status: false,
+            },
+        );
+        let retried = create_and_insert_op(
+            &env.env(),
+            Facts {
+                pending: true,
+                awaiting_sys_deps: false,
+                has_validation_status: false,
+            },
+        );
+        let attempted_at = Timestamp::now();
+        env.env()
+            .conn()
+            .unwrap()
+            .with_commit_sync(|txn| set_validation_retry(txn, retried.as_hash()))
+            .unwrap();
+
+        let read: DbRead<_> = env.env().into();
+        let hashes = |ops: Vec<DhtOpHashed>| -> Vec<DhtOpHash> {
+            ops.into_iter().map(|op| op.to_hash()).collect()
+        };
+
+        // The op that was just attempted is not due yet.
+        let before = (attempted_at - std::time::Duration::from_secs(60)).unwrap();
+        let r = hashes(get_ops_to_sys_validate(&read, Some(before)).await.unwrap());
+        assert_eq!(r, vec![fresh.to_hash()]);
+
+        // Both ops are due once the backoff has passed.
+        let after = (attempted_at + std::time::Duration::from_secs(60)).unwrap();
+        let r = hashes(get_ops_to_sys_validate(&read, Some(after)).await.unwrap());
+        assert_eq!(r.len(), 2);
+        assert!(r.contains(&retried.to_hash()));
+
+        // No backoff means every pending op is returned.
+        let r = hashes(get_ops_to_sys_validate(&read, None).await.unwrap());
+        assert_eq!(r.len(), 2);
+
+        // Only the retried op is left for the retry loop.
+        let n = env
+            .env()
+            .conn()
+            .unwrap()
+            .with_reader_test(|txn| num_pending_retries(&txn).unwrap());
+        assert_eq!(n, 1);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     /// Make sure both workflows can't pull in the same ops.
     async fn workflows_are_exclusive() {
@@ -249,7 +354,7 @@ mod tests {
         for h in &hashes {
             read_ops.insert(h.clone());
         }
-        let hashes: Vec<_> = get_ops_to_sys_validate(&read)
+        let hashes: Vec<_> = get_ops_to_sys_validate(&read, None)
             .await
             .unwrap()
             .into_iter()
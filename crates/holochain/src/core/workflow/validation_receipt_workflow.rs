@@ -102,7 +102,7 @@ pub async fn validation_receipt_workflow(
         let op_hash = receipt.dht_op_hash.clone();
 
         // Sign on the dotted line.
-        let receipt = match ValidationReceipt::sign(receipt, &keystore).await {
+        let receipt = match sign_validation_receipt(receipt, &keystore).await {
             Ok(Some(r)) => r,
             Ok(None) => {
                 return Ok(WorkComplete::Incomplete);
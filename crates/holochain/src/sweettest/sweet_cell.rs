@@ -1,8 +1,23 @@
 use super::SweetZome;
+use crate::core::ValidationOverride;
 use hdk::prelude::*;
 use holo_hash::DnaHash;
-use holochain_sqlite::db::{DbKindAuthored, DbKindDht};
+use holochain_keystore::MetaLairClient;
+use holochain_p2p::AgentPubKeyExt;
+use holochain_p2p::HolochainP2pDna;
+use holochain_p2p::HolochainP2pDnaT;
+use holochain_sqlite::db::{AsP2pStateTxExt, DbKindAuthored, DbKindDht, DbKindP2pAgentStore};
+use holochain_sqlite::error::DatabaseResult;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_state::mutations::{
+    insert_op, set_validation_stage, set_validation_status, set_when_integrated,
+    StateMutationResult,
+};
+use holochain_state::source_chain::{SourceChain, SourceChainError, SourceChainResult};
+use holochain_state::validation_db::ValidationLimboStatus;
+use holochain_types::dht_op::DhtOpHashed;
 use holochain_types::env::DbWrite;
+use kitsune_p2p::agent_store::AgentInfoSigned;
 /// A reference to a Cell created by a SweetConductor installation function.
 /// It has very concise methods for calling a zome on this cell
 #[derive(Clone, derive_more::Constructor)]
@@ -10,6 +25,10 @@ pub struct SweetCell {
     pub(super) cell_id: CellId,
     pub(super) cell_authored_env: DbWrite<DbKindAuthored>,
     pub(super) cell_dht_env: DbWrite<DbKindDht>,
+    pub(super) cell_network: HolochainP2pDna,
+    pub(super) p2p_agents_env: DbWrite<DbKindP2pAgentStore>,
+    pub(super) validation_override: ValidationOverride,
+    pub(super) keystore: MetaLairClient,
 }
 
 impl SweetCell {
@@ -28,6 +47,16 @@ impl SweetCell {
         &self.cell_dht_env
     }
 
+    /// Get the p2p agent store environment for this cell's space
+    pub fn p2p_agents_env(&self) -> &DbWrite<DbKindP2pAgentStore> {
+        &self.p2p_agents_env
+    }
+
+    /// Get the network for this cell
+    pub fn network(&self) -> &HolochainP2pDna {
+        &self.cell_network
+    }
+
     /// Accessor for AgentPubKey
     pub fn agent_pubkey(&self) -> &AgentPubKey {
         self.cell_id.agent_pubkey()
@@ -42,4 +71,275 @@ impl SweetCell {
     pub fn zome<Z: Into<ZomeName>>(&self, zome_name: Z) -> SweetZome {
         SweetZome::new(self.cell_id.clone(), zome_name.into())
     }
+
+    /// Get the hash of the latest header on this cell's source chain.
+    pub async fn get_chain_head(&self) -> DatabaseResult<HeaderHash> {
+        let author = self.agent_pubkey().clone();
+        self.cell_authored_env
+            .async_reader(move |txn| {
+                Ok(txn.query_row(
+                    "SELECT hash FROM Header WHERE author = :author ORDER BY seq DESC LIMIT 1",
+                    named_params! { ":author": author },
+                    |row| row.get(0),
+                )?)
+            })
+            .await
+    }
+
+    /// Get the number of headers on this cell's source chain.
+    pub async fn get_chain_length(&self) -> DatabaseResult<u32> {
+        let author = self.agent_pubkey().clone();
+        self.cell_authored_env
+            .async_reader(move |txn| {
+                Ok(txn.query_row(
+                    "SELECT COUNT(*) FROM Header WHERE author = :author",
+                    named_params! { ":author": author },
+                    |row| row.get(0),
+                )?)
+            })
+            .await
+    }
+
+    /// Get every element on this cell's source chain, in chain order.
+    pub async fn get_source_chain_elements(&self) -> SourceChainResult<Vec<Element>> {
+        match self.source_chain().await? {
+            Some(chain) => chain.query(ChainQueryFilter::default()).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the number of elements on this cell's source chain.
+    pub async fn get_source_chain_length(&self) -> u32 {
+        match self
+            .source_chain()
+            .await
+            .expect("Failed to open source chain")
+        {
+            Some(chain) => chain
+                .query_count(ChainQueryFilter::default())
+                .await
+                .expect("Failed to count source chain elements"),
+            None => 0,
+        }
+    }
+
+    /// Get the element at the head of this cell's source chain.
+    pub async fn get_chain_head_element(&self) -> SourceChainResult<Option<Element>> {
+        match self.source_chain().await? {
+            Some(chain) => {
+                let (_, seq, _) = chain.chain_head()?;
+                let filter = ChainQueryFilter::new()
+                    .sequence_range(ChainQueryFilterRange::HeaderSeqRange(seq, seq));
+                Ok(chain.query(filter).await?.pop())
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Open this cell's source chain, or `None` if genesis hasn't run yet.
+    async fn source_chain(&self) -> SourceChainResult<Option<SourceChain>> {
+        match SourceChain::new(
+            self.cell_authored_env.clone(),
+            self.cell_dht_env.clone(),
+            self.keystore.clone(),
+            self.agent_pubkey().clone(),
+        )
+        .await
+        {
+            Ok(chain) => Ok(Some(chain)),
+            Err(SourceChainError::ChainEmpty) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Count all the ops in this cell's dht database.
+    pub async fn count_ops_in_db(&self) -> DatabaseResult<u32> {
+        self.cell_dht_env
+            .async_reader(|txn| {
+                Ok(txn.query_row("SELECT COUNT(*) FROM DhtOp", [], |row| row.get(0))?)
+            })
+            .await
+    }
+
+    /// Count the ops in this cell's dht database that have been integrated.
+    pub async fn count_integrated_ops(&self) -> DatabaseResult<u32> {
+        self.cell_dht_env
+            .async_reader(|txn| {
+                Ok(txn.query_row(
+                    "SELECT COUNT(*) FROM DhtOp WHERE when_integrated IS NOT NULL",
+                    [],
+                    |row| row.get(0),
+                )?)
+            })
+            .await
+    }
+
+    /// Count the ops in this cell's dht database authored by `author`.
+    pub async fn count_ops_for_author(&self, author: &AgentPubKey) -> DatabaseResult<u32> {
+        let author = author.clone();
+        self.cell_dht_env
+            .async_reader(move |txn| {
+                Ok(txn.query_row(
+                    "
+                    SELECT COUNT(*) FROM DhtOp
+                    JOIN Header ON DhtOp.header_hash = Header.hash
+                    WHERE Header.author = :author
+                    ",
+                    named_params! { ":author": author },
+                    |row| row.get(0),
+                )?)
+            })
+            .await
+    }
+
+    /// Assert this cell's dht database has exactly `expected` integrated ops.
+    pub async fn assert_op_count(&self, expected: u32) {
+        let integrated = self
+            .count_integrated_ops()
+            .await
+            .expect("Failed to count integrated ops");
+        if integrated != expected {
+            panic!(
+                "Expected {} integrated ops on agent {:?} but found {}. DB state: {}",
+                expected,
+                self.agent_pubkey(),
+                integrated,
+                self.op_count_diagnostic(integrated).await
+            );
+        }
+    }
+
+    /// Assert this cell's dht database has between `min` and `max`
+    /// integrated ops, inclusive.
+    pub async fn assert_op_count_within(&self, min: u32, max: u32) {
+        let integrated = self
+            .count_integrated_ops()
+            .await
+            .expect("Failed to count integrated ops");
+        if integrated < min || integrated > max {
+            panic!(
+                "Expected {} to {} integrated ops on agent {:?} but found {}. DB state: {}",
+                min,
+                max,
+                self.agent_pubkey(),
+                integrated,
+                self.op_count_diagnostic(integrated).await
+            );
+        }
+    }
+
+    async fn op_count_diagnostic(&self, integrated: u32) -> String {
+        let total = self
+            .count_ops_in_db()
+            .await
+            .expect("Failed to count ops in db");
+        format!(
+            "{} ops in the dht database, {} not yet integrated",
+            total,
+            total.saturating_sub(integrated)
+        )
+    }
+
+    /// Get this cell's agent info from the p2p agent store.
+    pub async fn get_agent_info(&self) -> DatabaseResult<Option<AgentInfoSigned>> {
+        let agent = self.agent_pubkey().to_kitsune();
+        self.p2p_agents_env
+            .async_reader(move |txn| txn.p2p_get_agent(&agent))
+            .await
+    }
+
+    /// Get every agent info in this cell's p2p agent store.
+    pub async fn get_all_peer_agent_infos(&self) -> DatabaseResult<Vec<AgentInfoSigned>> {
+        self.p2p_agents_env
+            .async_reader(|txn| txn.p2p_list_agents())
+            .await
+    }
+
+    /// Put an agent info into this cell's p2p agent store.
+    pub async fn inject_agent_info(&self, info: AgentInfoSigned) {
+        holochain_sqlite::db::p2p_put(&self.p2p_agents_env, &info)
+            .await
+            .expect("Failed to inject agent info");
+    }
+
+    /// Write `op` straight into this cell's dht database as integrated
+    /// with `validation_status`, without validating it.
+    pub async fn inject_op(&self, op: DhtOpHashed, validation_status: ValidationStatus) {
+        self.inject_integrated_ops(vec![op], validation_status)
+            .await
+    }
+
+    /// Write `ops` straight into this cell's dht database as integrated
+    /// and valid, without validating them.
+    pub async fn inject_ops(&self, ops: impl IntoIterator<Item = DhtOpHashed>) {
+        self.inject_integrated_ops(ops.into_iter().collect(), ValidationStatus::Valid)
+            .await
+    }
+
+    async fn inject_integrated_ops(&self, ops: Vec<DhtOpHashed>, status: ValidationStatus) {
+        self.cell_dht_env
+            .async_commit(move |txn| {
+                for op in ops {
+                    let hash = op.as_hash();
+                    insert_op(txn, &op)?;
+                    set_validation_status(txn, hash, status)?;
+                    // Integrated ops go back to the pending stage.
+                    set_validation_stage(txn, hash, ValidationLimboStatus::Pending)?;
+                    set_when_integrated(txn, hash, Timestamp::now())?;
+                }
+                StateMutationResult::Ok(())
+            })
+            .await
+            .expect("Failed to inject ops");
+    }
+
+    /// Accept every op without validating it while `skip` is true.
+    /// This applies to every cell of this dna on the conductor.
+    pub fn set_validation_skip(&self, skip: bool) {
+        let status = if skip {
+            Some(ValidationStatus::Valid)
+        } else {
+            None
+        };
+        self.validation_override.set(status);
+    }
+
+    /// Give every op `outcome` without validating it.
+    /// This applies to every cell of this dna on the conductor.
+    pub fn set_validation_override(&self, outcome: ValidationStatus) {
+        self.validation_override.set(Some(outcome));
+    }
+
+    /// Start a gossip round with `target` as soon as any round in progress
+    /// is over, instead of waiting for gossip to choose it.
+    /// Returns once the round has completed.
+    /// Panics if the round fails or there is no address for `target`.
+    pub async fn force_gossip_round(&self, target: &AgentPubKey) {
+        self.cell_network
+            .trigger_gossip(Some(target.clone()))
+            .await
+            .expect("Failed to trigger gossip");
+    }
+
+    /// Start a gossip round with every peer this cell knows about,
+    /// and return once they have all completed.
+    pub async fn force_gossip_with_all_peers(&self) {
+        self.cell_network
+            .trigger_gossip(None)
+            .await
+            .expect("Failed to trigger gossip");
+    }
+
+    /// Assert this cell's source chain has exactly `expected` headers.
+    pub async fn assert_chain_length(&self, expected: u32) {
+        let length = self
+            .get_chain_length()
+            .await
+            .expect("Failed to read chain length");
+        assert_eq!(
+            length, expected,
+            "Expected chain length {} but got {}",
+            expected, length
+        );
+    }
 }
@@ -3,16 +3,23 @@
 
 use super::{SweetAgents, SweetApp, SweetAppBatch, SweetCell, SweetConductorHandle};
 use crate::conductor::{
-    api::error::ConductorApiResult, config::ConductorConfig, error::ConductorResult,
-    handle::ConductorHandle, CellError, Conductor, ConductorBuilder,
+    api::error::{ConductorApiError, ConductorApiResult},
+    config::ConductorConfig,
+    error::ConductorResult,
+    handle::ConductorHandle,
+    CellError, Conductor, ConductorBuilder,
 };
 use hdk::prelude::*;
 use holo_hash::DnaHash;
 use holochain_conductor_api::{AdminInterfaceConfig, InterfaceDriver};
 use holochain_keystore::MetaLairClient;
-use holochain_state::test_utils::{test_environments, TestEnvs};
+use holochain_p2p::actor::HolochainP2pRefToDna;
+use holochain_p2p::DnaHashExt;
+use holochain_p2p::HolochainP2pSender;
+use holochain_state::test_utils::{test_environments, test_environments_in_memory, TestEnvs};
 use holochain_types::prelude::*;
 use holochain_websocket::*;
+use kitsune_p2p::metrics::GossipMetrics;
 use kitsune_p2p::KitsuneP2pConfig;
 use std::sync::Arc;
 
@@ -110,11 +117,22 @@ impl SweetConductor {
             .unwrap()
     }
 
-    /// Create a SweetConductor with a new set of TestEnvs from the given config
+    /// Create a SweetConductor with a new set of TestEnvs from the standard config.
     pub async fn from_standard_config() -> SweetConductor {
         Self::from_config(standard_config()).await
     }
 
+    /// Create a SweetConductor from the standard config whose databases all live in memory,
+    /// which makes startup much faster.
+    ///
+    /// The per-DNA databases are recreated empty if the conductor is restarted.
+    pub async fn from_standard_config_in_memory() -> SweetConductor {
+        let envs = test_environments_in_memory();
+        let config = standard_config();
+        let handle = Self::handle_from_existing(&envs, &config, &[]).await;
+        Self::new(handle, envs, config).await
+    }
+
     /// Access the TestEnvs for this conductor
     pub fn envs(&self) -> &TestEnvs {
         &self.envs
@@ -159,7 +177,10 @@ impl SweetConductor {
     /// Install the dna first.
     /// This allows a big speed up when
     /// installing many apps with the same dna
-    async fn setup_app_1_register_dna(&mut self, dna_files: &[&DnaFile]) -> ConductorApiResult<()> {
+    pub(super) async fn setup_app_1_register_dna(
+        &mut self,
+        dna_files: &[&DnaFile],
+    ) -> ConductorApiResult<()> {
         for &dna_file in dna_files {
             self.register_dna(dna_file.clone()).await?;
             self.dnas.push(dna_file.clone());
@@ -175,15 +196,29 @@ impl SweetConductor {
         installed_app_id: &str,
         agent: AgentPubKey,
         dna_files: &[&DnaFile],
+    ) -> ConductorApiResult<()> {
+        let roles = dna_files
+            .iter()
+            .map(|&dna| (format!("{}", dna.dna_hash()), dna))
+            .collect::<Vec<_>>();
+        self.setup_app_2_install_and_enable_roles(installed_app_id, agent, roles.as_slice())
+            .await
+    }
+
+    /// Install the app with the given AppRoleIds and enable it
+    async fn setup_app_2_install_and_enable_roles(
+        &mut self,
+        installed_app_id: &str,
+        agent: AgentPubKey,
+        roles: &[(AppRoleId, &DnaFile)],
     ) -> ConductorApiResult<()> {
         let installed_app_id = installed_app_id.to_string();
 
-        let installed_cells = dna_files
+        let installed_cells = roles
             .iter()
-            .map(|&dna| {
-                let cell_handle = format!("{}", dna.dna_hash());
+            .map(|(role_id, dna)| {
                 let cell_id = CellId::new(dna.dna_hash().clone(), agent.clone());
-                (InstalledCell::new(cell_id, cell_handle), None)
+                (InstalledCell::new(cell_id, role_id.clone()), None)
             })
             .collect();
         self.handle()
@@ -211,11 +246,18 @@ impl SweetConductor {
         for dna_hash in dna_hashes {
             let cell_authored_env = self.handle().0.get_authored_env(&dna_hash)?;
             let cell_dht_env = self.handle().0.get_dht_env(&dna_hash)?;
+            let cell_network = self.handle().0.holochain_p2p().to_dna(dna_hash.clone());
+            let p2p_agents_env = self.handle().0.get_p2p_env(dna_hash.to_kitsune());
+            let validation_override = self.handle().0.get_validation_override(&dna_hash)?;
             let cell_id = CellId::new(dna_hash, agent.clone());
             let cell = SweetCell {
                 cell_id,
                 cell_authored_env,
                 cell_dht_env,
+                cell_network,
+                p2p_agents_env,
+                validation_override,
+                keystore: self.keystore(),
             };
             sweet_cells.push(cell);
         }
@@ -250,6 +292,56 @@ impl SweetConductor {
             .await
     }
 
+    /// Opinionated app setup.
+    /// Creates an app for the given agent with the given roles, filling each
+    /// non-deferred role with the DnaFile at the same position.
+    ///
+    /// Deferred and disabled roles don't get a cell, so the number of DnaFiles
+    /// must match the number of non-deferred roles.
+    pub async fn setup_app_for_agent_with_roles(
+        &mut self,
+        installed_app_id: &str,
+        agent: AgentPubKey,
+        roles: &[AppRoleManifest],
+        dna_files: &[DnaFile],
+    ) -> ConductorApiResult<SweetApp> {
+        let role_ids = roles
+            .iter()
+            .filter(|role| !is_deferred(role))
+            .map(|role| role.id.clone())
+            .collect::<Vec<_>>();
+
+        if role_ids.len() != dna_files.len() {
+            panic!(
+                "setup_app_for_agent_with_roles must take as many DnaFiles as there are non-deferred roles."
+            )
+        }
+
+        let dna_files: Vec<_> = dna_files.iter().collect();
+        self.setup_app_1_register_dna(dna_files.as_slice()).await?;
+
+        let roles = role_ids
+            .into_iter()
+            .zip(dna_files.iter().copied())
+            .collect::<Vec<_>>();
+        self.setup_app_2_install_and_enable_roles(
+            installed_app_id,
+            agent.clone(),
+            roles.as_slice(),
+        )
+        .await?;
+
+        self.handle()
+            .0
+            .clone()
+            .reconcile_cell_status_with_app_status()
+            .await?;
+
+        let dna_files = dna_files.iter().map(|d| d.dna_hash().clone());
+        self.setup_app_3_create_sweet_app(installed_app_id, agent, dna_files)
+            .await
+    }
+
     /// Opinionated app setup.
     /// Creates an app using the given DnaFiles, with no extra configuration.
     /// An AgentPubKey will be generated, and is accessible via the returned SweetApp.
@@ -347,22 +439,67 @@ impl SweetConductor {
     ///
     /// Attempting to use this conductor without starting it up again will cause a panic.
     pub async fn shutdown(&mut self) {
-        if let Some(handle) = self.handle.take() {
-            handle.shutdown_and_wait().await;
-        } else {
-            panic!("Attempted to shutdown conductor which was already shutdown");
+        self.try_shutdown()
+            .await
+            .expect("Couldn't shut down the conductor")
+    }
+
+    /// Shutdown this conductor, returning any error instead of panicking.
+    pub async fn try_shutdown(&mut self) -> ConductorApiResult<()> {
+        match self.handle.take() {
+            Some(handle) => handle.try_shutdown_and_wait().await,
+            None => Err(ConductorApiError::other(
+                "Attempted to shutdown conductor which was already shutdown",
+            )),
+        }
+    }
+
+    /// Consume the signals this conductor emitted before it was shut down,
+    /// unless its signal stream was already taken with [`SweetConductor::signals`].
+    ///
+    /// Waits at most `timeout` for the stream to end.
+    pub(super) async fn drain_signals(&mut self, timeout: std::time::Duration) {
+        use tokio_stream::StreamExt;
+        if let Some(mut signals) = self.signal_stream.take() {
+            let drained =
+                tokio::time::timeout(timeout, async { while signals.next().await.is_some() {} })
+                    .await;
+            if drained.is_err() {
+                tracing::warn!("Signal stream didn't end within {:?} of shutdown", timeout);
+            }
         }
     }
 
     /// Start up this conductor if it's not already running.
     pub async fn startup(&mut self) {
-        if self.handle.is_none() {
-            self.handle = Some(SweetConductorHandle(
-                Self::handle_from_existing(&self.envs, &self.config, self.dnas.as_slice()).await,
+        self.try_startup()
+            .await
+            .expect("Couldn't start up the conductor")
+    }
+
+    /// Start up this conductor if it's not already running,
+    /// returning any error instead of panicking.
+    ///
+    /// The conductor is rebuilt from its stored config and environments,
+    /// so previously installed apps are still present.
+    /// A fresh signal stream is available via [`SweetConductor::signals`] afterwards.
+    pub async fn try_startup(&mut self) -> ConductorApiResult<()> {
+        if self.handle.is_some() {
+            return Err(ConductorApiError::other(
+                "Attempted to start conductor which was already started",
             ));
-        } else {
-            panic!("Attempted to start conductor which was already started");
         }
+
+        let handle = Conductor::builder()
+            .config(self.config.clone())
+            .test(&self.envs, self.dnas.as_slice())
+            .await?;
+        let signal_stream = handle.signal_broadcaster().await.subscribe_merged();
+
+        self.handle = Some(SweetConductorHandle(handle));
+        self.signal_stream = Some(Box::new(signal_stream));
+
+        Ok(())
     }
 
     /// Check if this conductor is running
@@ -388,6 +525,14 @@ impl SweetConductor {
             .expect("Tried to use a conductor that is offline")
     }
 
+    /// Get the totals of gossip activity for a dna on this conductor.
+    pub async fn gossip_metrics(&self, dna_hash: &DnaHash) -> GossipMetrics {
+        self.holochain_p2p()
+            .gossip_metrics(dna_hash.clone())
+            .await
+            .expect("Failed to get gossip metrics")
+    }
+
     /// Force trigger all dht ops that haven't received
     /// enough validation receipts yet.
     pub async fn force_all_publish_dht_ops(&self) {
@@ -466,3 +611,15 @@ impl std::borrow::Borrow<SweetConductorHandle> for SweetConductor {
             .expect("Tried to use a conductor that is offline")
     }
 }
+
+/// Whether the given role is not provisioned with a cell when its app is installed.
+fn is_deferred(role: &AppRoleManifest) -> bool {
+    match role.provisioning.as_ref() {
+        None => false,
+        Some(CellProvisioning::Create { deferred })
+        | Some(CellProvisioning::CreateClone { deferred })
+        | Some(CellProvisioning::UseExisting { deferred })
+        | Some(CellProvisioning::CreateIfNotExists { deferred }) => *deferred,
+        Some(CellProvisioning::Disabled) => true,
+    }
+}
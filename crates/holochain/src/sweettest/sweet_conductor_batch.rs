@@ -1,8 +1,81 @@
-use super::{standard_config, SweetAgents, SweetAppBatch, SweetConductor};
-use crate::conductor::{api::error::ConductorApiResult, config::ConductorConfig};
+use super::{
+    standard_config, SweetAgents, SweetApp, SweetAppBatch, SweetConductor, SweetDnaFile, SweetZome,
+};
+use crate::conductor::{
+    api::error::{ConductorApiError, ConductorApiResult},
+    config::ConductorConfig,
+};
 use futures::future;
 use hdk::prelude::*;
+use holochain_p2p::AgentPubKeyExt;
+use holochain_sqlite::prelude::{p2p_remove_agents, DbKindP2pAgentStore, DbWrite};
 use holochain_types::prelude::*;
+use kitsune_p2p::KitsuneAgent;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How strictly [`SweetConductorBatch::wait_for_consistency_level`] checks for consistency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsistencyLevel {
+    /// Every conductor has integrated all ops published by every cell in the batch.
+    AllOps,
+    /// At least the given number of conductors have integrated all ops
+    /// published by every cell in the batch.
+    MinN(usize),
+}
+
+/// The contents of a TOML file describing a batch of conductors.
+/// See [`SweetConductorBatch::setup_from_config_file`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ConductorBatchConfig {
+    /// One config per conductor in the batch.
+    pub conductors: Vec<ConductorConfig>,
+    /// Paths to DNA bundles which are registered on every conductor.
+    #[serde(default)]
+    pub shared_dnas: Vec<PathBuf>,
+    /// The app interface ports to open on each conductor,
+    /// in the same order as `conductors`.
+    #[serde(default)]
+    pub app_interface_ports: Vec<Vec<u16>>,
+}
+
+impl ConductorBatchConfig {
+    /// Check that the conductors can run side by side.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.conductors.is_empty() {
+            anyhow::bail!("The batch config has no conductors");
+        }
+        if self.app_interface_ports.len() > self.conductors.len() {
+            anyhow::bail!(
+                "The batch config has app interface ports for {} conductors but only {} conductors",
+                self.app_interface_ports.len(),
+                self.conductors.len()
+            );
+        }
+        let admin_ports = self
+            .conductors
+            .iter()
+            .flat_map(|config| config.admin_interfaces.iter().flatten())
+            .map(|interface| ("admin", interface.driver.port()));
+        let app_ports = self
+            .app_interface_ports
+            .iter()
+            .flatten()
+            .map(|port| ("app", *port));
+        let mut ports = HashSet::new();
+        // Port 0 lets the OS choose a free port.
+        for (kind, port) in admin_ports.chain(app_ports).filter(|(_, port)| *port != 0) {
+            if !ports.insert(port) {
+                anyhow::bail!("More than one interface uses {} port {}", kind, port);
+            }
+        }
+        Ok(())
+    }
+}
+
 /// A collection of SweetConductors, with methods for operating on the entire collection
 #[derive(derive_more::From, derive_more::Into, derive_more::IntoIterator)]
 pub struct SweetConductorBatch(Vec<SweetConductor>);
@@ -27,6 +100,37 @@ impl SweetConductorBatch {
         Self::from_configs(std::iter::repeat_with(standard_config).take(num)).await
     }
 
+    /// Create a SweetConductor for each conductor listed in the TOML
+    /// [`ConductorBatchConfig`] at `path`, and register the shared DNAs on each of them.
+    pub async fn setup_from_config_file(path: &Path) -> anyhow::Result<SweetConductorBatch> {
+        let toml = std::fs::read_to_string(path)?;
+        let config: ConductorBatchConfig = toml::from_str(&toml)?;
+        config.validate()?;
+        let mut dna_files = Vec::with_capacity(config.shared_dnas.len());
+        for dna_path in &config.shared_dnas {
+            dna_files.push(SweetDnaFile::from_bundle(dna_path).await?);
+        }
+        let dna_files: Vec<_> = dna_files.iter().collect();
+
+        let mut batch = Self::from_configs(config.conductors).await;
+        for conductor in batch.iter_mut() {
+            conductor.setup_app_1_register_dna(&dna_files).await?;
+        }
+        for (conductor, ports) in batch.iter().zip(&config.app_interface_ports) {
+            for port in ports {
+                conductor.inner_handle().add_app_interface(*port).await?;
+            }
+        }
+        Ok(batch)
+    }
+
+    /// Collect already created SweetConductors into a batch
+    pub async fn from_conductor_iter<I: IntoIterator<Item = SweetConductor>>(
+        iter: I,
+    ) -> SweetConductorBatch {
+        iter.into_iter().collect()
+    }
+
     /// Iterate over the SweetConductors
     pub fn iter(&self) -> impl Iterator<Item = &SweetConductor> {
         self.0.iter()
@@ -42,6 +146,65 @@ impl SweetConductorBatch {
         self.0
     }
 
+    /// Start a new conductor with the given config and append it to this batch.
+    ///
+    /// The new conductor has no apps installed, so peer info is not exchanged here.
+    /// Call [`SweetConductorBatch::exchange_peer_info`] after installing apps on it,
+    /// or use [`SweetConductorBatch::add_conductor_and_exchange`].
+    pub async fn add_conductor(&mut self, config: ConductorConfig) -> &mut SweetConductor {
+        self.0.push(SweetConductor::from_config(config).await);
+        self.0.last_mut().expect("a conductor was just pushed")
+    }
+
+    /// Start a new conductor with the given config, install an app for a new agent on it,
+    /// append it to this batch and exchange peer info between all conductors of the batch.
+    pub async fn add_conductor_and_exchange(
+        &mut self,
+        config: ConductorConfig,
+        installed_app_id: &str,
+        dna_files: &[DnaFile],
+    ) -> ConductorApiResult<SweetApp> {
+        let app = self
+            .add_conductor(config)
+            .await
+            .setup_app(installed_app_id, dna_files)
+            .await?;
+        self.exchange_peer_info().await;
+        Ok(app)
+    }
+
+    /// Remove the conductor at the given index from this batch,
+    /// shutting it down if it is running.
+    ///
+    /// The agents of the removed conductor are purged from the peer stores
+    /// of the remaining conductors, so they stop gossiping with it.
+    /// The conductor is returned so its databases can still be inspected.
+    /// All conductors after `index` shift down by one.
+    pub async fn remove_conductor(&mut self, index: usize) -> SweetConductor {
+        let mut conductor = self.0.remove(index);
+        if conductor.is_running() {
+            conductor.shutdown().await;
+        }
+
+        // Read from the database, because a stopped conductor can't list its cells.
+        let agents: Vec<Arc<KitsuneAgent>> =
+            crate::conductor::conductor::read_conductor_state(&conductor.envs().conductor())
+                .await
+                .expect("failed to read the conductor state")
+                .installed_apps()
+                .iter()
+                .flat_map(|(_, app)| app.all_cells())
+                .map(|cell_id| cell_id.agent_pubkey().to_kitsune())
+                .collect();
+        for env in self.p2p_envs() {
+            p2p_remove_agents(&env, agents.iter().map(|agent| agent.as_ref()))
+                .await
+                .unwrap();
+        }
+
+        conductor
+    }
+
     /// Opinionated app setup.
     /// Creates one app on each Conductor in this batch, creating a new AgentPubKey for each.
     /// The created AgentPubKeys can be retrieved via each SweetApp.
@@ -105,15 +268,155 @@ impl SweetConductorBatch {
             .into())
     }
 
+    /// Opinionated app setup with named roles.
+    /// Creates one app on each Conductor in this batch, creating a new AgentPubKey for each,
+    /// and fills each non-deferred role with the DnaFile at the same position.
+    pub async fn setup_app_with_roles(
+        &mut self,
+        installed_app_id: &str,
+        roles: &[AppRoleManifest],
+        dna_files: &[DnaFile],
+    ) -> ConductorApiResult<SweetAppBatch> {
+        let apps = self
+            .0
+            .iter_mut()
+            .map(|conductor| async move {
+                let agent = SweetAgents::one(conductor.keystore()).await;
+                conductor
+                    .setup_app_for_agent_with_roles(installed_app_id, agent, roles, dna_files)
+                    .await
+            })
+            .collect::<Vec<_>>();
+
+        Ok(future::join_all(apps)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?
+            .into())
+    }
+
+    /// Like [`Self::setup_app_for_zipped_agents`], but with named roles.
+    /// Each non-deferred role is filled with the DnaFile at the same position.
+    pub async fn setup_app_for_zipped_agents_with_roles(
+        &mut self,
+        installed_app_id: &str,
+        agents: &[AgentPubKey],
+        roles: &[AppRoleManifest],
+        dna_files: &[DnaFile],
+    ) -> ConductorApiResult<SweetAppBatch> {
+        if agents.len() != self.0.len() {
+            panic!(
+                "setup_app_for_zipped_agents_with_roles must take as many Agents as there are Conductors in this batch."
+            )
+        }
+
+        let apps = self
+            .0
+            .iter_mut()
+            .zip(agents.iter())
+            .map(|(conductor, agent)| {
+                conductor.setup_app_for_agent_with_roles(
+                    installed_app_id,
+                    agent.clone(),
+                    roles,
+                    dna_files,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Ok(future::join_all(apps)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?
+            .into())
+    }
+
     /// Let each conductor know about each others' agents so they can do networking
     pub async fn exchange_peer_info(&self) {
+        crate::conductor::p2p_agent_store::exchange_peer_info(self.p2p_envs()).await;
+    }
+
+    /// The p2p databases of all conductors in this batch.
+    fn p2p_envs(&self) -> Vec<DbWrite<DbKindP2pAgentStore>> {
         let mut all = Vec::new();
         for c in self.0.iter() {
             for env in c.envs().p2p().lock().values() {
                 all.push(env.clone());
             }
         }
-        crate::conductor::p2p_agent_store::exchange_peer_info(all).await;
+        all
+    }
+
+    /// Let each conductor know about only some of the other conductors' agents,
+    /// to simulate partial network topologies.
+    ///
+    /// An entry of `(i, j) => true` means conductor `i` learns about the agents
+    /// of conductor `j`, for every space the two conductors share.
+    /// The relation is directed: `(j, i)` must also be set for `j` to learn about `i`.
+    /// Missing entries are treated as `false`.
+    pub async fn exchange_peer_info_partial(&self, connectivity: &HashMap<(usize, usize), bool>) {
+        for (&(i, j), &connected) in connectivity.iter() {
+            if !connected || i == j {
+                continue;
+            }
+            let to_envs = self.0[i].envs().p2p().lock().clone();
+            let from_envs = self.0[j].envs().p2p().lock().clone();
+            for (space, to_env) in to_envs {
+                if let Some(from_env) = from_envs.get(&space) {
+                    let infos =
+                        crate::conductor::p2p_agent_store::all_agent_infos(from_env.clone().into())
+                            .await
+                            .unwrap();
+                    crate::conductor::p2p_agent_store::inject_agent_infos(to_env, infos.iter())
+                        .await
+                        .unwrap();
+                }
+            }
+        }
+    }
+
+    /// Split this batch into two groups of conductors which can't see each other,
+    /// until the returned [`NetworkPartition`] is healed.
+    ///
+    /// `partition` holds the indices of the conductors on one side of the split,
+    /// all other conductors are on the other side. Only the agents of cells which are
+    /// running when this is called are partitioned.
+    pub async fn with_network_partition<'a>(
+        &'a mut self,
+        partition: &[usize],
+    ) -> NetworkPartition<'a> {
+        let agents: Vec<HashSet<Arc<KitsuneAgent>>> = self
+            .0
+            .iter()
+            .map(|c| {
+                c.list_cell_ids(None)
+                    .into_iter()
+                    .map(|cell_id| cell_id.agent_pubkey().to_kitsune())
+                    .collect()
+            })
+            .collect();
+
+        for (i, c) in self.0.iter().enumerate() {
+            let other_side: HashSet<_> = agents
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| partition.contains(&i) != partition.contains(j))
+                .flat_map(|(_, agents)| agents.iter().cloned())
+                .collect();
+            // Block the other side before removing it, so gossip can't bring it back.
+            c.set_p2p_blocked_agents(other_side.clone());
+            let envs: Vec<_> = c.envs().p2p().lock().values().cloned().collect();
+            for env in envs {
+                p2p_remove_agents(&env, other_side.iter().map(|agent| agent.as_ref()))
+                    .await
+                    .unwrap();
+            }
+        }
+
+        NetworkPartition {
+            batch: self,
+            healed: false,
+        }
     }
 
     /// Force trigger all dht ops that haven't received
@@ -123,6 +426,186 @@ impl SweetConductorBatch {
             c.force_all_publish_dht_ops().await;
         }
     }
+
+    /// Call the same zome function with the same input on every conductor in this batch
+    /// concurrently. The zome at each index is called on the conductor at the same index.
+    ///
+    /// Results are returned in the same order as the conductors.
+    /// Panics with the index of the conductor if any call fails.
+    pub async fn call_all<I, O>(&self, zomes: &[SweetZome], fn_name: &str, input: I) -> Vec<O>
+    where
+        I: Clone + serde::Serialize + std::fmt::Debug,
+        O: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        self.call_all_collect_errors(zomes, fn_name, input)
+            .await
+            .into_iter()
+            .enumerate()
+            .map(|(i, result)| {
+                result.unwrap_or_else(|e| {
+                    panic!("call to '{}' failed on conductor {}: {:?}", fn_name, i, e)
+                })
+            })
+            .collect()
+    }
+
+    /// Like `call_all`, but returns the result of each call instead of panicking.
+    pub async fn call_all_collect_errors<I, O>(
+        &self,
+        zomes: &[SweetZome],
+        fn_name: &str,
+        input: I,
+    ) -> Vec<ConductorApiResult<O>>
+    where
+        I: Clone + serde::Serialize + std::fmt::Debug,
+        O: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        if zomes.len() != self.0.len() {
+            panic!("call_all must take as many zomes as there are Conductors in this batch.")
+        }
+
+        future::join_all(
+            self.0
+                .iter()
+                .zip(zomes.iter())
+                .map(|(conductor, zome)| conductor.call_fallible(zome, fn_name, input.clone())),
+        )
+        .await
+    }
+
+    /// Wait until every conductor has integrated all ops published by every cell
+    /// in this batch, or return an error describing the integration state on timeout.
+    pub async fn wait_for_consistency(&self, timeout: Duration) -> Result<(), String> {
+        self.wait_for_consistency_level(timeout, ConsistencyLevel::AllOps)
+            .await
+    }
+
+    /// Like `wait_for_consistency`, but with a configurable level of strictness.
+    pub async fn wait_for_consistency_level(
+        &self,
+        timeout: Duration,
+        consistency_level: ConsistencyLevel,
+    ) -> Result<(), String> {
+        const DELAY_PER_ATTEMPT: Duration = Duration::from_millis(100);
+
+        // The expected number of integrated ops per DNA is the sum of all ops published
+        // by all cells of that DNA across the batch.
+        let mut expected_counts: HashMap<DnaHash, usize> = HashMap::new();
+        // Each conductor has one DHT environment per DNA.
+        let mut dht_envs = Vec::new();
+        for (i, c) in self.0.iter().enumerate().filter(|(_, c)| c.is_running()) {
+            let mut dnas = Vec::new();
+            for cell_id in c.list_cell_ids(None) {
+                let dna_hash = cell_id.dna_hash();
+                let authored_env = c
+                    .get_authored_env(dna_hash)
+                    .map_err(|e| format!("conductor {}: {:?}", i, e))?;
+                *expected_counts.entry(dna_hash.clone()).or_default() +=
+                    crate::test_utils::get_published_ops(&authored_env, cell_id.agent_pubkey())
+                        .len();
+
+                if !dnas.contains(dna_hash) {
+                    dnas.push(dna_hash.clone());
+                    let dht_env = c
+                        .get_dht_env(dna_hash)
+                        .map_err(|e| format!("conductor {}: {:?}", i, e))?;
+                    dht_envs.push((i, dna_hash.clone(), dht_env));
+                }
+            }
+        }
+
+        let num_conductors = dht_envs
+            .iter()
+            .map(|(i, _, _)| *i)
+            .collect::<std::collections::HashSet<_>>()
+            .len();
+        let required_conductors = match consistency_level {
+            ConsistencyLevel::AllOps => num_conductors,
+            ConsistencyLevel::MinN(n) => n.min(num_conductors),
+        };
+
+        let start = std::time::Instant::now();
+        loop {
+            let mut lagging = Vec::new();
+            for (i, dna_hash, env) in dht_envs.iter() {
+                let expected = expected_counts.get(dna_hash).copied().unwrap_or_default();
+                let integrated = crate::test_utils::display_integration(env).await;
+                if integrated < expected {
+                    lagging.push((*i, dna_hash.clone(), integrated, expected));
+                }
+            }
+
+            let num_lagging = lagging
+                .iter()
+                .map(|(i, _, _, _)| *i)
+                .collect::<std::collections::HashSet<_>>()
+                .len();
+            if num_conductors - num_lagging >= required_conductors {
+                return Ok(());
+            }
+
+            if start.elapsed() >= timeout {
+                return Err(format!(
+                    "consistency ({:?}) not reached after {:?}. Lagging (conductor, dna, integrated, expected): {:?}",
+                    consistency_level, timeout, lagging
+                ));
+            }
+
+            tokio::time::sleep(DELAY_PER_ATTEMPT).await;
+        }
+    }
+
+    /// Shutdown all running conductors in this batch concurrently,
+    /// then drain the signals they emitted before shutting down.
+    ///
+    /// The conductors keep their inner state so they can be started again.
+    /// Signal streams taken before the shutdown won't receive any more signals.
+    /// All errors encountered while shutting down are returned rather than only the first one.
+    pub async fn shutdown_all(&mut self) -> Result<(), Vec<ConductorApiError>> {
+        let errors = future::join_all(
+            self.0
+                .iter_mut()
+                .filter(|c| c.is_running())
+                .map(|c| c.try_shutdown()),
+        )
+        .await
+        .into_iter()
+        .filter_map(Result::err)
+        .collect::<Vec<_>>();
+
+        future::join_all(
+            self.0
+                .iter_mut()
+                .map(|c| c.drain_signals(Duration::from_secs(10))),
+        )
+        .await;
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Shutdown all conductors in this batch and start them up again from their
+    /// stored config and environments.
+    ///
+    /// All errors encountered while starting up are returned rather than only the first one.
+    pub async fn restart_all(&mut self) -> Result<(), Vec<ConductorApiError>> {
+        self.shutdown_all().await?;
+
+        let errors = future::join_all(self.0.iter_mut().map(|c| c.try_startup()))
+            .await
+            .into_iter()
+            .filter_map(Result::err)
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
 }
 
 impl std::ops::Index<usize> for SweetConductorBatch {
@@ -138,3 +621,78 @@ impl std::ops::IndexMut<usize> for SweetConductorBatch {
         &mut self.0[index]
     }
 }
+
+impl FromIterator<SweetConductor> for SweetConductorBatch {
+    fn from_iter<I: IntoIterator<Item = SweetConductor>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<SweetConductor> for SweetConductorBatch {
+    fn extend<I: IntoIterator<Item = SweetConductor>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+/// Concatenates two batches, keeping the conductors of `self` first.
+impl std::ops::Add<SweetConductorBatch> for SweetConductorBatch {
+    type Output = SweetConductorBatch;
+
+    fn add(mut self, other: SweetConductorBatch) -> Self::Output {
+        self.extend(other);
+        self
+    }
+}
+
+/// A split of a [`SweetConductorBatch`] into two groups of conductors which don't know
+/// about each other's agents, created by [`SweetConductorBatch::with_network_partition`].
+///
+/// While the partition is alive, no conductor stores the agent infos of the other side
+/// in its peer store, so the two groups can't gossip or publish to each other.
+/// The batch can still be used through this guard.
+///
+/// Dropping the partition only stops blocking the other side's agents.
+/// The conductors don't learn about each other again until peer info is exchanged,
+/// so use [`NetworkPartition::heal`] to do both.
+pub struct NetworkPartition<'a> {
+    batch: &'a mut SweetConductorBatch,
+    healed: bool,
+}
+
+impl NetworkPartition<'_> {
+    /// Stop partitioning the batch and let every conductor know about
+    /// each others' agents again.
+    pub async fn heal(mut self) {
+        self.unblock();
+        self.batch.exchange_peer_info().await;
+    }
+
+    fn unblock(&mut self) {
+        if !self.healed {
+            for c in self.batch.iter() {
+                c.set_p2p_blocked_agents(HashSet::new());
+            }
+            self.healed = true;
+        }
+    }
+}
+
+impl std::ops::Deref for NetworkPartition<'_> {
+    type Target = SweetConductorBatch;
+
+    fn deref(&self) -> &Self::Target {
+        self.batch
+    }
+}
+
+impl std::ops::DerefMut for NetworkPartition<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.batch
+    }
+}
+
+impl Drop for NetworkPartition<'_> {
+    fn drop(&mut self) {
+        self.unblock();
+    }
+}
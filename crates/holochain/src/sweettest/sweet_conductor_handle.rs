@@ -103,14 +103,22 @@ impl SweetConductorHandle {
     /// Conductors are already cleaned up on drop but this
     /// is useful if you need to know when it's finished cleaning up.
     pub async fn shutdown_and_wait(&self) {
+        self.try_shutdown_and_wait()
+            .await
+            .expect("Conductor shutdown error");
+    }
+
+    /// Like `shutdown_and_wait`, but returns the error the conductor shut down with
+    pub async fn try_shutdown_and_wait(&self) -> ConductorApiResult<()> {
         let c = &self.0;
         if let Some(shutdown) = c.take_shutdown_handle() {
             c.shutdown();
             shutdown
                 .await
-                .expect("Failed to await shutdown handle")
-                .expect("Conductor shutdown error");
+                .map_err(ConductorApiError::other)?
+                .map_err(ConductorApiError::other)?;
         }
+        Ok(())
     }
 
     /// Intentionally private clone function, only to be used internally
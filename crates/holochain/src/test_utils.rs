@@ -475,7 +475,7 @@ async fn consistency_envs_others<AuthorDb, DhtDb>(
     }
 }
 
-fn get_published_ops<Db: ReadAccess<DbKindAuthored>>(
+pub(crate) fn get_published_ops<Db: ReadAccess<DbKindAuthored>>(
     env: &Db,
     author: &AgentPubKey,
 ) -> Vec<DhtOpLight> {
@@ -652,7 +652,7 @@ pub async fn query_integration<Db: ReadAccess<DbKindDht>>(env: &Db) -> Integrati
         .unwrap()
 }
 
-async fn display_integration<Db: ReadAccess<DbKindDht>>(env: &Db) -> usize {
+pub(crate) async fn display_integration<Db: ReadAccess<DbKindDht>>(env: &Db) -> usize {
     fresh_reader_test(env.clone(), |txn| {
         txn.query_row(
             "SELECT COUNT(hash) FROM DhtOp WHERE DhtOp.when_integrated IS NOT NULL",
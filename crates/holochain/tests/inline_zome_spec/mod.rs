@@ -141,6 +141,28 @@ async fn inline_zome_2_agents_1_dna() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+#[cfg(feature = "test_utils")]
+async fn inline_zome_in_memory_conductor() -> anyhow::Result<()> {
+    let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", simple_crud_zome()).await?;
+
+    let mut conductor = SweetConductor::from_standard_config_in_memory().await;
+
+    let app = conductor.setup_app("app", &[dna_file]).await.unwrap();
+    let (alice,) = app.into_tuple();
+
+    let hash: HeaderHash = conductor
+        .call(&alice.zome("zome1"), "create_unit", ())
+        .await;
+    let elements: Vec<Option<Element>> = conductor.call(&alice.zome("zome1"), "read", hash).await;
+    assert!(elements[0].is_some());
+
+    // None of the databases were written to disk
+    assert_eq!(std::fs::read_dir(conductor.envs().path())?.count(), 0);
+
+    Ok(())
+}
+
 /// Simple scenario involving three agents using an app with two DNAs
 #[tokio::test(flavor = "multi_thread")]
 #[cfg(feature = "test_utils")]
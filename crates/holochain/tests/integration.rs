@@ -8,5 +8,6 @@ mod new_lair;
 mod ser_regression;
 mod sharded_gossip;
 mod speed_tests;
+mod sweet_cell;
 mod test_cli;
 mod test_utils;
@@ -343,3 +343,585 @@ fn check_elements_for_private_entry(caller: AgentPubKey, elements: Vec<Element>)
         }
     }
 }
+
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_shutdown_and_restart() {
+    use holochain::test_utils::inline_zomes::simple_create_read_zome;
+
+    let _g = observability::test_run().ok();
+    const NUM_CONDUCTORS: usize = 2;
+
+    let mut conductors = SweetConductorBatch::from_standard_config(NUM_CONDUCTORS).await;
+
+    let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", simple_create_read_zome())
+        .await
+        .unwrap();
+
+    let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+    let ((alice,), (_bobbo,)) = apps.into_tuples();
+
+    let hash: HeaderHash = conductors[0].call(&alice.zome("zome1"), "create", ()).await;
+
+    conductors.shutdown_all().await.unwrap();
+    assert!(conductors.iter().all(|c| !c.is_running()));
+    // shutting down or starting up in the wrong state is an error, not a panic
+    assert!(conductors[0].try_shutdown().await.is_err());
+
+    conductors.restart_all().await.unwrap();
+    assert!(conductors.iter().all(|c| c.is_running()));
+    assert!(conductors[0].try_startup().await.is_err());
+
+    for conductor in conductors.iter() {
+        let apps = conductor.list_apps(None).await.unwrap();
+        assert_eq!(apps.len(), 1);
+    }
+
+    // the element committed before the restart is still available
+    let element: Option<Element> = conductors[0].call(&alice.zome("zome1"), "read", hash).await;
+    assert!(element.is_some());
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_call_all() {
+    use holochain::test_utils::inline_zomes::simple_create_read_zome;
+
+    let _g = observability::test_run().ok();
+    const NUM_CONDUCTORS: usize = 3;
+
+    let mut conductors = SweetConductorBatch::from_standard_config(NUM_CONDUCTORS).await;
+
+    let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", simple_create_read_zome())
+        .await
+        .unwrap();
+
+    let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+    let zomes = apps
+        .cells_flattened()
+        .into_iter()
+        .map(|cell| cell.zome("zome1"))
+        .collect::<Vec<_>>();
+
+    let hashes: Vec<HeaderHash> = conductors.call_all(&zomes, "create", ()).await;
+    assert_eq!(hashes.len(), NUM_CONDUCTORS);
+
+    // each conductor can read back the element it created
+    for (i, hash) in hashes.into_iter().enumerate() {
+        let element: Option<Element> = conductors[i].call(&zomes[i], "read", hash).await;
+        let element = element.expect("Element was None");
+        assert_eq!(element.header().author(), zomes[i].cell_id().agent_pubkey());
+    }
+
+    // calling an unknown function fails on every conductor without panicking
+    let results = conductors
+        .call_all_collect_errors::<_, ()>(&zomes, "no_such_fn", ())
+        .await;
+    assert_eq!(results.len(), NUM_CONDUCTORS);
+    assert!(results.iter().all(|r| r.is_err()));
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_wait_for_consistency() {
+    use holochain::sweettest::ConsistencyLevel;
+    use holochain::test_utils::inline_zomes::simple_create_read_zome;
+    use std::time::Duration;
+
+    let _g = observability::test_run().ok();
+
+    // a small linear congruential generator keeps the generated cases reproducible
+    let mut seed: u64 = 0xbeef;
+    let mut next_random = move |max: u64| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 33) % max + 1
+    };
+
+    for _ in 0..5 {
+        let num_conductors = next_random(4) as usize;
+        let num_entries = next_random(3) as usize;
+
+        let mut conductors = SweetConductorBatch::from_standard_config(num_conductors).await;
+
+        let (dna_file, _) =
+            SweetDnaFile::unique_from_inline_zome("zome1", simple_create_read_zome())
+                .await
+                .unwrap();
+
+        let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+        let zomes = apps
+            .cells_flattened()
+            .into_iter()
+            .map(|cell| cell.zome("zome1"))
+            .collect::<Vec<_>>();
+
+        for _ in 0..num_entries {
+            let _: Vec<HeaderHash> = conductors.call_all(&zomes, "create", ()).await;
+        }
+
+        if num_conductors == 1 {
+            // A lone conductor only needs to integrate its own ops.
+            conductors
+                .wait_for_consistency(Duration::from_secs(10))
+                .await
+                .unwrap();
+            continue;
+        }
+
+        // Without exchanging peer info no conductor can see the others' ops.
+        assert!(conductors
+            .wait_for_consistency(Duration::from_millis(500))
+            .await
+            .is_err());
+        assert!(conductors
+            .wait_for_consistency_level(Duration::from_millis(500), ConsistencyLevel::MinN(1))
+            .await
+            .is_err());
+
+        if num_conductors >= 3 {
+            // Stopped conductors are skipped, but the others keep their index in the report.
+            conductors[0].shutdown().await;
+            let err = conductors
+                .wait_for_consistency(Duration::from_millis(500))
+                .await
+                .unwrap_err();
+            assert!(!err.contains("(0, "), "{}", err);
+            assert!(err.contains("(1, "), "{}", err);
+        }
+    }
+}
+
+/// Test that ops propagate around a ring where each conductor
+/// only knows about the next one.
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn gossip_propagates_around_ring() {
+    use holochain::test_utils::inline_zomes::simple_create_read_zome;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    let _g = observability::test_run().ok();
+    const NUM_CONDUCTORS: usize = 3;
+
+    let mut conductors = SweetConductorBatch::from_standard_config(NUM_CONDUCTORS).await;
+
+    let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", simple_create_read_zome())
+        .await
+        .unwrap();
+
+    let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+    let ((alice,), (bobbo,), (carol,)) = apps.into_tuples();
+
+    // 0 -> 1 -> 2 -> 0
+    let connectivity: HashMap<_, _> = (0..NUM_CONDUCTORS)
+        .map(|i| ((i, (i + 1) % NUM_CONDUCTORS), true))
+        .collect();
+    conductors.exchange_peer_info_partial(&connectivity).await;
+
+    let hash: HeaderHash = conductors[0].call(&alice.zome("zome1"), "create", ()).await;
+
+    conductors
+        .wait_for_consistency(Duration::from_secs(60))
+        .await
+        .unwrap();
+
+    for (i, cell) in [(1, &bobbo), (2, &carol)] {
+        let element: Option<Element> = conductors[i]
+            .call(&cell.zome("zome1"), "read", hash.clone())
+            .await;
+        assert!(
+            element.is_some(),
+            "conductor {} did not receive the element",
+            i
+        );
+    }
+}
+
+/// Test that gossip keeps working while conductors join and leave the network.
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_churn() {
+    use holochain::conductor::p2p_agent_store::all_agent_infos;
+    use holochain::sweettest::standard_config;
+    use holochain::test_utils::inline_zomes::simple_create_read_zome;
+    use holochain_p2p::AgentPubKeyExt;
+    use holochain_p2p::DnaHashExt;
+    use std::time::Duration;
+
+    let _g = observability::test_run().ok();
+    const NUM_CONDUCTORS: usize = 3;
+    const NUM_ROUNDS: usize = 3;
+
+    let mut conductors = SweetConductorBatch::from_standard_config(NUM_CONDUCTORS).await;
+
+    let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", simple_create_read_zome())
+        .await
+        .unwrap();
+
+    let apps = conductors
+        .setup_app("app", &[dna_file.clone()])
+        .await
+        .unwrap();
+    let cells = apps.cells_flattened();
+    conductors.exchange_peer_info().await;
+
+    // The first conductor stays for the whole test.
+    let alice = cells[0].zome("zome1");
+    let hash: HeaderHash = conductors[0].call(&alice, "create", ()).await;
+
+    let space = dna_file.dna_hash().to_kitsune();
+    let mut newest_zome = None;
+    for _ in 0..NUM_ROUNDS {
+        let app = conductors
+            .add_conductor_and_exchange(standard_config(), "app", &[dna_file.clone()])
+            .await
+            .unwrap();
+        newest_zome = Some(app.cells()[0].zome("zome1"));
+        assert_eq!(conductors.iter().count(), NUM_CONDUCTORS + 1);
+
+        let removed_agents: Vec<_> = conductors[1]
+            .list_cell_ids(None)
+            .into_iter()
+            .map(|cell_id| cell_id.agent_pubkey().to_kitsune())
+            .collect();
+        let removed = conductors.remove_conductor(1).await;
+        assert!(!removed.is_running());
+        assert_eq!(conductors.iter().count(), NUM_CONDUCTORS);
+        assert!(conductors.iter().all(|c| c.is_running()));
+
+        // No remaining conductor keeps the agents of the removed one.
+        for c in conductors.iter() {
+            let infos = all_agent_infos(c.get_p2p_env(space.clone()).into())
+                .await
+                .unwrap();
+            assert!(infos
+                .iter()
+                .all(|info| !removed_agents.contains(&info.agent)));
+        }
+    }
+
+    conductors
+        .wait_for_consistency(Duration::from_secs(60))
+        .await
+        .unwrap();
+
+    // The conductor that joined last must have received alice's element.
+    let newest = conductors.iter().last().unwrap();
+    let element: Option<Element> = newest.call(&newest_zome.unwrap(), "read", hash).await;
+    assert!(element.is_some());
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn setup_app_with_roles_installs_named_roles() {
+    use holochain::test_utils::inline_zomes::simple_create_read_zome;
+    use holochain_types::prelude::{AppRoleManifest, CellProvisioning};
+
+    let _g = observability::test_run().ok();
+    let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+
+    let (dna_alpha, _) = SweetDnaFile::unique_from_inline_zome("zome1", simple_create_read_zome())
+        .await
+        .unwrap();
+    let (dna_beta, _) = SweetDnaFile::unique_from_inline_zome("zome1", simple_create_read_zome())
+        .await
+        .unwrap();
+
+    let role = |id: &str, deferred: bool| AppRoleManifest {
+        id: id.to_string(),
+        provisioning: Some(CellProvisioning::Create { deferred }),
+        ..AppRoleManifest::sample(id.to_string())
+    };
+    // The deferred role doesn't get a cell, so it takes no DnaFile.
+    let roles = [
+        role("alpha", false),
+        role("beta", false),
+        role("gamma", true),
+    ];
+
+    let apps = conductors
+        .setup_app_with_roles("app", &roles, &[dna_alpha.clone(), dna_beta.clone()])
+        .await
+        .unwrap();
+
+    for (conductor, app) in conductors.iter().zip(apps.into_inner()) {
+        let (alpha, beta) = app.into_tuple();
+        assert_eq!(alpha.cell_id().dna_hash(), dna_alpha.dna_hash());
+        assert_eq!(beta.cell_id().dna_hash(), dna_beta.dna_hash());
+
+        let info = conductor
+            .get_app_info(&"app".to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        let mut role_ids = info
+            .cell_data
+            .iter()
+            .map(|cell| cell.as_role_id().clone())
+            .collect::<Vec<_>>();
+        role_ids.sort();
+        assert_eq!(role_ids, vec!["alpha".to_string(), "beta".to_string()]);
+    }
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn conductor_batch_from_iter_and_extend() {
+    use holochain::sweettest::standard_config;
+
+    let _g = observability::test_run().ok();
+
+    let mut conductors = Vec::new();
+    for _ in 0..2 {
+        conductors.push(SweetConductor::from_config(standard_config()).await);
+    }
+    let mut batch: SweetConductorBatch = conductors.into_iter().collect();
+    assert_eq!(batch.iter().count(), 2);
+
+    batch.extend(vec![SweetConductor::from_standard_config().await]);
+    assert_eq!(batch.iter().count(), 3);
+
+    let other = SweetConductorBatch::from_conductor_iter(vec![
+        SweetConductor::from_standard_config().await,
+    ])
+    .await;
+    let batch = batch + other;
+    assert_eq!(batch.iter().count(), 4);
+    assert!(batch.iter().all(|c| c.is_running()));
+
+    // every conductor keeps its own environments
+    let paths = batch
+        .iter()
+        .map(|c| c.envs().path().to_owned())
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(paths.len(), 4);
+}
+
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_setup_from_config_file() {
+    use holochain_types::prelude::DnaBundle;
+    use holochain_wasm_test_utils::TestWasm;
+
+    let _g = observability::test_run().ok();
+    let tmpdir = tempfile::tempdir().unwrap();
+
+    let (dna_file, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create])
+        .await
+        .unwrap();
+    let dna_path = tmpdir.path().join("shared.dna");
+    DnaBundle::from_dna_file(dna_file.clone())
+        .await
+        .unwrap()
+        .write_to_file(&dna_path)
+        .await
+        .unwrap();
+
+    let config_path = tmpdir.path().join("batch.toml");
+    let toml = format!(
+        r#"
+shared_dnas = ["{}"]
+
+[[conductors]]
+environment_path = "{}"
+
+[[conductors]]
+environment_path = "{}"
+"#,
+        dna_path.display(),
+        tmpdir.path().join("conductor-0").display(),
+        tmpdir.path().join("conductor-1").display(),
+    );
+    std::fs::write(&config_path, toml).unwrap();
+
+    let batch = SweetConductorBatch::setup_from_config_file(&config_path)
+        .await
+        .unwrap();
+    assert_eq!(batch.iter().count(), 2);
+    for conductor in batch.iter() {
+        assert!(conductor.is_running());
+        assert!(conductor.list_dnas().contains(dna_file.dna_hash()));
+    }
+}
+
+#[cfg(feature = "test_utils")]
+#[test]
+fn batch_config_rejects_port_collisions() {
+    use holochain::conductor::config::{AdminInterfaceConfig, InterfaceDriver};
+    use holochain::sweettest::ConductorBatchConfig;
+
+    let admin_config = |port| ConductorConfig {
+        admin_interfaces: Some(vec![AdminInterfaceConfig {
+            driver: InterfaceDriver::Websocket { port },
+        }]),
+        ..Default::default()
+    };
+    let config = |admin_ports: [u16; 2], app_interface_ports| ConductorBatchConfig {
+        conductors: admin_ports.into_iter().map(admin_config).collect(),
+        shared_dnas: vec![],
+        app_interface_ports,
+    };
+
+    config([9000, 9001], vec![vec![9002], vec![9003, 0]])
+        .validate()
+        .unwrap();
+    // Any number of interfaces may let the OS choose their port.
+    config([0, 0], vec![vec![0], vec![0]]).validate().unwrap();
+
+    let err = config([9000, 9000], vec![]).validate().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "More than one interface uses admin port 9000"
+    );
+
+    let err = config([9000, 9001], vec![vec![9002], vec![9002]])
+        .validate()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "More than one interface uses app port 9002"
+    );
+
+    let err = config([9000, 9001], vec![vec![9001]])
+        .validate()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "More than one interface uses app port 9001"
+    );
+
+    assert!(config([9000, 9001], vec![vec![], vec![], vec![9002]])
+        .validate()
+        .is_err());
+}
+
+/// Test that the two sides of a network partition don't see each other's data,
+/// and that they do once it's healed.
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn network_partition_converges_after_heal() {
+    use holochain::conductor::p2p_agent_store::all_agent_infos;
+    use holochain::test_utils::consistency_10s;
+    use holochain::test_utils::inline_zomes::simple_create_read_zome;
+    use holochain_p2p::AgentPubKeyExt;
+    use holochain_p2p::DnaHashExt;
+    use std::time::Duration;
+
+    let _g = observability::test_run().ok();
+    const NUM_CONDUCTORS: usize = 4;
+
+    let mut conductors = SweetConductorBatch::from_standard_config(NUM_CONDUCTORS).await;
+
+    let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", simple_create_read_zome())
+        .await
+        .unwrap();
+
+    let apps = conductors
+        .setup_app("app", &[dna_file.clone()])
+        .await
+        .unwrap();
+    let cells = apps.cells_flattened();
+    let zomes: Vec<_> = cells.iter().map(|cell| cell.zome("zome1")).collect();
+    conductors.exchange_peer_info().await;
+
+    let partition = conductors.with_network_partition(&[0, 1]).await;
+    let left: HeaderHash = partition[0].call(&zomes[0], "create", ()).await;
+    let right: HeaderHash = partition[2].call(&zomes[2], "create", ()).await;
+
+    // Each side gossips within itself.
+    consistency_10s(&[&cells[0], &cells[1]]).await;
+    consistency_10s(&[&cells[2], &cells[3]]).await;
+
+    // But knows nothing of the other side.
+    let space = dna_file.dna_hash().to_kitsune();
+    for (i, hash) in [(0, &right), (1, &right), (2, &left), (3, &left)] {
+        let other = (i + 2) % NUM_CONDUCTORS;
+        let infos = all_agent_infos(partition[i].get_p2p_env(space.clone()).into())
+            .await
+            .unwrap();
+        assert!(
+            infos
+                .iter()
+                .all(|info| info.agent != cells[other].agent_pubkey().to_kitsune()),
+            "conductor {} knows about conductor {} through the partition",
+            i,
+            other
+        );
+        let element: Option<Element> = partition[i].call(&zomes[i], "read", hash.clone()).await;
+        assert!(
+            element.is_none(),
+            "conductor {} received the element {:?} through the partition",
+            i,
+            hash
+        );
+    }
+
+    partition.heal().await;
+
+    conductors
+        .wait_for_consistency(Duration::from_secs(60))
+        .await
+        .unwrap();
+
+    for (i, zome) in zomes.iter().enumerate() {
+        for hash in [&left, &right] {
+            let element: Option<Element> = conductors[i].call(zome, "read", hash.clone()).await;
+            assert!(
+                element.is_some(),
+                "conductor {} did not receive the element {:?}",
+                i,
+                hash
+            );
+        }
+    }
+}
+
+/// Test that dropping a network partition stops blocking the other side's agents,
+/// so the conductors converge once they exchange peer info again.
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn network_partition_unblocks_after_drop() {
+    use holochain::test_utils::inline_zomes::simple_create_read_zome;
+    use std::time::Duration;
+
+    let _g = observability::test_run().ok();
+
+    let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+
+    let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", simple_create_read_zome())
+        .await
+        .unwrap();
+
+    let apps = conductors
+        .setup_app("app", &[dna_file.clone()])
+        .await
+        .unwrap();
+    let cells = apps.cells_flattened();
+    let zomes: Vec<_> = cells.iter().map(|cell| cell.zome("zome1")).collect();
+    conductors.exchange_peer_info().await;
+
+    let partition = conductors.with_network_partition(&[0]).await;
+    let left: HeaderHash = partition[0].call(&zomes[0], "create", ()).await;
+    let right: HeaderHash = partition[1].call(&zomes[1], "create", ()).await;
+    drop(partition);
+    conductors.exchange_peer_info().await;
+
+    conductors
+        .wait_for_consistency(Duration::from_secs(60))
+        .await
+        .unwrap();
+
+    for (i, zome) in zomes.iter().enumerate() {
+        for hash in [&left, &right] {
+            let element: Option<Element> = conductors[i].call(zome, "read", hash.clone()).await;
+            assert!(
+                element.is_some(),
+                "conductor {} did not receive the element {:?}",
+                i,
+                hash
+            );
+        }
+    }
+}
@@ -84,6 +84,61 @@ async fn fullsync_sharded_gossip() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn gossip_metrics_count_ops() -> anyhow::Result<()> {
+    use holochain::{
+        conductor::handle::DevSettingsDelta, test_utils::inline_zomes::simple_create_read_zome,
+    };
+
+    let _g = observability::test_run().ok();
+
+    let mut tuning =
+        kitsune_p2p_types::config::tuning_params_struct::KitsuneP2pTuningParams::default();
+    tuning.gossip_strategy = "sharded-gossip".to_string();
+
+    let mut network = KitsuneP2pConfig::default();
+    network.transport_pool = vec![kitsune_p2p::TransportConfig::Quic {
+        bind_to: None,
+        override_host: None,
+        override_port: None,
+    }];
+    network.tuning_params = Arc::new(tuning);
+    let mut config = ConductorConfig::default();
+    config.network = Some(network);
+
+    let mut conductors = SweetConductorBatch::from_config(2, config).await;
+    for c in conductors.iter() {
+        c.update_dev_settings(DevSettingsDelta {
+            publish: Some(false),
+            ..Default::default()
+        });
+    }
+
+    let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", simple_create_read_zome())
+        .await
+        .unwrap();
+    let dna_hash = dna_file.dna_hash().clone();
+
+    let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let _: HeaderHash = conductors[0].call(&alice.zome("zome1"), "create", ()).await;
+
+    // Publish is off so bobbo can only get alice's ops through gossip.
+    consistency_10s(&[&alice, &bobbo]).await;
+
+    let alice_metrics = conductors[0].gossip_metrics(&dna_hash).await;
+    let bobbo_metrics = conductors[1].gossip_metrics(&dna_hash).await;
+    assert!(alice_metrics.ops_sent > 0);
+    assert!(bobbo_metrics.ops_received > 0);
+    assert!(alice_metrics.rounds_initiated + alice_metrics.rounds_accepted > 0);
+
+    Ok(())
+}
+
 #[cfg(feature = "test_utils")]
 #[tokio::test(flavor = "multi_thread")]
 async fn fullsync_sharded_local_gossip() -> anyhow::Result<()> {
@@ -0,0 +1,392 @@
+#![cfg(feature = "test_utils")]
+
+use ::fixt::prelude::*;
+use hdk::prelude::*;
+use holochain::conductor::handle::DevSettingsDelta;
+use holochain::core::ValidationOverride;
+use holochain::sweettest::{SweetCell, SweetConductor, SweetConductorBatch, SweetDnaFile};
+use holochain::test_utils::consistency_10s;
+use holochain_keystore::test_keystore::spawn_test_keystore;
+use holochain_p2p::actor::HolochainP2pRefToDna;
+use holochain_p2p::AgentPubKeyExt;
+use holochain_sqlite::error::DatabaseResult;
+use holochain_sqlite::rusqlite::{named_params, OptionalExtension};
+use holochain_state::mutations::{insert_op, set_when_integrated, StateMutationResult};
+use holochain_state::prelude::{
+    test_authored_env, test_dht_env, test_p2p_agent_store_env, TestEnv,
+};
+use holochain_types::prelude::*;
+use kitsune_p2p::fixt::AgentInfoSignedFixturator;
+
+/// An InlineZome which creates a unit entry
+fn create_unit_zome() -> InlineZome {
+    let entry_def = EntryDef::default_with_id("unit");
+    InlineZome::new_unique(vec![entry_def.clone()]).callback("create_unit", move |api, ()| {
+        let entry = Entry::app(().try_into().unwrap()).unwrap();
+        let hash = api.create(CreateInput::new(
+            entry_def.id.clone(),
+            entry,
+            ChainTopOrdering::default(),
+        ))?;
+        Ok(hash)
+    })
+}
+
+/// An agent activity op authored by `author`
+fn agent_activity_op(author: AgentPubKey) -> DhtOpHashed {
+    let mut create = fixt!(Create);
+    create.author = author;
+    DhtOpHashed::from_content_sync(DhtOp::RegisterAgentActivity(
+        fixt!(Signature),
+        create.into(),
+    ))
+}
+
+async fn setup() -> (SweetConductor, SweetCell) {
+    let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", create_unit_zome())
+        .await
+        .unwrap();
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", &[dna_file]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    (conductor, cell)
+}
+
+/// A cell on a stub network with no conductor, for injecting ops directly.
+/// The test databases are returned too as they must outlive the cell.
+async fn stub_cell() -> (
+    SweetCell,
+    (
+        TestEnv<DbKindAuthored>,
+        TestEnv<DbKindDht>,
+        TestEnv<DbKindP2pAgentStore>,
+    ),
+) {
+    let authored_env = test_authored_env();
+    let dht_env = test_dht_env();
+    let cell_id = fixt!(CellId);
+    let network = holochain_p2p::stub_network()
+        .await
+        .to_dna(cell_id.dna_hash().clone());
+    let p2p_env = test_p2p_agent_store_env();
+    let cell = SweetCell::new(
+        cell_id,
+        authored_env.env(),
+        dht_env.env(),
+        network,
+        p2p_env.env(),
+        ValidationOverride::default(),
+        spawn_test_keystore().await.unwrap(),
+    );
+    (cell, (authored_env, dht_env, p2p_env))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn chain_head_follows_zome_calls() {
+    observability::test_run().ok();
+    let (conductor, cell) = setup().await;
+
+    // The first call also runs init so only measure from the second.
+    let _: HeaderHash = conductor.call(&cell.zome("zome1"), "create_unit", ()).await;
+    let length = cell.get_chain_length().await.unwrap();
+
+    let hash: HeaderHash = conductor.call(&cell.zome("zome1"), "create_unit", ()).await;
+    cell.assert_chain_length(length + 1).await;
+    assert_eq!(cell.get_chain_head().await.unwrap(), hash);
+
+    for _ in 0..3 {
+        let _: HeaderHash = conductor.call(&cell.zome("zome1"), "create_unit", ()).await;
+    }
+    cell.assert_chain_length(length + 4).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn source_chain_elements_follow_zome_calls() {
+    observability::test_run().ok();
+    let (conductor, cell) = setup().await;
+
+    let mut hashes = Vec::new();
+    for _ in 0..3 {
+        let hash: HeaderHash = conductor.call(&cell.zome("zome1"), "create_unit", ()).await;
+        hashes.push(hash);
+    }
+
+    let elements = cell.get_source_chain_elements().await.unwrap();
+    assert_eq!(
+        elements.len() as u32,
+        cell.get_chain_length().await.unwrap()
+    );
+    assert_eq!(elements.len() as u32, cell.get_source_chain_length().await);
+
+    // Elements come back in chain order, starting from genesis.
+    assert_eq!(elements[0].header().header_type(), HeaderType::Dna);
+    for (seq, element) in elements.iter().enumerate() {
+        assert_eq!(element.header().header_seq(), seq as u32);
+    }
+
+    // The units created by the zome calls are the last elements on the chain.
+    let created = &elements[elements.len() - 3..];
+    assert_eq!(
+        created
+            .iter()
+            .map(|element| element.header_address().clone())
+            .collect::<Vec<_>>(),
+        hashes
+    );
+    for element in created {
+        assert_eq!(element.header().header_type(), HeaderType::Create);
+        assert_eq!(
+            element.entry().as_option(),
+            Some(&Entry::app(().try_into().unwrap()).unwrap())
+        );
+    }
+
+    let head = cell.get_chain_head_element().await.unwrap().unwrap();
+    assert_eq!(head.header_address(), hashes.last().unwrap());
+    assert_eq!(&head, elements.last().unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn count_injected_ops() {
+    let (cell, _envs) = stub_cell().await;
+    let alice = fixt!(AgentPubKey);
+    let bob = fixt!(AgentPubKey);
+
+    let ops: Vec<_> = std::iter::repeat(alice.clone())
+        .take(3)
+        .chain(std::iter::repeat(bob.clone()).take(2))
+        .map(agent_activity_op)
+        .collect();
+    cell.dht_env()
+        .async_commit(move |txn| {
+            for (i, op) in ops.iter().enumerate() {
+                insert_op(txn, op)?;
+                // Only integrate the first two.
+                if i < 2 {
+                    set_when_integrated(txn, op.as_hash(), Timestamp::now())?;
+                }
+            }
+            StateMutationResult::Ok(())
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(cell.count_ops_in_db().await.unwrap(), 5);
+    assert_eq!(cell.count_integrated_ops().await.unwrap(), 2);
+    assert_eq!(cell.count_ops_for_author(&alice).await.unwrap(), 3);
+    assert_eq!(cell.count_ops_for_author(&bob).await.unwrap(), 2);
+    assert_eq!(
+        cell.count_ops_for_author(&fixt!(AgentPubKey))
+            .await
+            .unwrap(),
+        0
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn assert_injected_op_count() {
+    let (cell, _envs) = stub_cell().await;
+
+    cell.assert_op_count(0).await;
+    cell.inject_ops((0..5).map(|_| agent_activity_op(fixt!(AgentPubKey))))
+        .await;
+    cell.assert_op_count(5).await;
+    cell.assert_op_count_within(4, 6).await;
+    cell.assert_op_count_within(5, 5).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[should_panic(expected = "Expected 4 to 6 integrated ops")]
+async fn assert_injected_op_count_out_of_range() {
+    let (cell, _envs) = stub_cell().await;
+
+    cell.inject_ops((0..3).map(|_| agent_activity_op(fixt!(AgentPubKey))))
+        .await;
+    cell.assert_op_count_within(4, 6).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn agent_info_in_p2p_store() {
+    observability::test_run().ok();
+    let (_conductor, cell) = setup().await;
+
+    let info = cell.get_agent_info().await.unwrap().unwrap();
+    assert_eq!(info.agent, cell.agent_pubkey().to_kitsune());
+
+    let peer_info = fixt!(AgentInfoSigned, Unpredictable);
+    cell.inject_agent_info(peer_info.clone()).await;
+    let all_infos = cell.get_all_peer_agent_infos().await.unwrap();
+    assert_eq!(all_infos.len(), 2);
+    assert!(all_infos.contains(&info));
+    assert!(all_infos.contains(&peer_info));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn injected_ops_are_integrated() {
+    observability::test_run().ok();
+    let (_conductor, cell) = setup().await;
+    holochain::test_utils::consistency_10s(&[&cell]).await;
+    let integrated = cell.count_integrated_ops().await.unwrap();
+
+    let store_element = |header: Header| {
+        DhtOpHashed::from_content_sync(DhtOp::StoreElement(fixt!(Signature), header, None))
+    };
+    cell.inject_op(
+        store_element(fixt!(Create).into()),
+        ValidationStatus::Rejected,
+    )
+    .await;
+    assert_eq!(cell.count_integrated_ops().await.unwrap(), integrated + 1);
+
+    cell.inject_ops((0..3).map(|_| store_element(fixt!(Create).into())))
+        .await;
+    assert_eq!(cell.count_integrated_ops().await.unwrap(), integrated + 4);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn skipped_validation_integrates_bad_ops() {
+    observability::test_run().ok();
+    let (conductor, cell) = setup().await;
+    holochain::test_utils::consistency_10s(&[&cell]).await;
+    let integrated = cell.count_integrated_ops().await.unwrap();
+
+    cell.set_validation_skip(true);
+
+    // Ops from an unknown author with made up signatures and previous headers.
+    let ops: Vec<_> = (0..3)
+        .map(|_| {
+            DhtOpHashed::from_content_sync(DhtOp::StoreElement(
+                fixt!(Signature),
+                fixt!(Create).into(),
+                None,
+            ))
+        })
+        .collect();
+    cell.dht_env()
+        .async_commit(move |txn| {
+            for op in ops.iter() {
+                insert_op(txn, op)?;
+            }
+            StateMutationResult::Ok(())
+        })
+        .await
+        .unwrap();
+    conductor
+        .get_cell_triggers(cell.cell_id())
+        .unwrap()
+        .sys_validation
+        .trigger();
+
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(10);
+    while cell.count_integrated_ops().await.unwrap() < integrated + 3 {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "Injected ops were never integrated"
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn overridden_validation_integrates_with_status() {
+    observability::test_run().ok();
+    for status in [ValidationStatus::Rejected, ValidationStatus::Abandoned] {
+        let (conductor, cell) = setup().await;
+        cell.set_validation_override(status);
+
+        let op = DhtOpHashed::from_content_sync(DhtOp::StoreElement(
+            fixt!(Signature),
+            fixt!(Create).into(),
+            None,
+        ));
+        let op_hash = op.as_hash().clone();
+        cell.dht_env()
+            .async_commit(move |txn| insert_op(txn, &op))
+            .await
+            .unwrap();
+        conductor
+            .get_cell_triggers(cell.cell_id())
+            .unwrap()
+            .sys_validation
+            .trigger();
+
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(10);
+        let integrated_status = loop {
+            let op_hash = op_hash.clone();
+            let integrated: Option<ValidationStatus> = cell
+                .dht_env()
+                .async_reader(move |txn| {
+                    DatabaseResult::Ok(
+                        txn.query_row(
+                            "SELECT validation_status FROM DhtOp
+                            WHERE hash = :hash AND when_integrated IS NOT NULL",
+                            named_params! { ":hash": op_hash },
+                            |row| row.get(0),
+                        )
+                        .optional()?,
+                    )
+                })
+                .await
+                .unwrap();
+            if let Some(integrated) = integrated {
+                break integrated;
+            }
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "Op overridden to {:?} was never integrated",
+                status
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        };
+        assert_eq!(integrated_status, status);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn forced_gossip_reaches_peer() {
+    observability::test_run().ok();
+    let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+    // Turn off publish so the ops can only arrive through gossip.
+    for c in conductors.iter() {
+        c.update_dev_settings(DevSettingsDelta {
+            publish: Some(false),
+            ..Default::default()
+        });
+    }
+    let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", create_unit_zome())
+        .await
+        .unwrap();
+    let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+    conductors.exchange_peer_info().await;
+    let ((alice,), (bob,)) = apps.into_tuples();
+
+    let _: HeaderHash = conductors[0]
+        .call(&alice.zome("zome1"), "create_unit", ())
+        .await;
+    // Alice's ops can only be gossiped once she has integrated them.
+    consistency_10s(&[&alice]).await;
+
+    // Bob's forced round returns once he has received and stored alice's ops.
+    bob.force_gossip_round(alice.agent_pubkey()).await;
+    assert_ne!(
+        bob.count_ops_for_author(alice.agent_pubkey())
+            .await
+            .unwrap(),
+        0,
+        "Bob did not receive alice's ops in the forced round"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[should_panic(expected = "Failed to trigger gossip")]
+async fn forced_gossip_with_unknown_agent_fails() {
+    observability::test_run().ok();
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", create_unit_zome())
+        .await
+        .unwrap();
+    let app = conductor.setup_app("app", &[dna_file]).await.unwrap();
+    let (alice,) = app.into_tuple();
+
+    alice.force_gossip_round(&fixt!(AgentPubKey)).await;
+}
@@ -278,6 +278,12 @@ pub fn create_config(port: u16, environment_path: PathBuf) -> ConductorConfig {
         dpki: None,
         keystore: KeystoreConfig::DangerTestKeystoreLegacyDeprecated,
         db_sync_strategy: DbSyncStrategy::default(),
+        wal_checkpoint_interval_secs: None,
+        max_pending_retries: None,
+        pending_retry_backoff_ms: None,
+        verify_chain_integrity_on_startup: false,
+        allow_pruning: false,
+        max_gossip_rounds_per_second: None,
     }
 }
 
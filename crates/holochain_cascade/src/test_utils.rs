@@ -221,6 +221,13 @@ impl HolochainP2pDnaT for PassThroughNetwork {
         todo!()
     }
 
+    async fn trigger_gossip(
+        &self,
+        _to_agent: Option<AgentPubKey>,
+    ) -> actor::HolochainP2pResult<()> {
+        todo!()
+    }
+
     async fn join(&self, _agent: AgentPubKey) -> actor::HolochainP2pResult<()> {
         todo!()
     }
@@ -395,6 +402,13 @@ impl HolochainP2pDnaT for MockNetwork {
         todo!()
     }
 
+    async fn trigger_gossip(
+        &self,
+        _to_agent: Option<AgentPubKey>,
+    ) -> actor::HolochainP2pResult<()> {
+        todo!()
+    }
+
     async fn join(&self, _agent: AgentPubKey) -> actor::HolochainP2pResult<()> {
         todo!()
     }
@@ -54,6 +54,41 @@ pub struct ConductorConfig {
     /// This is best left at it's default value unless you know what you
     /// are doing.
     pub db_sync_strategy: DbSyncStrategy,
+
+    #[serde(default)]
+    /// How often, in seconds, to checkpoint the write-ahead log of the
+    /// per-DNA databases in the background.
+    /// If omitted, checkpoints are left to SQLite's automatic checkpointing.
+    pub wal_checkpoint_interval_secs: Option<u64>,
+
+    #[serde(default)]
+    /// How many times sys validation retries an op that is pending on a
+    /// dependency before abandoning it.
+    /// If omitted, pending ops are retried until their dependencies arrive.
+    pub max_pending_retries: Option<u32>,
+
+    #[serde(default)]
+    /// How long, in milliseconds, sys validation waits before retrying an
+    /// op that is pending on a dependency.
+    /// If omitted, pending ops are retried whenever sys validation runs.
+    pub pending_retry_backoff_ms: Option<u64>,
+
+    #[serde(default)]
+    /// Check every running cell's source chain for forks and gaps when the
+    /// conductor starts up. Cells whose chains fail the check are not started.
+    pub verify_chain_integrity_on_startup: bool,
+
+    #[serde(default)]
+    /// Allow old headers and entries to be pruned from source chains.
+    /// Pruning is refused unless this is set.
+    pub allow_pruning: bool,
+
+    #[serde(default)]
+    /// The most gossip rounds each gossip loop initiates per second,
+    /// for nodes on constrained networks.
+    /// If set, this overrides `gossip_max_rounds_per_second` in the
+    /// network tuning params. If omitted, gossip rounds are not limited.
+    pub max_gossip_rounds_per_second: Option<f64>,
     //
     //
     // /// Which signals to emit
@@ -125,6 +160,12 @@ pub mod tests {
                 keystore: KeystoreConfig::DangerTestKeystoreLegacyDeprecated,
                 admin_interfaces: None,
                 db_sync_strategy: DbSyncStrategy::default(),
+                wal_checkpoint_interval_secs: None,
+                max_pending_retries: None,
+                pending_retry_backoff_ms: None,
+                verify_chain_integrity_on_startup: false,
+                allow_pruning: false,
+                max_gossip_rounds_per_second: None,
             }
         );
     }
@@ -175,6 +216,12 @@ pub mod tests {
       network_type: quic_bootstrap
 
     db_sync_strategy: Fast
+    wal_checkpoint_interval_secs: 300
+    max_pending_retries: 20
+    pending_retry_backoff_ms: 500
+    verify_chain_integrity_on_startup: true
+    allow_pruning: true
+    max_gossip_rounds_per_second: 1.5
     "#;
         let result: ConductorConfigResult<ConductorConfig> = config_from_yaml(yaml);
         use holochain_p2p::kitsune_p2p::*;
@@ -219,6 +266,12 @@ pub mod tests {
                 }]),
                 network: Some(network_config),
                 db_sync_strategy: DbSyncStrategy::Fast,
+                wal_checkpoint_interval_secs: Some(300),
+                max_pending_retries: Some(20),
+                pending_retry_backoff_ms: Some(500),
+                verify_chain_integrity_on_startup: true,
+                allow_pruning: true,
+                max_gossip_rounds_per_second: Some(1.5),
             }
         );
     }
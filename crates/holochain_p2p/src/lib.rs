@@ -130,6 +130,12 @@ pub trait HolochainP2pDnaT {
 
     /// New data has been integrated and is ready for gossiping.
     async fn new_integrated_data(&self) -> actor::HolochainP2pResult<()>;
+
+    /// Initiate gossip with `to_agent` as soon as possible.
+    /// If `to_agent` is `None` gossip with every known remote agent.
+    /// Resolves once the rounds have completed, or with an error if
+    /// there is no address for an agent.
+    async fn trigger_gossip(&self, to_agent: Option<AgentPubKey>) -> actor::HolochainP2pResult<()>;
 }
 
 /// A wrapper around HolochainP2pSender that partially applies the dna_hash / agent_pub_key.
@@ -324,6 +330,12 @@ impl HolochainP2pDnaT for HolochainP2pDna {
             .new_integrated_data((*self.dna_hash).clone())
             .await
     }
+
+    async fn trigger_gossip(&self, to_agent: Option<AgentPubKey>) -> actor::HolochainP2pResult<()> {
+        self.sender
+            .trigger_gossip((*self.dna_hash).clone(), to_agent)
+            .await
+    }
 }
 
 pub use kitsune_p2p::dht_arc;
@@ -1250,6 +1250,23 @@ impl HolochainP2pHandler for HolochainP2pActor {
         )
     }
 
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_trigger_gossip(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: Option<AgentPubKey>,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let to_agent = to_agent.map(|a| a.into_kitsune());
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(
+            async move { Ok(kitsune_p2p.trigger_gossip(space, to_agent).await?) }
+                .boxed()
+                .into(),
+        )
+    }
+
     #[tracing::instrument(skip(self), level = "trace")]
     fn handle_authority_for_hash(
         &mut self,
@@ -1306,4 +1323,16 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .boxed()
         .into())
     }
+
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_gossip_metrics(
+        &mut self,
+        dna_hash: DnaHash,
+    ) -> HolochainP2pHandlerResult<kitsune_p2p::metrics::GossipMetrics> {
+        let space = dna_hash.into_kitsune();
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move { Ok(kitsune_p2p.gossip_metrics(space).await?) }
+            .boxed()
+            .into())
+    }
 }
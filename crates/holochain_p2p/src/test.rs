@@ -112,6 +112,13 @@ impl HolochainP2pHandler for StubNetwork {
     fn handle_new_integrated_data(&mut self, dna_hash: DnaHash) -> HolochainP2pHandlerResult<()> {
         Err("stub".into())
     }
+    fn handle_trigger_gossip(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: Option<AgentPubKey>,
+    ) -> HolochainP2pHandlerResult<()> {
+        Err("stub".into())
+    }
     fn handle_authority_for_hash(
         &mut self,
         dna_hash: DnaHash,
@@ -133,6 +140,12 @@ impl HolochainP2pHandler for StubNetwork {
     ) -> HolochainP2pHandlerResult<String> {
         Err("stub".into())
     }
+    fn handle_gossip_metrics(
+        &mut self,
+        dna_hash: DnaHash,
+    ) -> HolochainP2pHandlerResult<kitsune_p2p::metrics::GossipMetrics> {
+        Err("stub".into())
+    }
 }
 
 /// Spawn a stub network that doesn't respond to any messages.
@@ -268,6 +268,12 @@ ghost_actor::ghost_chan! {
         /// New data has been integrated and is ready for gossiping.
         fn new_integrated_data(dna_hash: DnaHash) -> ();
 
+        /// Initiate gossip with `to_agent` as soon as possible.
+        /// If `to_agent` is `None` gossip with every known remote agent.
+        /// Resolves once the rounds have completed, or with an error if
+        /// there is no address for an agent.
+        fn trigger_gossip(dna_hash: DnaHash, to_agent: Option<AgentPubKey>) -> ();
+
         /// Check if any local agent in this space is an authority for a hash.
         fn authority_for_hash(dna_hash: DnaHash, dht_hash: AnyDhtHash) -> bool;
 
@@ -283,6 +289,9 @@ ghost_actor::ghost_chan! {
         fn dump_network_metrics(
             dna_hash: Option<DnaHash>,
         ) -> String;
+
+        /// Get the totals of gossip activity for a dna.
+        fn gossip_metrics(dna_hash: DnaHash) -> kitsune_p2p::metrics::GossipMetrics;
     }
 }
 
@@ -0,0 +1,55 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use holochain_sqlite::conn::DbSyncLevel;
+use holochain_sqlite::db::{DbKindWasm, DbWrite, WriteManager};
+use holochain_sqlite::error::DatabaseResult;
+use holochain_sqlite::rusqlite::params;
+
+/// Number of rows inserted per iteration, each in its own transaction.
+const INSERTS: usize = 100;
+
+/// Compare insert throughput at the `Normal` and `Full` sync levels.
+pub fn insert_sync_level(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert_sync_level");
+    group.sample_size(10);
+    for sync_level in [DbSyncLevel::Normal, DbSyncLevel::Full] {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let env = DbWrite::open_with_sync_level(tmpdir.path(), DbKindWasm, sync_level).unwrap();
+        env.conn()
+            .unwrap()
+            .with_commit_sync(|txn| {
+                txn.execute(
+                    "CREATE TABLE IF NOT EXISTS BenchInsert (id INTEGER PRIMARY KEY, data BLOB)",
+                    [],
+                )?;
+                DatabaseResult::Ok(())
+            })
+            .unwrap();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{:?}", sync_level)),
+            &env,
+            |b, env| {
+                b.iter(|| {
+                    let mut conn = env.conn().unwrap();
+                    for _ in 0..INSERTS {
+                        conn.with_commit_sync(|txn| {
+                            txn.execute(
+                                "INSERT INTO BenchInsert (data) VALUES (?)",
+                                params![vec![0u8; 256]],
+                            )?;
+                            DatabaseResult::Ok(())
+                        })
+                        .unwrap();
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, insert_sync_level);
+
+criterion_main!(benches);
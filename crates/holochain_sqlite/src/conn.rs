@@ -103,21 +103,249 @@ impl Databases {
     }
 }
 
+/// Connection pool settings for a database.
+/// Any setting left as `None` falls back to the default for that setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DbPoolConfig {
+    /// The maximum number of connections the pool will hold.
+    /// Defaults to the number of reader threads plus one for the writer.
+    pub max_size: Option<u32>,
+    /// The number of idle connections the pool tries to maintain.
+    /// Defaults to zero.
+    pub min_idle: Option<u32>,
+    /// How long to wait for a connection before giving up.
+    /// Defaults to the r2d2 default of 30 seconds.
+    pub connection_timeout: Option<Duration>,
+    /// How often to checkpoint the write-ahead log in the background.
+    /// Defaults to never, leaving checkpoints to SQLite's automatic checkpointing.
+    pub checkpoint_interval: Option<Duration>,
+    /// The mode used for background checkpoints.
+    pub checkpoint_mode: WalCheckpointMode,
+}
+
+impl DbPoolConfig {
+    /// Set the maximum number of connections.
+    pub fn with_max_size(mut self, max_size: u32) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Set the number of idle connections to maintain.
+    pub fn with_min_idle(mut self, min_idle: u32) -> Self {
+        self.min_idle = Some(min_idle);
+        self
+    }
+
+    /// Set how long to wait for a connection.
+    pub fn with_connection_timeout(mut self, connection_timeout: Duration) -> Self {
+        self.connection_timeout = Some(connection_timeout);
+        self
+    }
+
+    /// Checkpoint the write-ahead log in the background at the given interval.
+    pub fn with_checkpoint_interval(
+        mut self,
+        checkpoint_interval: Duration,
+        checkpoint_mode: WalCheckpointMode,
+    ) -> Self {
+        self.checkpoint_interval = Some(checkpoint_interval);
+        self.checkpoint_mode = checkpoint_mode;
+        self
+    }
+}
+
+/// The mode of a write-ahead log checkpoint.
+/// Corresponds to the argument of the `PRAGMA wal_checkpoint` pragma.
+/// See [sqlite documentation](https://www.sqlite.org/pragma.html#pragma_wal_checkpoint).
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum WalCheckpointMode {
+    /// Checkpoint as many frames as possible without waiting for readers or writers. Default.
+    Passive,
+    /// Wait for writers to finish then checkpoint every frame.
+    Full,
+    /// Like [`WalCheckpointMode::Full`], but also wait for readers
+    /// so the next writer restarts the log from the beginning.
+    Restart,
+    /// Like [`WalCheckpointMode::Restart`], but also truncate the log file to zero bytes.
+    Truncate,
+}
+
+impl Default for WalCheckpointMode {
+    fn default() -> Self {
+        WalCheckpointMode::Passive
+    }
+}
+
+impl WalCheckpointMode {
+    fn as_pragma_arg(&self) -> &'static str {
+        match self {
+            WalCheckpointMode::Passive => "PASSIVE",
+            WalCheckpointMode::Full => "FULL",
+            WalCheckpointMode::Restart => "RESTART",
+            WalCheckpointMode::Truncate => "TRUNCATE",
+        }
+    }
+}
+
 pub(crate) fn new_connection_pool(
-    path: Option<&Path>,
+    path: &Path,
     synchronous_level: DbSyncLevel,
+    pool_config: DbPoolConfig,
 ) -> ConnectionPool {
     use r2d2_sqlite::SqliteConnectionManager;
-    let manager = match path {
-        Some(path) => SqliteConnectionManager::file(path),
-        None => SqliteConnectionManager::memory(),
-    };
-    let customizer = Box::new(ConnCustomizer { synchronous_level });
+    let manager = SqliteConnectionManager::file(path);
+    let customizer = Box::new(ConnCustomizer {
+        synchronous_level,
+        query_only: false,
+    });
     // We need the same amount of connections as reader threads plus one for the writer thread.
-    let max_cons = num_read_threads() + 1;
+    let max_cons = pool_config
+        .max_size
+        .unwrap_or(num_read_threads() as u32 + 1);
+    let mut builder = r2d2::Pool::builder()
+        .max_size(max_cons)
+        // Never maintain idle connections unless configured to
+        .min_idle(Some(pool_config.min_idle.unwrap_or(0)))
+        // Close connections after 30-60 seconds of idle time
+        .idle_timeout(Some(Duration::from_secs(30)))
+        .thread_pool(R2D2_THREADPOOL.clone())
+        .connection_customizer(customizer);
+    if let Some(connection_timeout) = pool_config.connection_timeout {
+        builder = builder.connection_timeout(connection_timeout);
+    }
+    builder.build(manager).unwrap()
+}
+
+/// The URI of a new in-memory database.
+///
+/// Every connection opened with this URI shares the same database through
+/// SQLite's `memdb` VFS, which locks like a database file so readers and
+/// writers wait on the busy timeout. SQLCipher predates shared `memdb`
+/// databases, so with encryption the connections share a cache instead
+/// and conflicting readers fail immediately.
+pub(crate) fn new_in_memory_uri() -> String {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    if cfg!(feature = "db-encryption") {
+        format!("file:holochain-in-memory-{}?mode=memory&cache=shared", id)
+    } else {
+        format!("file:/holochain-in-memory-{}?vfs=memdb", id)
+    }
+}
+
+fn in_memory_manager(uri: &str) -> r2d2_sqlite::SqliteConnectionManager {
+    r2d2_sqlite::SqliteConnectionManager::file(uri)
+        .with_flags(OpenFlags::default() | OpenFlags::SQLITE_OPEN_URI)
+}
+
+/// Create a pool of connections to the in-memory database at `uri`.
+/// Connections are never closed so the database lives as long as the pool.
+pub(crate) fn new_connection_pool_in_memory(
+    uri: &str,
+    synchronous_level: DbSyncLevel,
+    pool_config: DbPoolConfig,
+) -> ConnectionPool {
+    let manager = in_memory_manager(uri);
+    let customizer = Box::new(ConnCustomizer {
+        synchronous_level,
+        query_only: false,
+    });
+    let max_cons = pool_config
+        .max_size
+        .unwrap_or(num_read_threads() as u32 + 1);
+    let mut builder = r2d2::Pool::builder()
+        .max_size(max_cons)
+        // Keep at least one connection open so the database isn't dropped
+        .min_idle(Some(pool_config.min_idle.unwrap_or(1).max(1)))
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .thread_pool(R2D2_THREADPOOL.clone())
+        .connection_customizer(customizer);
+    if let Some(connection_timeout) = pool_config.connection_timeout {
+        builder = builder.connection_timeout(connection_timeout);
+    }
+    builder.build(manager).unwrap()
+}
+
+/// Periodically checkpoint the write-ahead log of the pool's database
+/// until the returned task is dropped.
+/// Does nothing if there is no tokio runtime to run the task on.
+pub(crate) fn spawn_checkpoint_task(
+    pool: ConnectionPool,
+    interval: Duration,
+    mode: WalCheckpointMode,
+) -> Option<CheckpointTask> {
+    let runtime = match tokio::runtime::Handle::try_current() {
+        Ok(runtime) => runtime,
+        Err(_) => {
+            tracing::warn!("No tokio runtime available, WAL checkpoints will not run");
+            return None;
+        }
+    };
+    let handle = runtime.spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        // The first tick completes immediately.
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let pool = pool.clone();
+            let result =
+                tokio::task::spawn_blocking(move || PConn::new(pool.get()?).checkpoint(mode)).await;
+            match result {
+                Ok(Ok(_)) => (),
+                Ok(Err(e)) => tracing::warn!(?e, "WAL checkpoint failed"),
+                Err(e) => tracing::warn!(?e, "WAL checkpoint task failed"),
+            }
+        }
+    });
+    Some(CheckpointTask(handle))
+}
+
+/// A background WAL checkpoint task, which is aborted when dropped.
+pub(crate) struct CheckpointTask(tokio::task::JoinHandle<()>);
+
+impl Drop for CheckpointTask {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Create a pool of read-only connections to the database file at `path`.
+///
+/// Every connection has `PRAGMA query_only` set, so readers never take
+/// connections away from writers. There is one connection per reader thread.
+/// These connections are never handed out for writing.
+pub(crate) fn new_read_connection_pool(
+    path: &Path,
+    synchronous_level: DbSyncLevel,
+) -> ConnectionPool {
+    read_connection_pool(
+        r2d2_sqlite::SqliteConnectionManager::file(path),
+        synchronous_level,
+    )
+}
+
+/// Create a pool of read-only connections to the in-memory database at `uri`.
+/// See [`new_read_connection_pool`].
+pub(crate) fn new_read_connection_pool_in_memory(
+    uri: &str,
+    synchronous_level: DbSyncLevel,
+) -> ConnectionPool {
+    read_connection_pool(in_memory_manager(uri), synchronous_level)
+}
+
+fn read_connection_pool(
+    manager: r2d2_sqlite::SqliteConnectionManager,
+    synchronous_level: DbSyncLevel,
+) -> ConnectionPool {
+    let customizer = Box::new(ConnCustomizer {
+        synchronous_level,
+        query_only: true,
+    });
     r2d2::Pool::builder()
-        // Only up to 20 connections at a time
-        .max_size(max_cons as u32)
+        .max_size(num_read_threads() as u32)
         // Never maintain idle connections
         .min_idle(Some(0))
         // Close connections after 30-60 seconds of idle time
@@ -131,6 +359,7 @@ pub(crate) fn new_connection_pool(
 #[derive(Debug)]
 struct ConnCustomizer {
     synchronous_level: DbSyncLevel,
+    query_only: bool,
 }
 
 /// The sqlite synchronous level.
@@ -138,6 +367,9 @@ struct ConnCustomizer {
 /// See [sqlite documentation](https://www.sqlite.org/pragma.html#pragma_synchronous).
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
 pub enum DbSyncLevel {
+    /// Like [`DbSyncLevel::Full`] but also syncs the directory after
+    /// deleting a rollback journal. Not needed for WAL mode.
+    Extra,
     /// Use xSync for all writes. Not needed for WAL mode.
     Full,
     /// Sync at critical moments. Default.
@@ -177,6 +409,9 @@ impl Default for DbSyncStrategy {
 impl r2d2::CustomizeConnection<Connection, rusqlite::Error> for ConnCustomizer {
     fn on_acquire(&self, conn: &mut Connection) -> Result<(), rusqlite::Error> {
         initialize_connection(conn, self.synchronous_level)?;
+        if self.query_only {
+            conn.pragma_update(None, "query_only", &true)?;
+        }
         Ok(())
     }
 }
@@ -212,6 +447,7 @@ pub(crate) fn initialize_connection(
     conn.pragma_update(None, "foreign_keys", &"ON".to_string())?;
 
     match synchronous_level {
+        DbSyncLevel::Extra => conn.pragma_update(None, "synchronous", &"3".to_string())?,
         DbSyncLevel::Full => conn.pragma_update(None, "synchronous", &"2".to_string())?,
         DbSyncLevel::Normal => conn.pragma_update(None, "synchronous", &"1".to_string())?,
         DbSyncLevel::Off => conn.pragma_update(None, "synchronous", &"0".to_string())?,
@@ -220,6 +456,71 @@ pub(crate) fn initialize_connection(
     Ok(())
 }
 
+fn integrity_check(conn: &Connection) -> DatabaseResult<Vec<String>> {
+    let problems = match run_check(conn, "integrity_check") {
+        Ok(problems) => problems,
+        Err(e) if is_corrupt(&e) => vec![e.to_string()],
+        Err(e) => return Err(e.into()),
+    };
+    Ok(problems.into_iter().filter(|p| p != "ok").collect())
+}
+
+fn quick_check(conn: &Connection) -> DatabaseResult<bool> {
+    match run_check(conn, "quick_check") {
+        Ok(problems) => Ok(problems == ["ok"]),
+        Err(e) if is_corrupt(&e) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn run_check(conn: &Connection, pragma: &str) -> rusqlite::Result<Vec<String>> {
+    conn.prepare(&format!("PRAGMA {}", pragma))?
+        .query_map([], |row| row.get(0))?
+        .collect()
+}
+
+/// Corruption may be reported as an error rather than as check output.
+/// SQLCipher reports pages which fail authentication as a generic error.
+fn is_corrupt(e: &rusqlite::Error) -> bool {
+    match e {
+        rusqlite::Error::SqliteFailure(rusqlite::ffi::Error { code, .. }, _) => match code {
+            ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase => true,
+            ErrorCode::Unknown => cfg!(feature = "db-encryption"),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Run [`PConn::quick_check`] on every database file under the given directory.
+/// Returns the paths of the databases that failed the check.
+pub fn quick_check_all(root: &Path) -> DatabaseResult<Vec<PathBuf>> {
+    let mut failed = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().map_or(false, |ext| ext == "sqlite3") {
+                let ok = Connection::open(&path)
+                    .and_then(|mut conn| {
+                        initialize_connection(&mut conn, DbSyncLevel::default())?;
+                        Ok(conn)
+                    })
+                    .map_err(DatabaseError::from)
+                    .and_then(|conn| quick_check(&conn))
+                    .unwrap_or(false);
+                if !ok {
+                    failed.push(path);
+                }
+            }
+        }
+    }
+    failed.sort();
+    Ok(failed)
+}
+
 #[cfg(feature = "db-encryption")]
 /// Simulate getting an encryption key from Lair.
 fn get_encryption_key_shim() -> [u8; 32] {
@@ -241,4 +542,231 @@ impl PConn {
     pub(crate) fn new(inner: PConnInner) -> Self {
         Self { inner }
     }
+
+    /// Run a full integrity check of the database.
+    /// Returns every problem SQLite reports, or an empty list if the database is ok.
+    pub fn integrity_check(&mut self) -> DatabaseResult<Vec<String>> {
+        integrity_check(self)
+    }
+
+    /// Run a faster, less thorough integrity check of the database.
+    /// Returns `true` if no problems were found.
+    pub fn quick_check(&mut self) -> DatabaseResult<bool> {
+        quick_check(self)
+    }
+
+    /// Checkpoint the write-ahead log.
+    /// Returns the number of frames in the log and the number of
+    /// frames that were checkpointed into the database.
+    pub fn checkpoint(&mut self, mode: WalCheckpointMode) -> DatabaseResult<(u32, u32)> {
+        let sql = format!("PRAGMA wal_checkpoint({})", mode.as_pragma_arg());
+        Ok(self.query_row(&sql, [], |row| Ok((row.get(1)?, row.get(2)?)))?)
+    }
+
+    /// Get the `EXPLAIN QUERY PLAN` output for the given query without running it.
+    #[cfg(feature = "explain_queries")]
+    pub fn explain_query(
+        &mut self,
+        sql: &str,
+        params: &[&dyn ToSql],
+    ) -> DatabaseResult<Vec<QueryPlanRow>> {
+        let rows = self
+            .prepare(&format!("EXPLAIN QUERY PLAN {}", sql))?
+            .query_map(params, |row| {
+                Ok(QueryPlanRow {
+                    id: row.get(0)?,
+                    parent: row.get(1)?,
+                    detail: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    /// Log the query plan for the given query at the given level.
+    #[cfg(feature = "explain_queries")]
+    pub fn explain_and_log(
+        &mut self,
+        sql: &str,
+        params: &[&dyn ToSql],
+        level: tracing::Level,
+    ) -> DatabaseResult<()> {
+        let plan = self
+            .explain_query(sql, params)?
+            .into_iter()
+            .map(|row| format!("\n{} {} {}", row.id, row.parent, row.detail))
+            .collect::<String>();
+        match level {
+            tracing::Level::ERROR => tracing::error!(%sql, "query plan:{}", plan),
+            tracing::Level::WARN => tracing::warn!(%sql, "query plan:{}", plan),
+            tracing::Level::INFO => tracing::info!(%sql, "query plan:{}", plan),
+            tracing::Level::DEBUG => tracing::debug!(%sql, "query plan:{}", plan),
+            tracing::Level::TRACE => tracing::trace!(%sql, "query plan:{}", plan),
+        }
+        Ok(())
+    }
+}
+
+/// One step of an SQLite query plan.
+#[cfg(feature = "explain_queries")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryPlanRow {
+    /// The id of this step.
+    pub id: u32,
+    /// The id of the step this one is nested in, or 0 at the top level.
+    pub parent: u32,
+    /// The description of this step, e.g. `SCAN t` or `SEARCH t USING INDEX i (x=?)`.
+    pub detail: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_memory_connections_share_database() {
+        let db = DbWrite::test_in_mem(DbKindWasm).unwrap();
+        // Hold one connection so the next one must come from elsewhere in the pool.
+        let mut writer = db.conn().unwrap();
+        writer
+            .with_commit_test(|txn| {
+                txn.execute("CREATE TABLE t (x INTEGER)", []).unwrap();
+                txn.execute("INSERT INTO t (x) VALUES (1)", []).unwrap();
+            })
+            .unwrap();
+
+        let mut reader = DbRead::from(db.clone()).conn().unwrap();
+        let x: i64 = reader.with_reader_test(|txn| {
+            txn.query_row("SELECT x FROM t", [], |row| row.get(0))
+                .unwrap()
+        });
+        assert_eq!(x, 1);
+
+        // A separate in-memory database doesn't see this one's tables.
+        let other = DbWrite::test_in_mem(DbKindWasm).unwrap();
+        assert!(other
+            .conn()
+            .unwrap()
+            .query_row("SELECT x FROM t", [], |row| row.get::<_, i64>(0))
+            .is_err());
+    }
+
+    #[cfg(not(feature = "db-encryption"))]
+    #[test]
+    fn in_memory_readers_do_not_see_uncommitted_writes() {
+        let db = DbWrite::open_in_memory(DbKindWasm).unwrap();
+        db.conn()
+            .unwrap()
+            .execute_batch("CREATE TABLE t (x INTEGER); INSERT INTO t (x) VALUES (1);")
+            .unwrap();
+        let mut reader = DbRead::from(db.clone()).conn().unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let writer = std::thread::spawn(move || {
+            let mut conn = db.conn().unwrap();
+            let txn = conn.transaction().unwrap();
+            txn.execute("INSERT INTO t (x) VALUES (2)", []).unwrap();
+            tx.send(()).unwrap();
+            std::thread::sleep(Duration::from_millis(100));
+            txn.rollback().unwrap();
+        });
+
+        // The read waits for the writer instead of seeing its uncommitted row.
+        rx.recv().unwrap();
+        let count: i64 = reader
+            .query_row("SELECT COUNT(*) FROM t", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+        writer.join().unwrap();
+    }
+
+    #[test]
+    fn checks_detect_corruption() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let path = {
+            let db = DbWrite::test(&tmpdir, DbKindWasm).unwrap();
+            db.test_commit(|txn| {
+                txn.execute("CREATE TABLE t (x BLOB)", []).unwrap();
+                for _ in 0..100 {
+                    txn.execute("INSERT INTO t (x) VALUES (?)", [vec![1u8; 512]])
+                        .unwrap();
+                }
+            });
+            let mut conn = db.conn().unwrap();
+            assert!(conn.quick_check().unwrap());
+            assert!(conn.integrity_check().unwrap().is_empty());
+            conn.checkpoint(WalCheckpointMode::Truncate).unwrap();
+            db.path().clone()
+        };
+        assert!(quick_check_all(tmpdir.path()).unwrap().is_empty());
+
+        // Overwrite everything after the first page with garbage.
+        let mut bytes = std::fs::read(&path).unwrap();
+        for b in bytes.iter_mut().skip(4096) {
+            *b = 0xAB;
+        }
+        std::fs::write(&path, bytes).unwrap();
+
+        let mut conn = Connection::open(&path).unwrap();
+        initialize_connection(&mut conn, DbSyncLevel::default()).unwrap();
+        assert!(!quick_check(&conn).unwrap());
+        assert!(!integrity_check(&conn).unwrap().is_empty());
+        assert_eq!(quick_check_all(tmpdir.path()).unwrap(), vec![path]);
+    }
+
+    #[test]
+    fn checkpoint_truncates_wal() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let db = DbWrite::test(&tmpdir, DbKindWasm).unwrap();
+        db.test_commit(|txn| {
+            txn.execute("CREATE TABLE t (x BLOB)", []).unwrap();
+        });
+        for _ in 0..10 {
+            db.test_commit(|txn| {
+                txn.execute("INSERT INTO t (x) VALUES (?)", [vec![0u8; 4096]])
+                    .unwrap();
+            });
+        }
+
+        let wal_path = PathBuf::from(format!("{}-wal", db.path().display()));
+        assert!(std::fs::metadata(&wal_path).unwrap().len() > 0);
+
+        let (log, checkpointed) = db
+            .conn()
+            .unwrap()
+            .checkpoint(WalCheckpointMode::Truncate)
+            .unwrap();
+        assert_eq!(log, checkpointed);
+        assert_eq!(std::fs::metadata(&wal_path).unwrap().len(), 0);
+    }
+
+    #[cfg(feature = "explain_queries")]
+    #[test]
+    fn explain_query_lists_plan_steps() {
+        let db = DbWrite::test_in_mem(DbKindWasm).unwrap();
+        db.test_commit(|txn| {
+            txn.execute("CREATE TABLE a (id INTEGER PRIMARY KEY, x INTEGER)", [])
+                .unwrap();
+            txn.execute("CREATE TABLE b (a_id INTEGER, y INTEGER)", [])
+                .unwrap();
+        });
+
+        let sql =
+            "SELECT a.x, COUNT(b.y) FROM a JOIN b ON b.a_id = a.id WHERE b.y > ? GROUP BY a.x";
+        let mut conn = db.conn().unwrap();
+        let plan = conn.explain_query(sql, &[&1]).unwrap();
+
+        // The wording of each step differs between SQLite versions.
+        assert!(!plan.is_empty());
+        assert!(
+            plan.iter().any(|row| row
+                .detail
+                .split_whitespace()
+                .any(|word| word == "a" || word == "b")),
+            "{:?}",
+            plan
+        );
+        conn.explain_and_log(sql, &[&1], tracing::Level::DEBUG)
+            .unwrap();
+    }
 }
@@ -1,7 +1,11 @@
 //! Functions dealing with obtaining and referencing singleton databases
 
 use crate::{
-    conn::{new_connection_pool, ConnectionPool, DbSyncLevel, PConn, DATABASE_HANDLES},
+    conn::{
+        new_connection_pool, new_connection_pool_in_memory, new_in_memory_uri,
+        new_read_connection_pool, new_read_connection_pool_in_memory, spawn_checkpoint_task,
+        CheckpointTask, ConnectionPool, DbPoolConfig, DbSyncLevel, PConn, DATABASE_HANDLES,
+    },
     prelude::*,
 };
 use derive_more::Into;
@@ -102,10 +106,13 @@ pub struct DbRead<Kind: DbKindT> {
     kind: Kind,
     path: PathBuf,
     connection_pool: ConnectionPool,
+    read_connection_pool: ConnectionPool,
     write_semaphore: Arc<Semaphore>,
     read_semaphore: Arc<Semaphore>,
     max_readers: usize,
     num_readers: Arc<AtomicUsize>,
+    /// Stops when the last handle to this database is dropped.
+    _checkpoint_task: Option<Arc<CheckpointTask>>,
 }
 
 #[derive(Shrinkwrap)]
@@ -131,8 +138,10 @@ impl<Kind: DbKindT> PermittedConn for DbWrite<Kind> {
 }
 
 impl<Kind: DbKindT> DbRead<Kind> {
+    /// Get a read-only connection.
+    /// Any attempt to write with this connection will fail.
     pub fn conn(&self) -> DatabaseResult<PConn> {
-        self.connection_pooled()
+        self.connection_pooled(&self.read_connection_pool)
     }
 
     pub async fn conn_permit(&self) -> PConnPermit {
@@ -152,9 +161,9 @@ impl<Kind: DbKindT> DbRead<Kind> {
 
     /// Get a connection from the pool.
     /// TODO: We should eventually swap this for an async solution.
-    fn connection_pooled(&self) -> DatabaseResult<PConn> {
+    fn connection_pooled(&self, pool: &ConnectionPool) -> DatabaseResult<PConn> {
         let now = std::time::Instant::now();
-        let r = Ok(PConn::new(self.connection_pool.get()?));
+        let r = Ok(PConn::new(pool.get()?));
         let el = now.elapsed();
         if el.as_millis() > 20 {
             tracing::error!("Connection pool took {:?} to be free'd", el);
@@ -208,7 +217,13 @@ pub struct DbWrite<Kind: DbKindT>(DbRead<Kind>);
 impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
     /// Create or open an existing database reference,
     pub fn open(path_prefix: &Path, kind: Kind) -> DatabaseResult<Self> {
-        Self::open_with_sync_level(path_prefix, kind, DbSyncLevel::default())
+        let sync_level = kind.preferred_sync_level();
+        Self::open_with_sync_level(path_prefix, kind, sync_level)
+    }
+
+    /// Get a connection which can write to the database.
+    pub fn conn(&self) -> DatabaseResult<PConn> {
+        self.0.connection_pooled(&self.0.connection_pool)
     }
 
     pub async fn conn_write_permit(&self) -> PConnPermit {
@@ -226,10 +241,43 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
         })
     }
 
+    /// Create a database which lives in memory for as long as
+    /// this reference or any clone of it exists.
+    #[cfg(any(test, feature = "test_utils"))]
+    pub fn open_in_memory(kind: Kind) -> DatabaseResult<Self> {
+        Self::new(None, kind, DbSyncLevel::default())
+    }
+
+    /// Create or open an existing database reference,
+    /// overriding the connection pool settings of its kind.
+    ///
+    /// The pool settings only apply when the database is first opened
+    /// by this process. Later calls return the existing handle unchanged.
+    pub fn open_with_pool_config(
+        path_prefix: &Path,
+        kind: Kind,
+        sync_level: DbSyncLevel,
+        pool_config: DbPoolConfig,
+    ) -> DatabaseResult<Self> {
+        DATABASE_HANDLES.get_or_insert(&kind, path_prefix, |kind| {
+            Self::new_with_pool_config(Some(path_prefix), kind, sync_level, pool_config)
+        })
+    }
+
     pub(crate) fn new(
         path_prefix: Option<&Path>,
         kind: Kind,
         sync_level: DbSyncLevel,
+    ) -> DatabaseResult<Self> {
+        let pool_config = kind.pool_config();
+        Self::new_with_pool_config(path_prefix, kind, sync_level, pool_config)
+    }
+
+    pub(crate) fn new_with_pool_config(
+        path_prefix: Option<&Path>,
+        kind: Kind,
+        sync_level: DbSyncLevel,
+        pool_config: DbPoolConfig,
     ) -> DatabaseResult<Self> {
         let path = match path_prefix {
             Some(path_prefix) => {
@@ -283,12 +331,33 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
         };
 
         // Now we know the database file is valid we can open a connection pool.
-        let pool = new_connection_pool(path.as_ref().map(|p| p.as_ref()), sync_level);
+        // Readers get their own connections so they can never be handed to a writer.
+        let (pool, read_pool) = match &path {
+            Some(path) => (
+                new_connection_pool(path, sync_level, pool_config),
+                new_read_connection_pool(path, sync_level),
+            ),
+            None => {
+                let uri = new_in_memory_uri();
+                (
+                    new_connection_pool_in_memory(&uri, sync_level, pool_config),
+                    new_read_connection_pool_in_memory(&uri, sync_level),
+                )
+            }
+        };
         let mut conn = pool.get()?;
         // set to faster write-ahead-log mode
         conn.pragma_update(None, "journal_mode", &"WAL".to_string())?;
         crate::table::initialize_database(&mut conn, kind.kind())?;
 
+        let checkpoint_task = pool_config
+            .checkpoint_interval
+            .filter(|_| path.is_some())
+            .and_then(|interval| {
+                spawn_checkpoint_task(pool.clone(), interval, pool_config.checkpoint_mode)
+            })
+            .map(Arc::new);
+
         Ok(DbWrite(DbRead {
             write_semaphore: Self::get_write_semaphore(kind.kind()),
             read_semaphore: Self::get_read_semaphore(kind.kind()),
@@ -297,6 +366,8 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
             kind,
             path: path.unwrap_or_default(),
             connection_pool: pool,
+            read_connection_pool: read_pool,
+            _checkpoint_task: checkpoint_task,
         }))
     }
 
@@ -327,7 +398,7 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
 
     #[cfg(any(test, feature = "test_utils"))]
     pub fn test_in_mem(kind: Kind) -> DatabaseResult<Self> {
-        Self::new(None, kind, DbSyncLevel::default())
+        Self::open_in_memory(kind)
     }
 
     /// Remove the db and directory
@@ -425,6 +496,18 @@ pub trait DbKindT: Clone + Send + Sync + 'static {
     /// they can be refilled from the network. Other databases cannot
     /// be refilled and some manual intervention is required.
     fn if_corrupt_wipe(&self) -> bool;
+    /// The connection pool settings for this kind of database.
+    /// Override this to give a database more or fewer connections than the default.
+    fn pool_config(&self) -> DbPoolConfig {
+        DbPoolConfig::default()
+    }
+    /// The sync level to open this kind of database with
+    /// when the caller doesn't choose one.
+    /// Databases which can't be refilled from the network
+    /// should override this to sync more often.
+    fn preferred_sync_level(&self) -> DbSyncLevel {
+        DbSyncLevel::default()
+    }
 }
 
 pub trait DbKindOp {}
@@ -471,6 +554,11 @@ impl DbKindT for DbKindAuthored {
     fn if_corrupt_wipe(&self) -> bool {
         false
     }
+
+    fn preferred_sync_level(&self) -> DbSyncLevel {
+        // The source chain can't be recovered if it's lost.
+        DbSyncLevel::Full
+    }
 }
 
 impl DbKindOp for DbKindAuthored {}
@@ -649,6 +737,34 @@ impl<'e> PConn {
         f(txn)
     }
 
+    /// Run `f` in an exclusive write transaction.
+    /// The transaction is committed if `f` returns `Ok` and rolled back
+    /// if it returns `Err` or panics.
+    pub fn with_transaction<E, R, F>(&'e mut self, f: F) -> Result<R, E>
+    where
+        E: From<DatabaseError>,
+        F: 'e + FnOnce(&mut Transaction) -> Result<R, E>,
+    {
+        let mut txn = self
+            .transaction_with_behavior(TransactionBehavior::Exclusive)
+            .map_err(DatabaseError::from)?;
+        // Dropping the transaction without committing rolls it back.
+        let result = f(&mut txn)?;
+        txn.commit().map_err(DatabaseError::from)?;
+        Ok(result)
+    }
+
+    /// Run `f` in a read transaction so every query in it sees the same
+    /// snapshot of the database. Nothing written in `f` is committed.
+    pub fn with_read_txn<E, R, F>(&'e mut self, f: F) -> Result<R, E>
+    where
+        E: From<DatabaseError>,
+        F: 'e + FnOnce(&mut Transaction) -> Result<R, E>,
+    {
+        let mut txn = self.transaction().map_err(DatabaseError::from)?;
+        f(&mut txn)
+    }
+
     #[cfg(feature = "test_utils")]
     pub fn with_reader_test<R, F>(&'e mut self, f: F) -> R
     where
@@ -665,12 +781,7 @@ impl<'e> WriteManager<'e> for PConn {
         E: From<DatabaseError>,
         F: 'e + FnOnce(&mut Transaction) -> Result<R, E>,
     {
-        let mut txn = self
-            .transaction_with_behavior(TransactionBehavior::Exclusive)
-            .map_err(DatabaseError::from)?;
-        let result = f(&mut txn)?;
-        txn.commit().map_err(DatabaseError::from)?;
-        Ok(result)
+        self.with_transaction(f)
     }
 }
 
@@ -722,3 +833,192 @@ where
         tokio::time::sleep(RETRY_INTERVAL).await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conn::WalCheckpointMode;
+    use std::time::Duration;
+
+    #[test]
+    fn pool_config_sets_pool_size() {
+        let tmpdir = tempfile::tempdir().unwrap();
+
+        let small = DbWrite::new_with_pool_config(
+            Some(tmpdir.path()),
+            DbKindConductor,
+            DbSyncLevel::default(),
+            DbPoolConfig::default().with_max_size(2),
+        )
+        .unwrap();
+        let large = DbWrite::new_with_pool_config(
+            Some(tmpdir.path()),
+            DbKindWasm,
+            DbSyncLevel::default(),
+            DbPoolConfig::default()
+                .with_max_size(32)
+                .with_min_idle(1)
+                .with_connection_timeout(Duration::from_secs(5)),
+        )
+        .unwrap();
+        let default = DbWrite::new(None, DbKindWasm, DbSyncLevel::default()).unwrap();
+
+        assert_eq!(small.connection_pool.max_size(), 2);
+        assert_eq!(large.connection_pool.max_size(), 32);
+        assert_eq!(large.connection_pool.min_idle(), Some(1));
+        assert_eq!(
+            large.connection_pool.connection_timeout(),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            default.connection_pool.max_size(),
+            num_read_threads() as u32 + 1
+        );
+    }
+
+    #[test]
+    fn in_memory_read_connections_are_separate() {
+        let db = DbWrite::open_in_memory(DbKindWasm).unwrap();
+        db.test_commit(|txn| {
+            txn.execute("CREATE TABLE t (x INTEGER)", []).unwrap();
+        });
+
+        let mut reader = DbRead::from(db.clone()).conn().unwrap();
+        assert!(reader.execute("INSERT INTO t (x) VALUES (1)", []).is_err());
+        drop(reader);
+
+        // Connections used for reading are never handed to writers.
+        for _ in 0..num_read_threads() + 1 {
+            db.test_commit(|txn| {
+                txn.execute("INSERT INTO t (x) VALUES (2)", []).unwrap();
+            });
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn checkpoint_task_stops_with_database() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let db = DbWrite::new_with_pool_config(
+            Some(tmpdir.path()),
+            DbKindWasm,
+            DbSyncLevel::default(),
+            DbPoolConfig::default()
+                .with_checkpoint_interval(Duration::from_millis(10), WalCheckpointMode::Passive),
+        )
+        .unwrap();
+        db.test_commit(|txn| {
+            txn.execute("CREATE TABLE t (x INTEGER)", []).unwrap();
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let wal_path = PathBuf::from(format!("{}-wal", db.path().display()));
+        assert!(wal_path.exists());
+
+        // SQLite removes the log once the last connection closes,
+        // so it is gone only if the task has released the pool.
+        drop(db);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!wal_path.exists());
+    }
+
+    #[test]
+    fn open_uses_preferred_sync_level() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let dna_hash = Arc::new(DnaHash::from_raw_32(vec![0; 32]));
+        let synchronous = |conn: &mut PConn| -> u8 {
+            conn.pragma_query_value(None, "synchronous", |row| row.get(0))
+                .unwrap()
+        };
+
+        let authored = DbWrite::open(tmpdir.path(), DbKindAuthored(dna_hash.clone())).unwrap();
+        let dht = DbWrite::open(tmpdir.path(), DbKindDht(dna_hash)).unwrap();
+
+        assert_eq!(synchronous(&mut authored.conn().unwrap()), 2);
+        assert_eq!(synchronous(&mut dht.conn().unwrap()), 1);
+    }
+
+    #[test]
+    fn concurrent_read_connections() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let db = DbWrite::test(&tmpdir, DbKindWasm).unwrap();
+        db.test_commit(|txn| {
+            txn.execute("CREATE TABLE t (x INTEGER)", []).unwrap();
+            txn.execute("INSERT INTO t (x) VALUES (1)", []).unwrap();
+        });
+
+        // Hold every read connection at once from separate threads.
+        let num_readers = num_read_threads();
+        let barrier = Arc::new(std::sync::Barrier::new(num_readers));
+        let handles: Vec<_> = (0..num_readers)
+            .map(|_| {
+                let db: DbRead<DbKindWasm> = db.clone().into();
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    let mut conn = db.conn().unwrap();
+                    barrier.wait();
+                    conn.with_reader_test(|txn| {
+                        txn.query_row("SELECT x FROM t", [], |row| row.get::<_, i64>(0))
+                            .unwrap()
+                    })
+                })
+            })
+            .collect();
+        for h in handles {
+            assert_eq!(h.join().unwrap(), 1);
+        }
+
+        // Readers don't block the writer.
+        let _reader = DbRead::from(db.clone()).conn().unwrap();
+        db.test_commit(|txn| {
+            txn.execute("INSERT INTO t (x) VALUES (2)", []).unwrap();
+        });
+
+        // Read connections can't write.
+        let mut conn = DbRead::from(db).conn().unwrap();
+        assert!(conn.execute("INSERT INTO t (x) VALUES (3)", []).is_err());
+    }
+
+    #[test]
+    fn failed_transactions_are_rolled_back() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let db = DbWrite::test(&tmpdir, DbKindWasm).unwrap();
+        let mut conn = db.conn().unwrap();
+        conn.with_transaction(|txn| {
+            txn.execute("CREATE TABLE t (x INTEGER)", [])?;
+            DatabaseResult::Ok(())
+        })
+        .unwrap();
+        let count = |conn: &mut PConn| -> u32 {
+            conn.with_read_txn(|txn| {
+                DatabaseResult::Ok(txn.query_row("SELECT COUNT(*) FROM t", [], |row| row.get(0))?)
+            })
+            .unwrap()
+        };
+
+        // A panic part way through writing leaves nothing behind.
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            conn.with_transaction(|txn| {
+                txn.execute("INSERT INTO t (x) VALUES (1)", [])?;
+                panic!("failed after writing");
+                #[allow(unreachable_code)]
+                DatabaseResult::Ok(())
+            })
+        }));
+        assert!(panicked.is_err());
+        assert_eq!(count(&mut conn), 0);
+
+        // So does an error.
+        let result = conn.with_transaction(|txn| {
+            txn.execute("INSERT INTO t (x) VALUES (1)", [])?;
+            Err::<(), _>(DatabaseError::EmptyKey)
+        });
+        assert_eq!(result, Err(DatabaseError::EmptyKey));
+        assert_eq!(count(&mut conn), 0);
+
+        conn.with_transaction(|txn| {
+            txn.execute("INSERT INTO t (x) VALUES (1)", [])?;
+            DatabaseResult::Ok(())
+        })
+        .unwrap();
+        assert_eq!(count(&mut conn), 1);
+    }
+}
@@ -145,6 +145,24 @@ fn tx_p2p_put(txn: &mut Transaction, record: P2pRecord) -> DatabaseResult<()> {
     Ok(())
 }
 
+/// Remove the AgentInfoSigned records of the given agents from the p2p_store
+pub async fn p2p_remove_agents(
+    db: &DbWrite<DbKindP2pAgentStore>,
+    agents: impl Iterator<Item = &KitsuneAgent>,
+) -> DatabaseResult<()> {
+    let agents: Vec<_> = agents.map(|agent| agent.0.clone()).collect();
+    db.async_commit(move |txn| {
+        for agent in agents {
+            txn.execute(
+                sql_p2p_agent_store::DELETE,
+                named_params! { ":agent": agent },
+            )?;
+        }
+        DatabaseResult::Ok(())
+    })
+    .await
+}
+
 /// Prune all expired AgentInfoSigned records from the p2p_store
 pub async fn p2p_prune(db: &DbWrite<DbKindP2pAgentStore>) -> DatabaseResult<()> {
     db.async_commit(move |txn| {
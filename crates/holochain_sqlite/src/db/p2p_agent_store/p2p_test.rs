@@ -112,6 +112,37 @@ async fn test_p2p_agent_store_extrapolated_coverage() {
     tmp_dir.close().unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_p2p_agent_store_remove_agents() {
+    let tmp_dir = tempfile::Builder::new()
+        .prefix("p2p_agent_store_remove_agents")
+        .tempdir()
+        .unwrap();
+
+    let space = rand_space();
+
+    let db = DbWrite::test(&tmp_dir, DbKindP2pAgentStore(space.clone())).unwrap();
+
+    let agents: Vec<_> = (0..3).map(|_| rand_agent()).collect();
+    for agent in &agents {
+        rand_insert(&db, &space, agent, true).await;
+    }
+
+    p2p_remove_agents(&db, agents[..2].iter().map(|agent| agent.as_ref()))
+        .await
+        .unwrap();
+
+    let permit = db.conn_permit().await;
+    let mut con = db.from_permit(permit).unwrap();
+    let all = con.p2p_list_agents().unwrap();
+    assert_eq!(1, all.len());
+    assert_eq!(all[0].agent, agents[2]);
+    assert!(con.p2p_get_agent(&agents[0]).unwrap().is_none());
+
+    // clean up temp dir
+    tmp_dir.close().unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_p2p_agent_store_gossip_query_sanity() {
     let tmp_dir = tempfile::Builder::new()
@@ -33,7 +33,17 @@ pub enum DatabaseError {
     Other(#[from] anyhow::Error),
 
     #[error(transparent)]
-    SqliteError(#[from] rusqlite::Error),
+    SqliteError(rusqlite::Error),
+
+    /// A statement broke one of the schema's constraints.
+    /// SQLite doesn't name the table for foreign key violations,
+    /// so `table` is empty for those.
+    #[error("{constraint} constraint failed on table '{table}': {message}")]
+    ConstraintViolation {
+        table: String,
+        constraint: String,
+        message: String,
+    },
 
     #[error("Failure to remove directory")]
     DirectoryError(#[from] std::io::Error),
@@ -54,6 +64,40 @@ pub enum DatabaseError {
     FailedToJoinBlocking(#[from] tokio::task::JoinError),
 }
 
+impl DatabaseError {
+    /// Whether this error was caused by a statement breaking a constraint.
+    pub fn is_constraint_violation(&self) -> bool {
+        matches!(self, DatabaseError::ConstraintViolation { .. })
+    }
+}
+
+impl From<rusqlite::Error> for DatabaseError {
+    fn from(e: rusqlite::Error) -> Self {
+        match e {
+            rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error {
+                    code: rusqlite::ErrorCode::ConstraintViolation,
+                    ..
+                },
+                Some(message),
+            ) => {
+                // SQLite formats these as "<KIND> constraint failed[: <table>.<column>, ...]".
+                let (constraint, detail) = match message.split_once(" constraint failed") {
+                    Some((constraint, detail)) => (constraint, detail.trim_start_matches(": ")),
+                    None => ("", ""),
+                };
+                let table = detail.split_once('.').map(|(table, _)| table).unwrap_or("");
+                DatabaseError::ConstraintViolation {
+                    table: table.to_string(),
+                    constraint: constraint.to_string(),
+                    message,
+                }
+            }
+            e => DatabaseError::SqliteError(e),
+        }
+    }
+}
+
 impl PartialEq for DatabaseError {
     fn eq(&self, other: &Self) -> bool {
         self.to_string() == other.to_string()
@@ -61,3 +105,63 @@ impl PartialEq for DatabaseError {
 }
 
 pub type DatabaseResult<T> = Result<T, DatabaseError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{DbKindWasm, DbWrite};
+
+    #[test]
+    fn constraint_violations_are_recognized() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let db = DbWrite::test(&tmpdir, DbKindWasm).unwrap();
+        db.test_commit(|txn| {
+            txn.execute("CREATE TABLE Parent (id INTEGER PRIMARY KEY)", [])
+                .unwrap();
+            txn.execute(
+                "CREATE TABLE Child (id INTEGER PRIMARY KEY, parent INTEGER NOT NULL REFERENCES Parent(id))",
+                [],
+            )
+            .unwrap();
+            txn.execute("INSERT INTO Parent (id) VALUES (1)", []).unwrap();
+        });
+
+        let foreign_key: DatabaseError = db.test_commit(|txn| {
+            txn.execute("INSERT INTO Child (id, parent) VALUES (1, 2)", [])
+                .unwrap_err()
+                .into()
+        });
+        assert!(foreign_key.is_constraint_violation());
+        match foreign_key {
+            DatabaseError::ConstraintViolation {
+                table, constraint, ..
+            } => {
+                assert_eq!(constraint, "FOREIGN KEY");
+                assert_eq!(table, "");
+            }
+            e => panic!("expected a constraint violation, got {:?}", e),
+        }
+
+        let unique: DatabaseError = db.test_commit(|txn| {
+            txn.execute("INSERT INTO Parent (id) VALUES (1)", [])
+                .unwrap_err()
+                .into()
+        });
+        match unique {
+            DatabaseError::ConstraintViolation {
+                table, constraint, ..
+            } => {
+                assert_eq!(constraint, "UNIQUE");
+                assert_eq!(table, "Parent");
+            }
+            e => panic!("expected a constraint violation, got {:?}", e),
+        }
+
+        let other: DatabaseError = db.test_commit(|txn| {
+            txn.execute("INSERT INTO Missing (id) VALUES (1)", [])
+                .unwrap_err()
+                .into()
+        });
+        assert!(!other.is_constraint_violation());
+    }
+}
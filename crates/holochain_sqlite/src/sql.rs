@@ -61,6 +61,7 @@ pub(crate) mod sql_p2p_agent_store {
     pub(crate) const EXTRAPOLATED_COVERAGE: &str =
         include_str!("sql/p2p_agent_store/extrapolated_coverage.sql");
     pub(crate) const PRUNE: &str = include_str!("sql/p2p_agent_store/prune.sql");
+    pub(crate) const DELETE: &str = include_str!("sql/p2p_agent_store/delete.sql");
 }
 
 pub(crate) mod sql_p2p_metrics {
@@ -161,6 +161,13 @@ CREATE TABLE IF NOT EXISTS ChainLock (
     expires_at_timestamp INTEGER NOT NULL
 );
 
+-- The last header removed from the start of each author's chain by pruning.
+CREATE TABLE IF NOT EXISTS ChainPruned (
+    author BLOB PRIMARY KEY ON CONFLICT REPLACE,
+    last_seq INTEGER NOT NULL,
+    last_hash BLOB NOT NULL
+);
+
 CREATE TABLE IF NOT EXISTS ScheduledFunctions (
     author BLOB NOT NULL,
     zome_name TEXT NOT NULL,
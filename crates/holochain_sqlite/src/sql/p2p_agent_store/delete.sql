@@ -0,0 +1,5 @@
+-- delete the matching agent
+DELETE FROM
+  p2p_agent_store
+WHERE
+  agent = :agent;
@@ -0,0 +1,90 @@
+use ::fixt::prelude::*;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use hdk::prelude::*;
+use holochain_p2p::MockHolochainP2pDnaT;
+use holochain_state::prelude::*;
+
+/// Number of entries on the queried chain.
+const CHAIN_LEN: usize = 1000;
+
+/// Compare reading full elements to reading only their headers.
+pub fn query_headers_only(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let test_env = test_authored_env();
+    let dht_env = test_dht_env();
+    let source_chain = runtime.block_on(chain_of(&test_env, &dht_env, CHAIN_LEN));
+
+    let mut group = c.benchmark_group("query_headers_only");
+    group.sample_size(10);
+    for headers_only in [false, true] {
+        let query = QueryFilter::new()
+            .include_entries(true)
+            .headers_only(headers_only);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(if headers_only { "headers" } else { "full" }),
+            &query,
+            |b, query| {
+                b.iter(|| {
+                    let elements = runtime.block_on(source_chain.query(query.clone())).unwrap();
+                    assert_eq!(elements.len(), CHAIN_LEN + 3);
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+async fn chain_of(
+    test_env: &TestEnv<DbKindAuthored>,
+    dht_env: &TestEnv<DbKindDht>,
+    len: usize,
+) -> SourceChain {
+    let keystore = test_keystore();
+    let vault = test_env.env();
+    let author = keystore.new_sign_keypair_random().await.unwrap();
+    let mut mock = MockHolochainP2pDnaT::new();
+    mock.expect_authority_for_hash().returning(|_| Ok(false));
+    genesis(
+        vault.clone(),
+        dht_env.env(),
+        keystore.clone(),
+        fixt!(DnaHash),
+        author.clone(),
+        None,
+    )
+    .await
+    .unwrap();
+    let source_chain = SourceChain::new(
+        vault.clone(),
+        dht_env.env(),
+        keystore.clone(),
+        author.clone(),
+    )
+    .await
+    .unwrap();
+    for _ in 0..len {
+        let entry = Entry::App(fixt!(AppEntryBytes));
+        let create = builder::Create {
+            entry_type: EntryType::App(fixt!(AppEntryType, EntryVisibility::Public)),
+            entry_hash: EntryHash::with_data_sync(&entry),
+        };
+        source_chain
+            .put(None, create, Some(entry), ChainTopOrdering::default())
+            .await
+            .unwrap();
+    }
+    source_chain.flush(&mock).await.unwrap();
+    SourceChain::new(vault, dht_env.env(), keystore, author)
+        .await
+        .unwrap()
+}
+
+criterion_group!(query, query_headers_only);
+
+criterion_main!(query);
@@ -0,0 +1,283 @@
+//! A [`DnaStore`] which persists DNAs and entry defs to a wasm database.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use holo_hash::DnaHash;
+use holochain_sqlite::db::WriteManager;
+use holochain_sqlite::prelude::*;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_types::prelude::*;
+
+use crate::entry_def::EntryDefStoreKey;
+use crate::mutations;
+use crate::prelude::StateMutationResult;
+use crate::prelude::StateQueryResult;
+
+/// A [`DnaStore`] backed by a wasm database, so stored DNAs survive restarts.
+///
+/// This uses the same tables the conductor already stores DNAs in,
+/// so it can be opened on an existing conductor's wasm database.
+/// Zomes without wasm (inline zomes) have no code to store,
+/// so DNAs read back from the store won't contain their code.
+///
+/// The [`DnaStore`] methods are infallible, so database errors panic.
+#[derive(Clone)]
+pub struct PersistentDnaStore {
+    db: DbWrite<DbKindWasm>,
+    add_hook: Option<AddHook>,
+}
+
+type AddHook = Arc<dyn Fn(&DnaFile) -> Result<(), DnaError> + Send + Sync>;
+
+impl PersistentDnaStore {
+    /// Create a store backed by the given wasm database.
+    pub fn new(db: DbWrite<DbKindWasm>) -> Self {
+        Self { db, add_hook: None }
+    }
+
+    /// Set a hook which every DnaFile must pass before it is added
+    /// with [`DnaStore::add_dna`]. A DnaFile the hook rejects is not stored.
+    pub fn set_add_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&DnaFile) -> Result<(), DnaError> + Send + Sync + 'static,
+    {
+        self.add_hook = Some(Arc::new(hook));
+    }
+
+    fn read<R>(&self, f: impl FnOnce(Transaction) -> StateQueryResult<R>) -> R {
+        self.db
+            .sync_reader(f)
+            .expect("Failed to read from the DNA store")
+    }
+
+    fn write<R>(&self, f: impl FnOnce(&mut Transaction) -> StateMutationResult<R>) -> R {
+        self.db
+            .conn()
+            .map_err(Into::into)
+            .and_then(|mut conn| conn.with_commit_sync(f))
+            .expect("Failed to write to the DNA store")
+    }
+}
+
+impl std::fmt::Debug for PersistentDnaStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistentDnaStore")
+            .field("path", self.db.path())
+            .finish()
+    }
+}
+
+impl DnaStore for PersistentDnaStore {
+    fn add_dna(&mut self, dna: DnaFile) -> Result<(), DnaError> {
+        self.validate_on_add(&dna)?;
+        self.write(|txn| put_dna_file(txn, &dna));
+        Ok(())
+    }
+
+    fn validate_on_add(&self, dna: &DnaFile) -> Result<(), DnaError> {
+        match &self.add_hook {
+            Some(hook) => hook(dna),
+            None => Ok(()),
+        }
+    }
+
+    fn add_dnas<T: IntoIterator<Item = (DnaHash, DnaFile)> + 'static>(&mut self, dnas: T) {
+        self.write(|txn| {
+            for (_, dna) in dnas {
+                put_dna_file(txn, &dna)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn add_entry_def(&mut self, k: EntryDefBufferKey, entry_def: EntryDef) {
+        self.write(|txn| crate::entry_def::put(txn, k, &entry_def))
+    }
+
+    fn add_entry_defs<T: IntoIterator<Item = (EntryDefBufferKey, EntryDef)> + 'static>(
+        &mut self,
+        entry_defs: T,
+    ) {
+        self.write(|txn| {
+            for (k, entry_def) in entry_defs {
+                crate::entry_def::put(txn, k, &entry_def)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn list(&self) -> Vec<DnaHash> {
+        self.read(|txn| {
+            let mut stmt = txn.prepare("SELECT hash FROM DnaDef")?;
+            let hashes = stmt
+                .query_map([], |row| row.get("hash"))?
+                .collect::<Result<Vec<DnaHash>, _>>()?;
+            Ok(hashes)
+        })
+    }
+
+    fn get_dna_def(&self, hash: &DnaHash) -> Option<DnaDef> {
+        self.read(|txn| Ok(crate::dna_def::get(&txn, hash)?.map(|d| d.into_content())))
+    }
+
+    fn get_dna_file(&self, hash: &DnaHash) -> Option<DnaFile> {
+        self.read(|txn| {
+            let dna_def = match crate::dna_def::get(&txn, hash)? {
+                Some(dna_def) => dna_def,
+                None => return Ok(None),
+            };
+            let mut code = BTreeMap::new();
+            for (zome_name, zome) in dna_def.zomes.iter() {
+                if let Ok(wasm_hash) = zome.wasm_hash(zome_name) {
+                    if let Some(wasm) = crate::wasm::get(&txn, &wasm_hash)? {
+                        code.insert(wasm_hash, wasm.into_content());
+                    }
+                }
+            }
+            Ok(Some(DnaFile::from_parts(dna_def, code.into())))
+        })
+    }
+
+    fn get_entry_def(&self, k: &EntryDefBufferKey) -> Option<EntryDef> {
+        self.read(|txn| crate::entry_def::get(&txn, k.clone()))
+    }
+
+    fn remove_dna(&mut self, hash: &DnaHash) {
+        self.write(|txn| {
+            let removed = match crate::dna_def::get(txn, hash)? {
+                Some(removed) => removed,
+                None => return Ok(()),
+            };
+            txn.execute(
+                "DELETE FROM DnaDef WHERE hash = :hash",
+                named_params! { ":hash": hash },
+            )?;
+
+            // Zomes can be shared between DNAs so only remove
+            // wasms and entry defs that no remaining DNA refers to.
+            let remaining = crate::dna_def::get_all(txn)?;
+            let still_used: HashSet<&ZomeDef> = remaining
+                .iter()
+                .flat_map(|dna| dna.zomes.iter().map(|(_, zome)| zome))
+                .collect();
+            let removed_zomes: Vec<_> = removed
+                .zomes
+                .iter()
+                .filter(|(_, zome)| !still_used.contains(zome))
+                .collect();
+
+            for (zome_name, zome) in removed_zomes.iter() {
+                if let Ok(wasm_hash) = zome.wasm_hash(zome_name) {
+                    txn.execute(
+                        "DELETE FROM Wasm WHERE hash = :hash",
+                        named_params! { ":hash": wasm_hash },
+                    )?;
+                }
+            }
+            for (k, _) in crate::entry_def::get_all(txn)? {
+                if removed_zomes.iter().any(|(_, zome)| *zome == k.zome) {
+                    let key: EntryDefStoreKey = k.into();
+                    txn.execute(
+                        "DELETE FROM EntryDef WHERE key = :key",
+                        named_params! { ":key": key },
+                    )?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn clear(&mut self) {
+        self.write(|txn| {
+            txn.execute("DELETE FROM DnaDef", [])?;
+            txn.execute("DELETE FROM Wasm", [])?;
+            txn.execute("DELETE FROM EntryDef", [])?;
+            Ok(())
+        })
+    }
+
+    fn list_entry_defs(&self) -> Vec<(EntryDefBufferKey, EntryDef)> {
+        self.read(|txn| crate::entry_def::get_all(&txn))
+    }
+}
+
+fn put_dna_file(txn: &mut Transaction, dna: &DnaFile) -> StateMutationResult<()> {
+    for (wasm_hash, wasm) in dna.code() {
+        mutations::insert_wasm(
+            txn,
+            DnaWasmHashed::with_pre_hashed(wasm.clone(), wasm_hash.clone()),
+        )?;
+    }
+    mutations::insert_dna_def(txn, dna.dna())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_types::test_utils::fake_dna_zomes;
+
+    #[test]
+    fn dna_survives_reopen() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let a = fake_dna_zomes("a", vec![("zome_a".into(), vec![1].into())]);
+        let b = fake_dna_zomes("b", vec![("zome_b".into(), vec![2].into())]);
+        let key = EntryDefBufferKey::new(a.dna_def().zomes[0].1.clone(), 0.into());
+        let entry_def = EntryDef::default_with_id("a");
+
+        {
+            let mut store = PersistentDnaStore::new(DbWrite::test(&tmpdir, DbKindWasm).unwrap());
+            store.add_dnas(vec![
+                (a.dna_hash().clone(), a.clone()),
+                (b.dna_hash().clone(), b.clone()),
+            ]);
+            store.add_entry_def(key.clone(), entry_def.clone());
+        }
+
+        let mut store = PersistentDnaStore::new(DbWrite::test(&tmpdir, DbKindWasm).unwrap());
+        let mut hashes = DnaStore::list(&store);
+        hashes.sort();
+        let mut expected = vec![a.dna_hash().clone(), b.dna_hash().clone()];
+        expected.sort();
+        assert_eq!(hashes, expected);
+        assert_eq!(
+            DnaStore::get_dna_file(&store, a.dna_hash()),
+            Some(a.clone())
+        );
+        assert_eq!(store.get_entry_def(&key), Some(entry_def));
+
+        store.remove_dna(a.dna_hash());
+        assert_eq!(DnaStore::list(&store), vec![b.dna_hash().clone()]);
+        assert!(store.get_entry_def(&key).is_none());
+        assert_eq!(DnaStore::get_dna_file(&store, b.dna_hash()), Some(b));
+
+        store.clear();
+        assert!(DnaStore::list(&store).is_empty());
+    }
+
+    #[test]
+    fn add_hook_rejects_dna() {
+        let a = fake_dna_zomes("a", vec![("zome_a".into(), vec![1].into())]);
+        let b = fake_dna_zomes("b", vec![("zome_b".into(), vec![2].into())]);
+        let mut store = PersistentDnaStore::new(DbWrite::test_in_mem(DbKindWasm).unwrap());
+        let rejected = a.dna_hash().clone();
+        store.set_add_hook(move |dna| {
+            if *dna.dna_hash() == rejected {
+                Err(DnaError::Invalid("rejected".into()))
+            } else {
+                Ok(())
+            }
+        });
+
+        assert!(matches!(
+            store.add_dna(a.clone()),
+            Err(DnaError::Invalid(_))
+        ));
+        assert!(DnaStore::get_dna_file(&store, a.dna_hash()).is_none());
+
+        store.add_dna(b.clone()).unwrap();
+        assert_eq!(DnaStore::get_dna_file(&store, b.dna_hash()), Some(b));
+    }
+}
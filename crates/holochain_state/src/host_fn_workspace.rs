@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use holo_hash::AgentPubKey;
+use holo_hash::DhtOpHash;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::HolochainP2pDnaT;
 use holochain_sqlite::db::DbKindAuthored;
@@ -15,7 +16,10 @@ use holochain_zome_types::SignedHeaderHashed;
 use crate::prelude::SourceChain;
 use crate::prelude::SourceChainError;
 use crate::prelude::SourceChainResult;
+use crate::prelude::StateQueryResult;
 use crate::scratch::SyncScratch;
+use crate::validation_receipts::list_receipts;
+use crate::validation_receipts::SignedValidationReceipt;
 use holochain_zome_types::Zome;
 
 #[derive(Clone)]
@@ -195,6 +199,16 @@ where
     ) {
         (self.authored.clone(), self.dht.clone(), self.cache.clone())
     }
+
+    /// The validation receipts this cell has received for the given op.
+    pub async fn validation_receipts(
+        &self,
+        op_hash: DhtOpHash,
+    ) -> StateQueryResult<Vec<SignedValidationReceipt>> {
+        self.dht
+            .async_reader(move |txn| list_receipts(&txn, &op_hash))
+            .await
+    }
 }
 
 impl SourceChainWorkspace {
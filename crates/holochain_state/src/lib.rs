@@ -37,6 +37,8 @@
 pub mod chain_lock;
 #[allow(missing_docs)]
 pub mod dna_def;
+#[cfg(feature = "persistent-dna-store")]
+pub mod dna_store;
 pub mod entry_def;
 pub mod host_fn_workspace;
 pub mod integrate;
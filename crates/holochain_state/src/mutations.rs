@@ -320,20 +320,37 @@ pub fn set_validation_stage(
         ValidationLimboStatus::AwaitingAppDeps(_) => Some(2),
         ValidationLimboStatus::AwaitingIntegration => Some(3),
     };
+    txn.execute(
+        "
+        UPDATE DhtOp
+        SET
+        validation_stage = :validation_stage
+        WHERE
+        DhtOp.hash = :hash
+        ",
+        named_params! {
+            ":validation_stage": stage,
+            ":hash": hash,
+        },
+    )?;
+    Ok(())
+}
+
+/// Count a retry of validating a [`DhtOp`] that was put back in
+/// the validation limbo, and record when it was attempted.
+pub fn set_validation_retry(txn: &mut Transaction, hash: &DhtOpHash) -> StateMutationResult<()> {
     let now = holochain_zome_types::Timestamp::now();
     txn.execute(
         "
         UPDATE DhtOp
         SET
         num_validation_attempts = IFNULL(num_validation_attempts, 0) + 1,
-        last_validation_attempt = :last_validation_attempt,
-        validation_stage = :validation_stage
+        last_validation_attempt = :last_validation_attempt
         WHERE
         DhtOp.hash = :hash
         ",
         named_params! {
             ":last_validation_attempt": now,
-            ":validation_stage": stage,
             ":hash": hash,
         },
     )?;
@@ -726,3 +743,21 @@ pub fn schedule_fn(
     }
     Ok(())
 }
+
+/// Remove a scheduled fn so it will not be dispatched again,
+/// whether its schedule is persisted or ephemeral.
+pub fn unschedule_fn(
+    txn: &mut Transaction,
+    author: &AgentPubKey,
+    scheduled_fn: ScheduledFn,
+) -> StateMutationResult<()> {
+    txn.execute(
+        holochain_sqlite::sql::sql_cell::schedule::DELETE,
+        named_params! {
+            ":zome_name": scheduled_fn.zome_name().to_string(),
+            ":scheduled_fn": scheduled_fn.fn_name().to_string(),
+            ":author" : author,
+        },
+    )?;
+    Ok(())
+}
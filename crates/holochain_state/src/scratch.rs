@@ -38,6 +38,7 @@ pub struct Scratch {
     entries: HashMap<EntryHash, Arc<Entry>>,
     chain_top_ordering: ChainTopOrdering,
     scheduled_fns: Vec<ScheduledFn>,
+    unscheduled_fns: Vec<ScheduledFn>,
     chain_head: Option<(u32, usize)>,
 }
 
@@ -62,9 +63,20 @@ impl Scratch {
     }
 
     pub fn add_scheduled_fn(&mut self, scheduled_fn: ScheduledFn) {
+        self.unscheduled_fns.retain(|f| *f != scheduled_fn);
         self.scheduled_fns.push(scheduled_fn)
     }
 
+    pub fn unscheduled_fns(&self) -> &[ScheduledFn] {
+        &self.unscheduled_fns
+    }
+
+    /// Unscheduling cancels any scheduling of the same fn earlier in this scratch.
+    pub fn add_unscheduled_fn(&mut self, scheduled_fn: ScheduledFn) {
+        self.scheduled_fns.retain(|f| *f != scheduled_fn);
+        self.unscheduled_fns.push(scheduled_fn)
+    }
+
     pub fn chain_top_ordering(&self) -> ChainTopOrdering {
         self.chain_top_ordering
     }
@@ -133,7 +145,9 @@ impl Scratch {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.zomed_headers.is_empty() && self.scheduled_fns.is_empty()
+        self.zomed_headers.is_empty()
+            && self.scheduled_fns.is_empty()
+            && self.unscheduled_fns.is_empty()
     }
 
     pub fn headers(&self) -> impl Iterator<Item = &SignedHeaderHashed> {
@@ -195,6 +209,10 @@ impl Scratch {
         self.scheduled_fns.drain(..)
     }
 
+    pub fn drain_unscheduled_fns(&mut self) -> impl Iterator<Item = ScheduledFn> + '_ {
+        self.unscheduled_fns.drain(..)
+    }
+
     /// Drain out all the headers.
     pub fn drain_zomed_headers(
         &mut self,
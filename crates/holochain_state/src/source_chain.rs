@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::integrate::authored_ops_to_dht_db;
@@ -12,17 +13,20 @@ use holo_hash::HasHash;
 use holo_hash::HeaderHash;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::HolochainP2pDnaT;
+use holochain_sqlite::rusqlite::OptionalExtension;
 use holochain_sqlite::rusqlite::Transaction;
 use holochain_types::dht_op::produce_op_lights_from_elements;
 use holochain_types::dht_op::produce_op_lights_from_iter;
 use holochain_types::dht_op::DhtOp;
 use holochain_types::dht_op::DhtOpLight;
+use holochain_types::dht_op::DhtOpType;
 use holochain_types::dht_op::OpOrder;
 use holochain_types::dht_op::UniqueForm;
 use holochain_types::element::SignedHeaderHashedExt;
 use holochain_types::env::DbRead;
 use holochain_types::env::DbWrite;
 use holochain_zome_types::header;
+use holochain_zome_types::header::POST_GENESIS_SEQ_THRESHOLD;
 use holochain_zome_types::query::ChainQueryFilterRange;
 use holochain_zome_types::CapAccess;
 use holochain_zome_types::CapGrant;
@@ -31,8 +35,10 @@ use holochain_zome_types::CellId;
 use holochain_zome_types::ChainTopOrdering;
 use holochain_zome_types::CounterSigningAgentState;
 use holochain_zome_types::CounterSigningSessionData;
+use holochain_zome_types::DnaDef;
 use holochain_zome_types::Element;
 use holochain_zome_types::Entry;
+use holochain_zome_types::EntryType;
 use holochain_zome_types::EntryVisibility;
 use holochain_zome_types::GrantedFunction;
 use holochain_zome_types::Header;
@@ -47,6 +53,7 @@ use holochain_zome_types::SignedHeader;
 use holochain_zome_types::SignedHeaderHashed;
 use holochain_zome_types::Timestamp;
 use holochain_zome_types::Zome;
+use holochain_zome_types::ZomeName;
 
 use crate::chain_lock::is_chain_locked;
 use crate::chain_lock::is_lock_expired;
@@ -61,6 +68,125 @@ pub use error::*;
 
 mod error;
 
+/// The named parameters used by [`push_query_filter_sql`],
+/// followed by any extra parameters.
+macro_rules! query_filter_params {
+    ($author:expr, $query:expr, $after_seq:expr $(, $k:literal: $v:expr)* $(,)?) => {
+        named_params! {
+            ":author": $author.as_ref(),
+            ":after_seq": $after_seq,
+            ":entry_type": $query.entry_type,
+            ":header_type": $query.header_type,
+            ":header_types": header_types_json(&$query),
+            ":range_start": match $query.sequence_range {
+                ChainQueryFilterRange::HeaderSeqRange(start, _) => Some(start),
+                _ => None,
+            },
+            ":range_end": match $query.sequence_range {
+                ChainQueryFilterRange::HeaderSeqRange(_, end) => Some(end),
+                _ => None,
+            },
+            ":range_start_hash": match &$query.sequence_range {
+                ChainQueryFilterRange::HeaderHashRange(start_hash, _) => Some(start_hash.clone()),
+                _ => None,
+            },
+            ":range_end_hash": match &$query.sequence_range {
+                ChainQueryFilterRange::HeaderHashRange(_, end_hash)
+                | ChainQueryFilterRange::HeaderHashTerminated(end_hash, _) => Some(end_hash.clone()),
+                _ => None,
+            },
+            ":range_prior_count": match $query.sequence_range {
+                ChainQueryFilterRange::HeaderHashTerminated(_, prior_count) => Some(prior_count),
+                _ => None,
+            },
+            $($k: $v,)*
+        }
+    };
+}
+
+/// The header types of a chain query as a JSON array,
+/// for binding to `:header_types`.
+fn header_types_json(query: &QueryFilter) -> Option<String> {
+    query.header_types.as_ref().map(|header_types| {
+        serde_json::Value::from(
+            header_types
+                .iter()
+                .map(|header_type| header_type.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .to_string()
+    })
+}
+
+/// An entry type id can only be matched once the zome it belongs to has
+/// resolved it into an `EntryType`, so the source chain can't filter on it.
+fn check_entry_type_resolved(query: &QueryFilter) -> SourceChainResult<()> {
+    match &query.entry_type_str {
+        Some(entry_type_str) => Err(SourceChainError::UnresolvedEntryTypeStr(
+            entry_type_str.clone(),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Push the joins and `WHERE` clause of a chain query onto `sql`.
+fn push_query_filter_sql(sql: &mut String, query: &QueryFilter) {
+    if !query.headers_only {
+        sql.push_str(
+            "
+        JOIN DhtOp On DhtOp.header_hash = Header.hash
+        ",
+        );
+    }
+    sql.push_str(
+        "
+        WHERE
+        Header.author = :author
+        AND
+        (
+            (:range_start IS NULL AND :range_end IS NULL AND :range_start_hash IS NULL AND :range_end_hash IS NULL AND :range_prior_count IS NULL)
+        ",
+    );
+    sql.push_str(match query.sequence_range {
+        ChainQueryFilterRange::Unbounded => "",
+        ChainQueryFilterRange::HeaderSeqRange(_, _) => {
+            "
+            OR (Header.seq BETWEEN :range_start AND :range_end)"
+        }
+        ChainQueryFilterRange::HeaderHashRange(_, _) => {
+            "
+            OR (
+                Header.seq BETWEEN
+                (SELECT Header.seq WHERE Header.hash = :range_start_hash)
+                AND
+                (SELECT Header.seq WHERE Header.hash = :range_end_hash)
+            )"
+        }
+        ChainQueryFilterRange::HeaderHashTerminated(_, _) => {
+            "
+            OR (
+                Header.seq BETWEEN
+                (SELECT Header.seq WHERE Header.hash = :range_end_hash) - :range_prior_count
+                AND
+                (SELECT Header.seq WHERE Header.hash = :range_end_hash)
+            )"
+        }
+    });
+    sql.push_str(
+        "
+        )
+        AND
+        (:entry_type IS NULL OR Header.entry_type = :entry_type)
+        AND
+        (:header_type IS NULL OR Header.type = :header_type)
+        AND
+        (:after_seq IS NULL OR Header.seq > :after_seq)
+        AND
+        (:header_types IS NULL OR Header.type IN (SELECT value FROM json_each(:header_types)))
+        ",
+    );
+}
+
 #[derive(Clone)]
 pub struct SourceChain<AuthorDb = DbWrite<DbKindAuthored>, DhtDb = DbWrite<DbKindDht>> {
     scratch: SyncScratch,
@@ -97,6 +223,44 @@ pub struct SourceChainJsonElement {
 //       not the entire source chain!
 /// Writable functions for a source chain with write access.
 impl SourceChain {
+    /// Remove the oldest headers that were authored before the cutoff,
+    /// along with their ops and any entries no other header refers to.
+    /// Headers are removed in sequence order starting after genesis,
+    /// stopping at the first one that is not [prunable](SourceChain::is_prunable),
+    /// so the rest of the chain stays linked.
+    /// Returns the number of headers removed.
+    pub async fn prune_before(&self, cutoff: Timestamp) -> SourceChainResult<u32> {
+        let author = self.author.clone();
+        let candidates = self
+            .vault
+            .async_reader(move |txn| {
+                let (_, head_seq, _) = chain_head_db(&txn, author.clone())?;
+                SourceChainResult::Ok(prune_candidates(&txn, &author, head_seq, cutoff)?)
+            })
+            .await?;
+        // Receipts are recorded against the ops held in the dht database.
+        let mut candidates = self
+            .dht_env
+            .async_reader(move |txn| {
+                let mut num_prunable = 0;
+                for candidate in &candidates {
+                    if !receipts_complete(&txn, &candidate.ops)? {
+                        break;
+                    }
+                    num_prunable += 1;
+                }
+                StateQueryResult::Ok((candidates, num_prunable))
+            })
+            .await?;
+        candidates.0.truncate(candidates.1);
+        let author = self.author.clone();
+        self.vault
+            .async_commit(move |txn| {
+                SourceChainResult::Ok(prune_headers(txn, &author, candidates.0)?)
+            })
+            .await
+    }
+
     pub async fn unlock_chain(&self) -> SourceChainResult<()> {
         let author = self.author.clone();
         self.vault
@@ -224,7 +388,7 @@ impl SourceChain {
         if self.scratch.apply(|s| s.is_empty())? {
             return Ok(Vec::new());
         }
-        let (scheduled_fns, zomed_headers, ops, entries) =
+        let (scheduled_fns, unscheduled_fns, zomed_headers, ops, entries) =
             self.scratch.apply_and_then(|scratch| {
                 let (zomed_headers, ops) =
                     build_ops_from_headers(scratch.drain_zomed_headers().collect::<Vec<_>>())?;
@@ -232,7 +396,8 @@ impl SourceChain {
                 // Drain out any entries.
                 let entries = scratch.drain_entries().collect::<Vec<_>>();
                 let scheduled_fns = scratch.drain_scheduled_fns().collect::<Vec<_>>();
-                SourceChainResult::Ok((scheduled_fns, zomed_headers, ops, entries))
+                let unscheduled_fns = scratch.drain_unscheduled_fns().collect::<Vec<_>>();
+                SourceChainResult::Ok((scheduled_fns, unscheduled_fns, zomed_headers, ops, entries))
             })?;
 
         let maybe_countersigned_entry = entries
@@ -265,6 +430,9 @@ impl SourceChain {
                 for scheduled_fn in scheduled_fns {
                     schedule_fn(txn, author.as_ref(), scheduled_fn, None, now)?;
                 }
+                for scheduled_fn in unscheduled_fns {
+                    unschedule_fn(txn, author.as_ref(), scheduled_fn)?;
+                }
                 // As at check.
                 let (new_persisted_head, new_head_seq, new_timestamp) =
                     chain_head_db(txn, author.clone())?;
@@ -646,8 +814,75 @@ where
     /// used by the `query` host function, which crosses the wasm boundary
     // FIXME: This query needs to be tested.
     pub async fn query(&self, query: QueryFilter) -> SourceChainResult<Vec<Element>> {
+        self.query_inner(query, None).await
+    }
+
+    /// Query at most `limit` elements which come after the element with
+    /// `after_hash` on the chain, or from genesis if `after_hash` is `None`.
+    /// Unlike an offset this cursor is stable when the chain grows between pages.
+    /// The offset and limit of the query are ignored.
+    pub async fn query_after(
+        &self,
+        mut query: QueryFilter,
+        after_hash: Option<HeaderHash>,
+        limit: u32,
+    ) -> SourceChainResult<Vec<Element>> {
+        let after_seq = match after_hash {
+            Some(after_hash) => Some(
+                self.header_seq(&after_hash)
+                    .await?
+                    .ok_or_else(|| SourceChainError::ElementMissing(after_hash.to_string()))?,
+            ),
+            None => None,
+        };
+        query.offset = None;
+        query.limit = Some(limit);
+        self.query_inner(query, after_seq).await
+    }
+
+    /// The sequence number of a header on this chain.
+    async fn header_seq(&self, hash: &HeaderHash) -> SourceChainResult<Option<u32>> {
+        let seq = self.scratch.apply(|scratch| {
+            scratch
+                .headers()
+                .find(|shh| shh.header_address() == hash)
+                .map(|shh| shh.header().header_seq())
+        })?;
+        if seq.is_some() {
+            return Ok(seq);
+        }
+        let author = self.author.clone();
+        let hash = hash.clone();
+        Ok(self
+            .vault
+            .async_reader(move |txn| {
+                let seq = txn
+                    .query_row(
+                        "SELECT seq FROM Header WHERE hash = :hash AND author = :author",
+                        named_params! {
+                            ":hash": hash,
+                            ":author": author.as_ref(),
+                        },
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                StateQueryResult::Ok(seq)
+            })
+            .await?)
+    }
+
+    async fn query_inner(
+        &self,
+        query: QueryFilter,
+        after_seq: Option<u32>,
+    ) -> SourceChainResult<Vec<Element>> {
+        check_entry_type_resolved(&query)?;
         let author = self.author.clone();
         let public_only = self.public_only;
+        // Pagination can only be left to the database if nothing is
+        // filtered out or added after the database query.
+        let paginate_in_db = self.query_is_exact_in_db(&query)?;
+        let include_entries = query.include_entries && !query.headers_only;
         let mut elements = self
             .vault
             .async_reader({
@@ -658,7 +893,7 @@ where
                 Header.hash AS header_hash, Header.blob AS header_blob
             "
                     .to_string();
-                    if query.include_entries {
+                    if include_entries {
                         sql.push_str(
                             "
                     , Entry.blob AS entry_blob
@@ -670,79 +905,35 @@ where
                 FROM Header
                 ",
                     );
-                    if query.include_entries {
+                    if include_entries {
                         sql.push_str(
                             "
                     LEFT JOIN Entry On Header.entry_hash = Entry.hash
                     ",
                         );
                     }
+                    push_query_filter_sql(&mut sql, &query);
                     sql.push_str(
                         "
-                JOIN DhtOp On DhtOp.header_hash = Header.hash
-                WHERE
-                Header.author = :author
-                AND
-                (:range_start IS NULL AND :range_end IS NULL AND :range_start_hash IS NULL AND :range_end_hash IS NULL AND :range_prior_count IS NULL)
-                ",
-                    );
-                    sql.push_str(match query.sequence_range {
-                        ChainQueryFilterRange::Unbounded => "",
-                        ChainQueryFilterRange::HeaderSeqRange(_, _) => "
-                        OR (Header.seq BETWEEN :range_start AND :range_end)",
-                        ChainQueryFilterRange::HeaderHashRange(_, _) => "
-                        OR (
-                            Header.seq BETWEEN
-                            (SELECT Header.seq WHERE Header.hash = :range_start_hash)
-                            AND
-                            (SELECT Header.seq WHERE Header.hash = :range_end_hash)
-                        )",
-                        ChainQueryFilterRange::HeaderHashTerminated(_, _) => "
-                        OR (
-                            Header.seq BETWEEN
-                            (SELECT Header.seq WHERE Header.hash = :range_end_hash) - :range_prior_count
-                            AND
-                            (SELECT Header.seq WHERE Header.hash = :range_end_hash)
-                        )",
-                    });
-                    sql.push_str(
-                        "
-                AND
-                (:entry_type IS NULL OR Header.entry_type = :entry_type)
-                AND
-                (:header_type IS NULL OR Header.type = :header_type)
                 ORDER BY Header.seq ASC
+                LIMIT IFNULL(:limit, -1) OFFSET IFNULL(:offset, 0)
                 ",
                     );
+                    let (limit, offset) = if paginate_in_db {
+                        (query.limit, query.offset)
+                    } else {
+                        (None, None)
+                    };
                     let mut stmt = txn.prepare(&sql)?;
                     let elements = stmt
                         .query_and_then(
-                        named_params! {
-                                ":author": author.as_ref(),
-                                ":entry_type": query.entry_type,
-                                ":header_type": query.header_type,
-                                ":range_start": match query.sequence_range {
-                                    ChainQueryFilterRange::HeaderSeqRange(start, _) => Some(start),
-                                    _ => None,
-                                },
-                                ":range_end": match query.sequence_range {
-                                    ChainQueryFilterRange::HeaderSeqRange(_, end) => Some(end),
-                                    _ => None,
-                                },
-                                ":range_start_hash": match &query.sequence_range {
-                                    ChainQueryFilterRange::HeaderHashRange(start_hash, _) => Some(start_hash.clone()),
-                                    _ => None,
-                                },
-                                ":range_end_hash": match &query.sequence_range {
-                                    ChainQueryFilterRange::HeaderHashRange(_, end_hash)
-                                    | ChainQueryFilterRange::HeaderHashTerminated(end_hash, _) => Some(end_hash.clone()),
-                                    _ => None,
-                                },
-                                ":range_prior_count": match query.sequence_range {
-                                    ChainQueryFilterRange::HeaderHashTerminated(_, prior_count) => Some(prior_count),
-                                    _ => None,
-                                },
-                            },
+                            query_filter_params!(
+                                author,
+                                query,
+                                after_seq,
+                                ":limit": limit,
+                                ":offset": offset
+                            ),
                             |row| {
                                 let header = from_blob::<SignedHeader>(row.get("header_blob")?)?;
                                 let SignedHeader(header, signature) = header;
@@ -752,16 +943,15 @@ where
                                 let hash: HeaderHash = row.get("header_hash")?;
                                 let header = HeaderHashed::with_pre_hashed(header, hash);
                                 let shh = SignedHeaderHashed::with_presigned(header, signature);
-                                let entry =
-                                    if query.include_entries && (!private_entry || !public_only) {
-                                        let entry: Option<Vec<u8>> = row.get("entry_blob")?;
-                                        match entry {
-                                            Some(entry) => Some(from_blob::<Entry>(entry)?),
-                                            None => None,
-                                        }
-                                    } else {
-                                        None
-                                    };
+                                let entry = if include_entries && (!private_entry || !public_only) {
+                                    let entry: Option<Vec<u8>> = row.get("entry_blob")?;
+                                    match entry {
+                                        Some(entry) => Some(from_blob::<Entry>(entry)?),
+                                        None => None,
+                                    }
+                                } else {
+                                    None
+                                };
                                 StateQueryResult::Ok(Element::new(shh, entry))
                             },
                         )?
@@ -770,12 +960,16 @@ where
                 }
             })
             .await?;
+        if paginate_in_db {
+            return Ok(elements);
+        }
         self.scratch.apply(|scratch| {
             let mut scratch_elements: Vec<_> = scratch
                 .headers()
+                .filter(|shh| after_seq.map_or(true, |seq| shh.header().header_seq() > seq))
                 .filter_map(|shh| {
                     let entry = match shh.header().entry_hash() {
-                        Some(eh) if query.include_entries => scratch.get_entry(eh).ok()?,
+                        Some(eh) if include_entries => scratch.get_entry(eh).ok()?,
                         _ => None,
                     };
                     Some(Element::new(shh.clone(), entry))
@@ -785,7 +979,271 @@ where
 
             elements.extend(scratch_elements);
         })?;
-        Ok(query.filter_elements(elements))
+        Ok(query.paginate(query.filter_elements(elements)))
+    }
+
+    /// Count the elements a query would return, ignoring its `offset` and `limit`.
+    pub async fn query_count(&self, query: QueryFilter) -> SourceChainResult<u32> {
+        check_entry_type_resolved(&query)?;
+        if !self.query_is_exact_in_db(&query)? {
+            let mut query = query.include_entries(false);
+            query.offset = None;
+            query.limit = None;
+            return Ok(self.query(query).await?.len() as u32);
+        }
+        let author = self.author.clone();
+        let count = self
+            .vault
+            .async_reader(move |txn| {
+                let mut sql = "
+                SELECT COUNT(DISTINCT Header.hash)
+                FROM Header
+                "
+                .to_string();
+                push_query_filter_sql(&mut sql, &query);
+                let count = txn.query_row(
+                    &sql,
+                    query_filter_params!(author, query, None::<u32>),
+                    |row| row.get(0),
+                )?;
+                StateQueryResult::Ok(count)
+            })
+            .await?;
+        Ok(count)
+    }
+
+    /// Count the entries on this chain, including the scratch, by their type.
+    /// Only headers are read, never the entries themselves.
+    pub async fn count_entries_by_type(&self) -> SourceChainResult<HashMap<EntryType, u32>> {
+        let author = self.author.clone();
+        let mut counts = self
+            .vault
+            .async_reader(move |txn| {
+                // All the headers in a group share an entry type so
+                // any one of them will do to read it from.
+                let mut stmt = txn.prepare(
+                    "
+                    SELECT Header.blob, COUNT(Header.hash)
+                    FROM Header
+                    WHERE
+                    Header.author = :author
+                    AND Header.entry_type IS NOT NULL
+                    GROUP BY Header.entry_type
+                    ",
+                )?;
+                let counts = stmt
+                    .query_and_then(named_params! { ":author": author }, |row| {
+                        let SignedHeader(header, _) = from_blob(row.get(0)?)?;
+                        let count: u32 = row.get(1)?;
+                        StateQueryResult::Ok(header.entry_type().cloned().map(|et| (et, count)))
+                    })?
+                    .filter_map(Result::transpose)
+                    .collect::<StateQueryResult<HashMap<_, _>>>()?;
+                StateQueryResult::Ok(counts)
+            })
+            .await?;
+        self.scratch.apply(|scratch| {
+            for entry_type in scratch
+                .headers()
+                .filter_map(|shh| shh.header().entry_type())
+            {
+                *counts.entry(entry_type.clone()).or_insert(0) += 1;
+            }
+        })?;
+        Ok(counts)
+    }
+
+    /// Count the app entries on this chain, including the scratch,
+    /// by the zome that defines them.
+    /// System entries like the agent key and cap grants belong to no zome
+    /// and are left out.
+    pub async fn count_entries_by_zome(
+        &self,
+        dna_def: &DnaDef,
+    ) -> SourceChainResult<HashMap<ZomeName, u32>> {
+        let mut counts = HashMap::new();
+        for (entry_type, count) in self.count_entries_by_type().await? {
+            if let EntryType::App(app_entry_type) = entry_type {
+                if let Some((zome_name, _)) = dna_def.zomes.get(app_entry_type.zome_id().index()) {
+                    *counts.entry(zome_name.clone()).or_insert(0) += count;
+                }
+            }
+        }
+        Ok(counts)
+    }
+
+    /// All the elements on this chain, including the scratch, whose entry is
+    /// of the given type, in chain order.
+    pub async fn get_entry_elements_for_type(
+        &self,
+        entry_type: EntryType,
+    ) -> SourceChainResult<Vec<Element>> {
+        self.query(
+            QueryFilter::new()
+                .entry_type(entry_type)
+                .include_entries(true),
+        )
+        .await
+    }
+
+    /// All the app entries of the given type on this chain, including the
+    /// scratch, deserialized into `T` in chain order.
+    /// Entries that fail to deserialize are skipped with a warning, as are
+    /// private entries when this chain is [`SourceChain::public_only`].
+    pub async fn get_entries_for_type<T>(&self, entry_type: EntryType) -> SourceChainResult<Vec<T>>
+    where
+        T: TryFrom<SerializedBytes>,
+        T::Error: std::fmt::Debug,
+    {
+        Ok(self
+            .get_entry_elements_for_type(entry_type)
+            .await?
+            .into_iter()
+            .filter_map(|element| {
+                let bytes = match element.entry().as_option()? {
+                    Entry::App(bytes) | Entry::CounterSign(_, bytes) => bytes.clone().into_sb(),
+                    _ => return None,
+                };
+                match T::try_from(bytes) {
+                    Ok(entry) => Some(entry),
+                    Err(e) => {
+                        tracing::warn!(
+                            header_hash = ?element.header_address(),
+                            "Skipping entry that failed to deserialize: {:?}",
+                            e
+                        );
+                        None
+                    }
+                }
+            })
+            .collect())
+    }
+
+    /// True if the database query alone gives the exact result of this query,
+    /// with no elements from the scratch or filtering afterwards.
+    fn query_is_exact_in_db(&self, query: &QueryFilter) -> SourceChainResult<bool> {
+        let filtered_after = query.entry_hashes.is_some()
+            || !matches!(
+                query.sequence_range,
+                ChainQueryFilterRange::Unbounded | ChainQueryFilterRange::HeaderSeqRange(_, _)
+            );
+        Ok(!filtered_after && self.scratch.apply(|scratch| scratch.is_empty())?)
+    }
+
+    /// Check that every persisted header's `prev_header` points to the
+    /// header before it, returning the first violation as a
+    /// [`SourceChainError::Forked`].
+    /// A gap straight after genesis is left by [`SourceChain::prune_before`]
+    /// and is not a violation.
+    /// This does not look at the scratch.
+    pub async fn verify_integrity(&self) -> SourceChainResult<()> {
+        match self.chain_breaks().await?.into_iter().next() {
+            Some(ChainBreak {
+                sequence,
+                expected_prev,
+                found_prev,
+            }) => Err(SourceChainError::Forked {
+                sequence,
+                expected_prev,
+                found_prev,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// The sequence numbers of every persisted header whose `prev_header`
+    /// doesn't point to the header before it.
+    pub async fn detect_forks(&self) -> SourceChainResult<Vec<u32>> {
+        Ok(self
+            .chain_breaks()
+            .await?
+            .into_iter()
+            .map(|b| b.sequence)
+            .collect())
+    }
+
+    /// Can this header be removed by [`SourceChain::prune_before`]?
+    /// Genesis headers and the chain head are never prunable.
+    /// Otherwise a header is prunable once its published ops and every op
+    /// that depends on it have received their required validation receipts.
+    pub async fn is_prunable(&self, header_hash: &HeaderHash) -> SourceChainResult<bool> {
+        let author = self.author.clone();
+        let header_hash = header_hash.clone();
+        let ops = self
+            .vault
+            .async_reader(move |txn| {
+                let (_, head_seq, _) = chain_head_db(&txn, author.clone())?;
+                SourceChainResult::Ok(prunable_header_ops(&txn, &author, head_seq, &header_hash)?)
+            })
+            .await?;
+        match ops {
+            // Receipts are recorded against the ops held in the dht database.
+            Some(ops) => Ok(self
+                .dht_env
+                .async_reader(move |txn| receipts_complete(&txn, &ops))
+                .await?),
+            None => Ok(false),
+        }
+    }
+
+    async fn chain_breaks(&self) -> SourceChainResult<Vec<ChainBreak>> {
+        let author = self.author.clone();
+        Ok(self
+            .vault
+            .async_reader(move |txn| {
+                let mut stmt = txn.prepare(
+                    "
+                    SELECT Header.hash, Header.seq, Header.prev_hash
+                    FROM Header
+                    WHERE Header.author = :author
+                    ORDER BY Header.seq ASC, Header.hash ASC
+                    ",
+                )?;
+                let pruned = txn
+                    .query_row(
+                        "SELECT last_seq, last_hash FROM ChainPruned WHERE author = :author",
+                        named_params! {
+                            ":author": author,
+                        },
+                        |row| Ok((row.get::<_, u32>(0)?, row.get::<_, HeaderHash>(1)?)),
+                    )
+                    .optional()?;
+                let rows = stmt.query_map(
+                    named_params! {
+                        ":author": author,
+                    },
+                    |row| {
+                        Ok((
+                            row.get::<_, HeaderHash>(0)?,
+                            row.get::<_, u32>(1)?,
+                            row.get::<_, Option<HeaderHash>>(2)?,
+                        ))
+                    },
+                )?;
+                let mut breaks = Vec::new();
+                let mut expected_prev = None;
+                let mut prev_seq = None;
+                for row in rows {
+                    let (hash, sequence, found_prev) = row?;
+                    // The first header after genesis may follow on from
+                    // the last header removed by `prune_before`.
+                    let follows_pruned = prev_seq == Some(POST_GENESIS_SEQ_THRESHOLD - 1)
+                        && pruned.as_ref().map_or(false, |(last_seq, last_hash)| {
+                            sequence == last_seq + 1 && found_prev.as_ref() == Some(last_hash)
+                        });
+                    if !follows_pruned && found_prev != expected_prev {
+                        breaks.push(ChainBreak {
+                            sequence,
+                            expected_prev: expected_prev.clone(),
+                            found_prev,
+                        });
+                    }
+                    expected_prev = Some(hash);
+                    prev_seq = Some(sequence);
+                }
+                StateQueryResult::Ok(breaks)
+            })
+            .await?)
     }
 
     pub async fn is_chain_locked(&self, lock: Vec<u8>) -> SourceChainResult<bool> {
@@ -825,6 +1283,197 @@ where
     }
 }
 
+/// A header which [`SourceChain::prune_before`] may remove.
+struct PruneCandidate {
+    hash: HeaderHash,
+    seq: u32,
+    entry_hash: Option<EntryHash>,
+    /// The ops which need complete receipts before the header can be removed.
+    ops: Vec<DhtOpHash>,
+}
+
+/// The non-genesis headers authored before the cutoff, in sequence order.
+fn prune_candidates(
+    txn: &Transaction,
+    author: &AgentPubKey,
+    head_seq: u32,
+    cutoff: Timestamp,
+) -> StateQueryResult<Vec<PruneCandidate>> {
+    let headers = txn
+        .prepare(
+            "
+            SELECT Header.hash, Header.seq, Header.blob, Header.entry_hash
+            FROM Header
+            WHERE
+            Header.author = :author
+            AND Header.seq >= :first_seq
+            AND Header.seq < :head_seq
+            ORDER BY Header.seq ASC
+            ",
+        )?
+        .query_and_then(
+            named_params! {
+                ":author": author,
+                ":first_seq": POST_GENESIS_SEQ_THRESHOLD,
+                ":head_seq": head_seq,
+            },
+            |row| {
+                let SignedHeader(header, _) = from_blob(row.get(2)?)?;
+                StateQueryResult::Ok((
+                    row.get::<_, HeaderHash>(0)?,
+                    row.get::<_, u32>(1)?,
+                    header.timestamp(),
+                    row.get::<_, Option<EntryHash>>(3)?,
+                ))
+            },
+        )?
+        .collect::<StateQueryResult<Vec<_>>>()?;
+
+    let mut candidates = Vec::new();
+    for (hash, seq, timestamp, entry_hash) in headers {
+        if timestamp >= cutoff {
+            break;
+        }
+        let ops = ops_awaiting_receipts(txn, &hash)?;
+        candidates.push(PruneCandidate {
+            hash,
+            seq,
+            entry_hash,
+            ops,
+        });
+    }
+    Ok(candidates)
+}
+
+/// Remove the candidate headers along with their ops and any entries
+/// no other header refers to, and record the last one removed.
+fn prune_headers(
+    txn: &mut Transaction,
+    author: &AgentPubKey,
+    candidates: Vec<PruneCandidate>,
+) -> StateQueryResult<u32> {
+    let mut pruned = 0;
+    let mut last = None;
+    for candidate in candidates {
+        // The header's ops are removed by the foreign key cascade.
+        txn.execute(
+            "DELETE FROM Header WHERE hash = :hash",
+            named_params! { ":hash": candidate.hash },
+        )?;
+        if let Some(entry_hash) = candidate.entry_hash {
+            txn.execute(
+                "
+                DELETE FROM Entry WHERE hash = :entry_hash
+                AND NOT EXISTS (SELECT 1 FROM Header WHERE entry_hash = :entry_hash)
+                ",
+                named_params! { ":entry_hash": entry_hash },
+            )?;
+        }
+        last = Some((candidate.seq, candidate.hash));
+        pruned += 1;
+    }
+    if let Some((last_seq, last_hash)) = last {
+        txn.execute(
+            "
+            INSERT INTO ChainPruned (author, last_seq, last_hash)
+            VALUES (:author, :last_seq, :last_hash)
+            ",
+            named_params! {
+                ":author": author,
+                ":last_seq": last_seq,
+                ":last_hash": last_hash,
+            },
+        )?;
+    }
+    Ok(pruned)
+}
+
+/// The ops which need complete receipts before this header can be pruned,
+/// or `None` if the header is genesis, the chain head or not in the chain.
+/// See [`SourceChain::is_prunable`].
+fn prunable_header_ops(
+    txn: &Transaction,
+    author: &AgentPubKey,
+    head_seq: u32,
+    header_hash: &HeaderHash,
+) -> StateQueryResult<Option<Vec<DhtOpHash>>> {
+    let in_range: bool = txn
+        .query_row(
+            "
+            SELECT Header.seq >= :first_seq AND Header.seq < :head_seq
+            FROM Header
+            WHERE Header.hash = :hash AND Header.author = :author
+            ",
+            named_params! {
+                ":first_seq": POST_GENESIS_SEQ_THRESHOLD,
+                ":head_seq": head_seq,
+                ":hash": header_hash,
+                ":author": author,
+            },
+            |row| row.get(0),
+        )
+        .optional()?
+        .unwrap_or(false);
+    if in_range {
+        Ok(Some(ops_awaiting_receipts(txn, header_hash)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// The header's own published ops and any ops which depend on the header.
+fn ops_awaiting_receipts(
+    txn: &Transaction,
+    header_hash: &HeaderHash,
+) -> StateQueryResult<Vec<DhtOpHash>> {
+    let ops = txn
+        .prepare(
+            "
+            SELECT DhtOp.hash FROM DhtOp
+            JOIN Header ON DhtOp.header_hash = Header.hash
+            WHERE DhtOp.header_hash = :hash
+            AND (DhtOp.type != :store_entry OR Header.private_entry = 0)
+            UNION
+            SELECT DhtOp.hash FROM DhtOp
+            WHERE DhtOp.dependency = :hash
+            ",
+        )?
+        .query_map(
+            named_params! {
+                ":hash": header_hash,
+                ":store_entry": DhtOpType::StoreEntry,
+            },
+            |row| row.get(0),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ops)
+}
+
+/// Have all of these ops received their required validation receipts?
+/// An op the dht database doesn't hold can't have received any.
+fn receipts_complete(txn: &Transaction, ops: &[DhtOpHash]) -> StateQueryResult<bool> {
+    for op in ops {
+        let complete: Option<Option<bool>> = txn
+            .query_row(
+                "SELECT receipts_complete FROM DhtOp WHERE hash = :hash",
+                named_params! { ":hash": op },
+                |row| row.get(0),
+            )
+            .optional()?;
+        if complete != Some(Some(true)) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// A header that doesn't follow on from the header before it.
+struct ChainBreak {
+    sequence: u32,
+    expected_prev: Option<HeaderHash>,
+    found_prev: Option<HeaderHash>,
+}
+
 pub fn lock_for_entry(entry: Option<&Entry>) -> SourceChainResult<Vec<u8>> {
     Ok(match entry {
         Some(Entry::CounterSign(session_data, _)) => holo_hash::encode::blake2b_256(
@@ -1304,16 +1953,13 @@ pub mod tests {
         Ok(())
     }
     #[tokio::test(flavor = "multi_thread")]
-    async fn test_relaxed_ordering_with_entry() -> SourceChainResult<()> {
+    async fn test_verify_integrity() -> SourceChainResult<()> {
         let test_env = test_authored_env();
         let dht_env = test_dht_env();
         let keystore = test_keystore();
         let env = test_env.env();
         let alice = fixt!(AgentPubKey, Predictable, 0);
 
-        let mut mock = MockHolochainP2pDnaT::new();
-        mock.expect_authority_for_hash().returning(|_| Ok(false));
-
         source_chain::genesis(
             env.clone(),
             dht_env.env(),
@@ -1324,43 +1970,342 @@ pub mod tests {
         )
         .await
         .unwrap();
+        let chain = SourceChain::new(env.clone(), dht_env.env(), keystore, alice.clone()).await?;
 
-        let chain_1 = SourceChain::new(
-            env.clone().into(),
-            dht_env.env(),
-            keystore.clone(),
-            alice.clone(),
-        )
-        .await?;
-        let chain_2 = SourceChain::new(
-            env.clone().into(),
-            dht_env.env(),
-            keystore.clone(),
-            alice.clone(),
-        )
-        .await?;
-        let chain_3 = SourceChain::new(
-            env.clone().into(),
-            dht_env.env(),
-            keystore.clone(),
-            alice.clone(),
-        )
-        .await?;
+        // Genesis leaves a valid chain of three headers.
+        chain.verify_integrity().await?;
+        assert!(chain.detect_forks().await?.is_empty());
 
-        let entry_1 = Entry::App(fixt!(AppEntryBytes));
-        let eh1 = EntryHash::with_data_sync(&entry_1);
-        let create = builder::Create {
-            entry_type: EntryType::App(fixt!(AppEntryType)),
-            entry_hash: eh1.clone(),
+        let hash_at = |seq: u32| {
+            fresh_reader_test(env.clone(), |txn| {
+                txn.query_row("SELECT hash FROM Header WHERE seq = ?", [seq], |row| {
+                    row.get::<_, HeaderHash>(0)
+                })
+                .unwrap()
+            })
         };
-        let h1 = chain_1
-            .put(
-                None,
-                create,
-                Some(entry_1.clone()),
-                ChainTopOrdering::Strict,
+        let genesis_hash = hash_at(0);
+        let agent_pkg_hash = hash_at(1);
+
+        // Point the agent header somewhere else.
+        let wrong_prev = fixt!(HeaderHash);
+        env.conn()
+            .unwrap()
+            .execute(
+                "UPDATE Header SET prev_hash = ? WHERE seq = 2",
+                [&wrong_prev],
             )
-            .await
+            .unwrap();
+        assert_matches!(
+            chain.verify_integrity().await,
+            Err(SourceChainError::Forked {
+                sequence: 2,
+                expected_prev: Some(expected),
+                found_prev: Some(found),
+            }) if expected == agent_pkg_hash && found == wrong_prev
+        );
+        assert_eq!(chain.detect_forks().await?, vec![2]);
+
+        // Remove the agent validation package to leave a gap.
+        env.conn()
+            .unwrap()
+            .execute(
+                "UPDATE Header SET prev_hash = ? WHERE seq = 2",
+                [&agent_pkg_hash],
+            )
+            .unwrap();
+        env.conn()
+            .unwrap()
+            .execute("DELETE FROM DhtOp WHERE header_hash = ?", [&agent_pkg_hash])
+            .unwrap();
+        env.conn()
+            .unwrap()
+            .execute("DELETE FROM Header WHERE hash = ?", [&agent_pkg_hash])
+            .unwrap();
+        assert_matches!(
+            chain.verify_integrity().await,
+            Err(SourceChainError::Forked {
+                sequence: 2,
+                expected_prev: Some(expected),
+                found_prev: Some(found),
+            }) if expected == genesis_hash && found == agent_pkg_hash
+        );
+        assert_eq!(chain.detect_forks().await?, vec![2]);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_prune_before() -> SourceChainResult<()> {
+        let test_env = test_authored_env();
+        let dht_env = test_dht_env();
+        let keystore = test_keystore();
+        let env = test_env.env();
+        let alice = fixt!(AgentPubKey, Predictable, 0);
+
+        // Hold every op in the dht database so it can collect receipts.
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(true));
+
+        source_chain::genesis(
+            env.clone(),
+            dht_env.env(),
+            keystore.clone(),
+            fake_dna_hash(1),
+            alice.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        // Commit 50 entries, take the cutoff, then commit 50 more.
+        let mut cutoff = None;
+        let mut first_entry_hash = None;
+        for batch in 0..2u32 {
+            let chain =
+                SourceChain::new(env.clone(), dht_env.env(), keystore.clone(), alice.clone())
+                    .await?;
+            for i in 0..50u32 {
+                let bytes = (batch * 50 + i).to_le_bytes().to_vec();
+                let entry = Entry::app(UnsafeBytes::from(bytes).into()).unwrap();
+                let entry_hash = EntryHash::with_data_sync(&entry);
+                first_entry_hash.get_or_insert_with(|| entry_hash.clone());
+                let create = builder::Create {
+                    entry_type: EntryType::App(AppEntryType::new(
+                        0.into(),
+                        0.into(),
+                        EntryVisibility::Public,
+                    )),
+                    entry_hash,
+                };
+                chain
+                    .put(None, create, Some(entry), ChainTopOrdering::Strict)
+                    .await?;
+            }
+            chain.flush(&mock).await?;
+            cutoff.get_or_insert_with(Timestamp::now);
+        }
+        let cutoff = cutoff.unwrap();
+        let first_entry_hash = first_entry_hash.unwrap();
+
+        let chain =
+            SourceChain::new(env.clone(), dht_env.env(), keystore.clone(), alice.clone()).await?;
+        assert_eq!(chain.len()?, 103);
+        let header_at = |seq: u32| {
+            fresh_reader_test(env.clone(), |txn| {
+                txn.query_row("SELECT hash FROM Header WHERE seq = ?", [seq], |row| {
+                    row.get::<_, HeaderHash>(0)
+                })
+                .unwrap()
+            })
+        };
+
+        // Receive a receipt for every op from each validator,
+        // the way a cell handles incoming validation receipts.
+        let validators = vec![
+            keystore.new_sign_keypair_random().await.unwrap(),
+            keystore.new_sign_keypair_random().await.unwrap(),
+        ];
+        let op_hashes = fresh_reader_test(dht_env.env(), |txn| {
+            txn.prepare("SELECT hash FROM DhtOp")
+                .unwrap()
+                .query_map([], |row| row.get::<_, DhtOpHash>(0))
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        });
+        let receive_receipts = |validator: AgentPubKey| {
+            let op_hashes = op_hashes.clone();
+            let keystore = keystore.clone();
+            let dht_env = dht_env.env();
+            async move {
+                for dht_op_hash in op_hashes {
+                    let receipt = ValidationReceipt {
+                        dht_op_hash,
+                        validation_status: ValidationStatus::Valid,
+                        validators: vec![validator.clone()],
+                        when_integrated: Timestamp::now(),
+                    };
+                    let receipt = validation_receipts::sign_validation_receipt(receipt, &keystore)
+                        .await
+                        .unwrap()
+                        .unwrap();
+                    dht_env
+                        .async_commit(move |txn| {
+                            validation_receipts::add_and_check_complete(txn, receipt, 1)
+                        })
+                        .await
+                        .unwrap();
+                }
+            }
+        };
+
+        // Nothing can be pruned while the ops still need receipts.
+        assert!(!chain.is_prunable(&header_at(3)).await?);
+        assert_eq!(chain.prune_before(cutoff).await?, 0);
+        receive_receipts(validators[0].clone()).await;
+        assert!(!chain.is_prunable(&header_at(3)).await?);
+        assert_eq!(chain.prune_before(cutoff).await?, 0);
+
+        receive_receipts(validators[1].clone()).await;
+        assert!(chain.is_prunable(&header_at(3)).await?);
+        // Genesis and the chain head are never prunable.
+        assert!(!chain.is_prunable(&header_at(0)).await?);
+        assert!(!chain.is_prunable(&header_at(102)).await?);
+
+        assert_eq!(chain.prune_before(cutoff).await?, 50);
+        chain.verify_integrity().await?;
+
+        let (num_headers, first_entry_count): (u32, u32) = fresh_reader_test(env.clone(), |txn| {
+            (
+                txn.query_row("SELECT COUNT(*) FROM Header", [], |row| row.get(0))
+                    .unwrap(),
+                txn.query_row(
+                    "SELECT COUNT(*) FROM Entry WHERE hash = ?",
+                    [&first_entry_hash],
+                    |row| row.get(0),
+                )
+                .unwrap(),
+            )
+        });
+        assert_eq!(num_headers, 53);
+        assert_eq!(first_entry_count, 0);
+
+        // The pruned chain can still be loaded and extended.
+        let chain =
+            SourceChain::new(env.clone(), dht_env.env(), keystore.clone(), alice.clone()).await?;
+        assert_eq!(chain.len()?, 103);
+        chain
+            .put(
+                None,
+                builder::InitZomesComplete {},
+                None,
+                ChainTopOrdering::Strict,
+            )
+            .await?;
+        chain.flush(&mock).await?;
+        chain.verify_integrity().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unpruned_gap_after_genesis_is_a_fork() -> SourceChainResult<()> {
+        let test_env = test_authored_env();
+        let dht_env = test_dht_env();
+        let keystore = test_keystore();
+        let env = test_env.env();
+        let alice = fixt!(AgentPubKey, Predictable, 0);
+
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(false));
+
+        source_chain::genesis(
+            env.clone(),
+            dht_env.env(),
+            keystore.clone(),
+            fake_dna_hash(1),
+            alice.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+        let chain =
+            SourceChain::new(env.clone(), dht_env.env(), keystore.clone(), alice.clone()).await?;
+        for _ in 0..2 {
+            chain
+                .put(
+                    None,
+                    builder::InitZomesComplete {},
+                    None,
+                    ChainTopOrdering::Strict,
+                )
+                .await?;
+        }
+        chain.flush(&mock).await?;
+        chain.verify_integrity().await?;
+
+        // Remove the first header after genesis without pruning it.
+        let first_hash: HeaderHash = fresh_reader_test(env.clone(), |txn| {
+            txn.query_row("SELECT hash FROM Header WHERE seq = 3", [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+        });
+        env.conn()
+            .unwrap()
+            .execute("DELETE FROM Header WHERE hash = ?", [&first_hash])
+            .unwrap();
+        assert_matches!(
+            chain.verify_integrity().await,
+            Err(SourceChainError::Forked {
+                sequence: 4,
+                found_prev: Some(found),
+                ..
+            }) if found == first_hash
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_relaxed_ordering_with_entry() -> SourceChainResult<()> {
+        let test_env = test_authored_env();
+        let dht_env = test_dht_env();
+        let keystore = test_keystore();
+        let env = test_env.env();
+        let alice = fixt!(AgentPubKey, Predictable, 0);
+
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(false));
+
+        source_chain::genesis(
+            env.clone(),
+            dht_env.env(),
+            keystore.clone(),
+            fake_dna_hash(1),
+            alice.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let chain_1 = SourceChain::new(
+            env.clone().into(),
+            dht_env.env(),
+            keystore.clone(),
+            alice.clone(),
+        )
+        .await?;
+        let chain_2 = SourceChain::new(
+            env.clone().into(),
+            dht_env.env(),
+            keystore.clone(),
+            alice.clone(),
+        )
+        .await?;
+        let chain_3 = SourceChain::new(
+            env.clone().into(),
+            dht_env.env(),
+            keystore.clone(),
+            alice.clone(),
+        )
+        .await?;
+
+        let entry_1 = Entry::App(fixt!(AppEntryBytes));
+        let eh1 = EntryHash::with_data_sync(&entry_1);
+        let create = builder::Create {
+            entry_type: EntryType::App(fixt!(AppEntryType)),
+            entry_hash: eh1.clone(),
+        };
+        let h1 = chain_1
+            .put(
+                None,
+                create,
+                Some(entry_1.clone()),
+                ChainTopOrdering::Strict,
+            )
+            .await
             .unwrap();
 
         let entry_err = Entry::App(fixt!(AppEntryBytes));
@@ -1817,6 +2762,471 @@ pub mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn query_pagination() -> SourceChainResult<()> {
+        let test_env = test_authored_env();
+        let dht_env = test_dht_env();
+        let keystore = test_keystore();
+        let vault = test_env.env();
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(false));
+        let author = keystore.new_sign_keypair_random().await.unwrap();
+        genesis(
+            vault.clone().into(),
+            dht_env.env(),
+            keystore.clone(),
+            fixt!(DnaHash),
+            author.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+        let source_chain = SourceChain::new(
+            vault.clone(),
+            dht_env.env(),
+            keystore.clone(),
+            author.clone(),
+        )
+        .await
+        .unwrap();
+
+        let entry_type = EntryType::App(fixt!(AppEntryType, EntryVisibility::Public));
+        let mut headers = Vec::new();
+        for _ in 0..100 {
+            let entry = Entry::App(fixt!(AppEntryBytes));
+            let create = builder::Create {
+                entry_type: entry_type.clone(),
+                entry_hash: EntryHash::with_data_sync(&entry),
+            };
+            let h = source_chain
+                .put(None, create, Some(entry), ChainTopOrdering::default())
+                .await
+                .unwrap();
+            headers.push(h);
+        }
+
+        let filter = QueryFilter::new().entry_type(entry_type);
+        let page_through = |source_chain: SourceChain| {
+            let filter = filter.clone();
+            async move {
+                let mut paged = Vec::new();
+                for page in 0..10 {
+                    let elements = source_chain
+                        .query(filter.clone().offset(page * 10).limit(10))
+                        .await
+                        .unwrap();
+                    assert_eq!(elements.len(), 10);
+                    paged.extend(elements.iter().map(|el| el.header_address().clone()));
+                }
+                let count = source_chain.query_count(filter.clone()).await.unwrap();
+                (paged, count)
+            }
+        };
+
+        // Unflushed headers are paged through in memory.
+        let (paged, count) = page_through(source_chain.clone()).await;
+        assert_eq!(paged, headers);
+        assert_eq!(count, 100);
+
+        // Flushed headers are paged through in the database.
+        source_chain.flush(&mock).await.unwrap();
+        let source_chain = SourceChain::new(vault, dht_env.env(), keystore, author)
+            .await
+            .unwrap();
+        let (paged, count) = page_through(source_chain.clone()).await;
+        assert_eq!(paged, headers);
+        assert_eq!(count, 100);
+        assert_eq!(source_chain.query_count(QueryFilter::new()).await?, 103);
+
+        // An entry type id has to be resolved by the zome before querying.
+        let unresolved = QueryFilter::new().entry_type_str("post");
+        assert!(matches!(
+            source_chain.query(unresolved.clone()).await,
+            Err(SourceChainError::UnresolvedEntryTypeStr(id)) if id == "post"
+        ));
+        assert!(matches!(
+            source_chain.query_count(unresolved).await,
+            Err(SourceChainError::UnresolvedEntryTypeStr(_))
+        ));
+        assert!(source_chain
+            .query(filter.clone().offset(100))
+            .await?
+            .is_empty());
+
+        // Headers only ignores include_entries.
+        let full = source_chain
+            .query(filter.clone().include_entries(true))
+            .await?;
+        let headers_only = source_chain
+            .query(filter.include_entries(true).headers_only(true))
+            .await?;
+        assert_eq!(headers_only.len(), 100);
+        assert!(full
+            .iter()
+            .all(|el| matches!(el.entry(), ElementEntry::Present(_))));
+        assert!(headers_only
+            .iter()
+            .all(|el| matches!(el.entry(), ElementEntry::NotStored)));
+        assert_eq!(
+            full.iter()
+                .map(|el| el.header_address())
+                .collect::<Vec<_>>(),
+            headers_only
+                .iter()
+                .map(|el| el.header_address())
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn query_sequence_range() -> SourceChainResult<()> {
+        let test_env = test_authored_env();
+        let dht_env = test_dht_env();
+        let keystore = test_keystore();
+        let vault = test_env.env();
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(false));
+        let author = keystore.new_sign_keypair_random().await.unwrap();
+        genesis(
+            vault.clone().into(),
+            dht_env.env(),
+            keystore.clone(),
+            fixt!(DnaHash),
+            author.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+        let source_chain = SourceChain::new(
+            vault.clone(),
+            dht_env.env(),
+            keystore.clone(),
+            author.clone(),
+        )
+        .await
+        .unwrap();
+        // Genesis is seq 0 to 2, so the chain ends at seq 49.
+        for _ in 3..50 {
+            let entry = Entry::App(fixt!(AppEntryBytes));
+            let create = builder::Create {
+                entry_type: EntryType::App(fixt!(AppEntryType)),
+                entry_hash: EntryHash::with_data_sync(&entry),
+            };
+            source_chain
+                .put(None, create, Some(entry), ChainTopOrdering::default())
+                .await
+                .unwrap();
+        }
+
+        let seqs = |elements: Vec<Element>| {
+            elements
+                .iter()
+                .map(|el| el.header().header_seq())
+                .collect::<Vec<_>>()
+        };
+        let check = |source_chain: SourceChain| async move {
+            let range = source_chain
+                .query(QueryFilter::new().sequence_range((10..=20).into()))
+                .await
+                .unwrap();
+            assert_eq!(seqs(range), (10..=20).collect::<Vec<_>>());
+
+            // Ranges past the chain head return what is available.
+            let past_head = source_chain
+                .query(QueryFilter::new().sequence_range((40..=100).into()))
+                .await
+                .unwrap();
+            assert_eq!(seqs(past_head), (40..=49).collect::<Vec<_>>());
+            assert!(source_chain
+                .query(QueryFilter::new().sequence_range((50..=60).into()))
+                .await
+                .unwrap()
+                .is_empty());
+        };
+
+        // Unflushed
+        check(source_chain.clone()).await;
+
+        // Flushed
+        source_chain.flush(&mock).await.unwrap();
+        let source_chain = SourceChain::new(vault, dht_env.env(), keystore, author)
+            .await
+            .unwrap();
+        check(source_chain).await;
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn query_header_types() -> SourceChainResult<()> {
+        let test_env = test_authored_env();
+        let dht_env = test_dht_env();
+        let keystore = test_keystore();
+        let vault = test_env.env();
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(false));
+        let author = keystore.new_sign_keypair_random().await.unwrap();
+        genesis(
+            vault.clone().into(),
+            dht_env.env(),
+            keystore.clone(),
+            fixt!(DnaHash),
+            author.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+        let source_chain = SourceChain::new(
+            vault.clone(),
+            dht_env.env(),
+            keystore.clone(),
+            author.clone(),
+        )
+        .await
+        .unwrap();
+
+        let entry_type = EntryType::App(fixt!(AppEntryType, EntryVisibility::Public));
+        let entry = Entry::App(fixt!(AppEntryBytes));
+        let entry_hash = EntryHash::with_data_sync(&entry);
+        let create = source_chain
+            .put(
+                None,
+                builder::Create {
+                    entry_type: entry_type.clone(),
+                    entry_hash: entry_hash.clone(),
+                },
+                Some(entry),
+                ChainTopOrdering::default(),
+            )
+            .await
+            .unwrap();
+        let new_entry = Entry::App(fixt!(AppEntryBytes));
+        let new_entry_hash = EntryHash::with_data_sync(&new_entry);
+        let update = source_chain
+            .put(
+                None,
+                builder::Update {
+                    original_entry_address: entry_hash,
+                    original_header_address: create.clone(),
+                    entry_type,
+                    entry_hash: new_entry_hash.clone(),
+                },
+                Some(new_entry),
+                ChainTopOrdering::default(),
+            )
+            .await
+            .unwrap();
+        let delete = source_chain
+            .put(
+                None,
+                builder::Delete {
+                    deletes_address: update.clone(),
+                    deletes_entry_address: new_entry_hash,
+                },
+                None,
+                ChainTopOrdering::default(),
+            )
+            .await
+            .unwrap();
+
+        let hashes = |elements: Vec<Element>| {
+            elements
+                .into_iter()
+                .map(|el| el.header_address().clone())
+                .collect::<Vec<_>>()
+        };
+        let check =
+            |source_chain: SourceChain| {
+                let (create, update, delete) = (create.clone(), update.clone(), delete.clone());
+                async move {
+                    // Genesis also creates the agent key entry.
+                    let creates = source_chain
+                        .query(QueryFilter::new().header_type(HeaderType::Create))
+                        .await
+                        .unwrap();
+                    assert_eq!(creates.len(), 2);
+                    assert_eq!(creates[1].header_address(), &create);
+                    assert_eq!(
+                        hashes(
+                            source_chain
+                                .query(QueryFilter::new().header_type(HeaderType::Update))
+                                .await
+                                .unwrap()
+                        ),
+                        vec![update.clone()]
+                    );
+                    assert_eq!(
+                        hashes(
+                            source_chain
+                                .query(QueryFilter::new().header_types(vec![HeaderType::Delete]))
+                                .await
+                                .unwrap()
+                        ),
+                        vec![delete.clone()]
+                    );
+                    assert_eq!(
+                        hashes(
+                            source_chain
+                                .query(
+                                    QueryFilter::new()
+                                        .header_types(vec![HeaderType::Update, HeaderType::Delete])
+                                )
+                                .await
+                                .unwrap()
+                        ),
+                        vec![update, delete]
+                    );
+                    assert_eq!(
+                        source_chain
+                            .query_count(QueryFilter::new().header_types(vec![
+                                HeaderType::Dna,
+                                HeaderType::AgentValidationPkg,
+                            ]))
+                            .await
+                            .unwrap(),
+                        2
+                    );
+                    assert!(source_chain
+                        .query(QueryFilter::new().header_types(vec![]))
+                        .await
+                        .unwrap()
+                        .is_empty());
+                }
+            };
+
+        // Unflushed
+        check(source_chain.clone()).await;
+
+        // Flushed
+        source_chain.flush(&mock).await.unwrap();
+        let source_chain = SourceChain::new(vault, dht_env.env(), keystore, author)
+            .await
+            .unwrap();
+        check(source_chain).await;
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn query_after_cursor() -> SourceChainResult<()> {
+        let test_env = test_authored_env();
+        let dht_env = test_dht_env();
+        let keystore = test_keystore();
+        let vault = test_env.env();
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(false));
+        let author = keystore.new_sign_keypair_random().await.unwrap();
+        genesis(
+            vault.clone().into(),
+            dht_env.env(),
+            keystore.clone(),
+            fixt!(DnaHash),
+            author.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let entry_type = EntryType::App(fixt!(AppEntryType));
+        let put_entries = |n: usize| {
+            let vault = vault.clone();
+            let dht_env = dht_env.env();
+            let keystore = keystore.clone();
+            let author = author.clone();
+            let entry_type = entry_type.clone();
+            let mock = &mock;
+            async move {
+                let source_chain = SourceChain::new(vault, dht_env, keystore, author)
+                    .await
+                    .unwrap();
+                let mut headers = Vec::new();
+                for _ in 0..n {
+                    let entry = Entry::App(fixt!(AppEntryBytes));
+                    let create = builder::Create {
+                        entry_type: entry_type.clone(),
+                        entry_hash: EntryHash::with_data_sync(&entry),
+                    };
+                    headers.push(
+                        source_chain
+                            .put(None, create, Some(entry), ChainTopOrdering::default())
+                            .await
+                            .unwrap(),
+                    );
+                }
+                source_chain.flush(mock).await.unwrap();
+                headers
+            }
+        };
+        let mut headers = put_entries(45).await;
+
+        // Page through the chain while it keeps growing.
+        let filter = QueryFilter::new().entry_type(entry_type.clone());
+        let mut paged = Vec::new();
+        let mut after_hash = None;
+        loop {
+            let source_chain = SourceChain::new(
+                vault.clone(),
+                dht_env.env(),
+                keystore.clone(),
+                author.clone(),
+            )
+            .await
+            .unwrap();
+            let elements = source_chain
+                .query_after(filter.clone(), after_hash.clone(), 10)
+                .await?;
+            assert!(elements.len() <= 10);
+            if paged.len() < 20 {
+                headers.extend(put_entries(3).await);
+            }
+            match elements.last() {
+                Some(last) => after_hash = Some(last.header_address().clone()),
+                None => break,
+            }
+            paged.extend(elements.iter().map(|el| el.header_address().clone()));
+        }
+        assert_eq!(paged, headers);
+
+        // Unflushed headers are paged through from the scratch.
+        let source_chain = SourceChain::new(
+            vault.clone(),
+            dht_env.env(),
+            keystore.clone(),
+            author.clone(),
+        )
+        .await
+        .unwrap();
+        let entry = Entry::App(fixt!(AppEntryBytes));
+        let create = builder::Create {
+            entry_type,
+            entry_hash: EntryHash::with_data_sync(&entry),
+        };
+        let unflushed = source_chain
+            .put(None, create, Some(entry), ChainTopOrdering::default())
+            .await
+            .unwrap();
+        let elements = source_chain
+            .query_after(filter.clone(), headers.last().cloned(), 10)
+            .await?;
+        assert_eq!(elements.len(), 1);
+        assert_eq!(*elements[0].header_address(), unflushed);
+        assert!(source_chain
+            .query_after(filter.clone(), Some(unflushed), 10)
+            .await?
+            .is_empty());
+
+        assert_matches!(
+            source_chain
+                .query_after(filter, Some(fixt!(HeaderHash)), 10)
+                .await,
+            Err(SourceChainError::ElementMissing(_))
+        );
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn source_chain_buffer_dump_entries_json() -> SourceChainResult<()> {
         let test_env = test_authored_env();
@@ -50,6 +50,14 @@ pub enum SourceChainError {
     #[error("The source chain's head is pointing to an address which has no content.")]
     MissingHead,
 
+    /// A header's `prev_header` doesn't point to the header before it.
+    #[error("The source chain is forked at sequence {sequence}. Expected prev_header {expected_prev:?} but found {found_prev:?}")]
+    Forked {
+        sequence: u32,
+        expected_prev: Option<HeaderHash>,
+        found_prev: Option<HeaderHash>,
+    },
+
     #[error("The content at address {0} is malformed and can't be deserialized.")]
     MalformedEntry(EntryHash),
 
@@ -110,6 +118,9 @@ pub enum SourceChainError {
     #[error("The source chain was missing for a host call that requires it.")]
     SourceChainMissing,
 
+    #[error("The query filters on the entry type id {0:?}, which must be resolved into an entry type before querying the source chain.")]
+    UnresolvedEntryTypeStr(String),
+
     /// Other
     #[error("Other: {0}")]
     Other(Box<dyn std::error::Error + Send + Sync>),
@@ -116,6 +116,18 @@ pub fn test_environments() -> TestEnvs {
     TestEnvs::new(tempdir)
 }
 
+/// Create a fresh set of test environments whose databases live in memory.
+///
+/// Conductors built on these environments also keep their per-DNA and p2p databases in memory.
+pub fn test_environments_in_memory() -> TestEnvs {
+    let tempdir = tempfile::Builder::new()
+        .prefix("holochain-test-environments")
+        .suffix(&nanoid::nanoid!())
+        .tempdir()
+        .unwrap();
+    TestEnvs::in_memory(tempdir, test_keystore())
+}
+
 /// Create a fresh set of test environments with a new TempDir in a given directory.
 pub fn test_environments_in(path: impl AsRef<Path>) -> TestEnvs {
     let tempdir = tempfile::Builder::new()
@@ -235,6 +247,8 @@ pub struct TestEnvs {
     dir: Either<TempDir, PathBuf>,
     /// The keystore sender for these environments
     keystore: MetaLairClient,
+    /// Whether the databases of these environments live in memory
+    in_memory: bool,
 }
 
 #[allow(missing_docs)]
@@ -252,6 +266,25 @@ impl TestEnvs {
             p2p_metrics,
             dir: Either::Left(tempdir),
             keystore,
+            in_memory: false,
+        }
+    }
+
+    /// Create all three non-cell environments at once in memory with a custom keystore.
+    /// The TempDir is still used as the root path of the environments but nothing is written to it.
+    pub fn in_memory(tempdir: TempDir, keystore: MetaLairClient) -> Self {
+        let conductor = DbWrite::open_in_memory(DbKindConductor).unwrap();
+        let wasm = DbWrite::open_in_memory(DbKindWasm).unwrap();
+        let p2p = Arc::new(parking_lot::Mutex::new(HashMap::new()));
+        let p2p_metrics = Arc::new(parking_lot::Mutex::new(HashMap::new()));
+        Self {
+            conductor,
+            wasm,
+            p2p,
+            p2p_metrics,
+            dir: Either::Left(tempdir),
+            keystore,
+            in_memory: true,
         }
     }
 
@@ -290,6 +323,7 @@ impl TestEnvs {
             p2p_metrics,
             dir,
             keystore,
+            in_memory,
         } = self;
         let dir = dir.left_and_then(|tempdir| {
             let pathbuf = tempdir.into_path();
@@ -303,6 +337,7 @@ impl TestEnvs {
             p2p_metrics,
             dir,
             keystore,
+            in_memory,
         }
     }
 
@@ -311,6 +346,11 @@ impl TestEnvs {
             .expect_left("can only use into_tempdir if not already solidified")
     }
 
+    /// Whether the databases of these environments live in memory
+    pub fn is_in_memory(&self) -> bool {
+        self.in_memory
+    }
+
     /// Get the root path for these environments
     pub fn path(&self) -> &Path {
         match &self.dir {
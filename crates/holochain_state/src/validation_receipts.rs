@@ -3,7 +3,6 @@
 use futures::Stream;
 use futures::StreamExt;
 use futures::TryStreamExt;
-use holo_hash::AgentPubKey;
 use holo_hash::DhtOpHash;
 use holochain_keystore::AgentPubKeyExt;
 use holochain_keystore::MetaLairClient;
@@ -13,74 +12,44 @@ use holochain_sqlite::rusqlite::named_params;
 use holochain_sqlite::rusqlite::OptionalExtension;
 use holochain_sqlite::rusqlite::Transaction;
 use holochain_zome_types::signature::Signature;
-use holochain_zome_types::Timestamp;
-use holochain_zome_types::ValidationStatus;
+use holochain_zome_types::ValidationReceipt;
 use mutations::StateMutationResult;
 
 use crate::mutations;
 use crate::prelude::from_blob;
 use crate::prelude::StateQueryResult;
 
-/// Validation receipt content - to be signed.
-#[derive(
-    Debug,
-    Clone,
-    PartialEq,
-    Eq,
-    PartialOrd,
-    Ord,
-    Hash,
-    serde::Serialize,
-    serde::Deserialize,
-    SerializedBytes,
-)]
-pub struct ValidationReceipt {
-    /// the op this validation receipt is for.
-    pub dht_op_hash: DhtOpHash,
-
-    /// the result of this validation.
-    pub validation_status: ValidationStatus,
-
-    /// the remote validator which is signing this receipt.
-    pub validators: Vec<AgentPubKey>,
-
-    /// Time when the op was integrated
-    pub when_integrated: Timestamp,
-}
-
-impl ValidationReceipt {
-    /// Sign this validation receipt.
-    pub async fn sign(
-        self,
-        keystore: &MetaLairClient,
-    ) -> holochain_keystore::LairResult<Option<SignedValidationReceipt>> {
-        if self.validators.is_empty() {
-            return Ok(None);
-        }
-        let this = self.clone();
-        // Try to sign with all validators but silently fail on
-        // any that cannot sign.
-        // If all signatures fail then return an error.
-        let futures = self
-            .validators
-            .iter()
-            .map(|validator| {
-                let this = this.clone();
-                let validator = validator.clone();
-                let keystore = keystore.clone();
-                async move { validator.sign(&keystore, this).await }
-            })
-            .collect::<Vec<_>>();
-        let stream = futures::stream::iter(futures);
-        let signatures = try_stream_of_results(stream).await?;
-        if signatures.is_empty() {
-            unreachable!("Signatures cannot be empty because the validators vec is not empty");
-        }
-        Ok(Some(SignedValidationReceipt {
-            receipt: self,
-            validators_signatures: signatures,
-        }))
+/// Sign this validation receipt.
+pub async fn sign_validation_receipt(
+    receipt: ValidationReceipt,
+    keystore: &MetaLairClient,
+) -> holochain_keystore::LairResult<Option<SignedValidationReceipt>> {
+    if receipt.validators.is_empty() {
+        return Ok(None);
+    }
+    let this = receipt.clone();
+    // Try to sign with all validators but silently fail on
+    // any that cannot sign.
+    // If all signatures fail then return an error.
+    let futures = receipt
+        .validators
+        .iter()
+        .map(|validator| {
+            let this = this.clone();
+            let validator = validator.clone();
+            let keystore = keystore.clone();
+            async move { validator.sign(&keystore, this).await }
+        })
+        .collect::<Vec<_>>();
+    let stream = futures::stream::iter(futures);
+    let signatures = try_stream_of_results(stream).await?;
+    if signatures.is_empty() {
+        unreachable!("Signatures cannot be empty because the validators vec is not empty");
     }
+    Ok(Some(SignedValidationReceipt {
+        receipt,
+        validators_signatures: signatures,
+    }))
 }
 
 /// Try to collect a stream of futures that return results into a vec.
@@ -152,6 +121,19 @@ pub fn add_if_unique(
     mutations::insert_validation_receipt(txn, receipt)
 }
 
+/// Add a receipt for an op, first marking the op's receipts as complete
+/// if it already has at least the required number of them.
+pub fn add_and_check_complete(
+    txn: &mut Transaction,
+    receipt: SignedValidationReceipt,
+    required_receipts: usize,
+) -> StateMutationResult<()> {
+    if count_valid(txn, &receipt.receipt.dht_op_hash)? >= required_receipts {
+        mutations::set_receipts_complete(txn, &receipt.receipt.dht_op_hash, true)?;
+    }
+    add_if_unique(txn, receipt)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,6 +142,8 @@ mod tests {
     use holochain_types::dht_op::DhtOp;
     use holochain_types::dht_op::DhtOpHashed;
     use holochain_zome_types::fixt::*;
+    use holochain_zome_types::Timestamp;
+    use holochain_zome_types::ValidationStatus;
 
     async fn fake_vr(
         dht_op_hash: &DhtOpHash,
@@ -172,7 +156,10 @@ mod tests {
             validators: vec![agent],
             when_integrated: Timestamp::now(),
         };
-        receipt.sign(keystore).await.unwrap().unwrap()
+        sign_validation_receipt(receipt, keystore)
+            .await
+            .unwrap()
+            .unwrap()
     }
 
     #[tokio::test(flavor = "multi_thread")]
@@ -11,18 +11,26 @@ pub struct HostFnAccess {
     pub read_workspace_deterministic: Permission,
     /// Can write and workspace
     pub write_workspace: Permission,
+    /// Can schedule and unschedule functions
+    pub scheduling: Permission,
     /// Can write to the network
     pub write_network: Permission,
+    /// Can read from the network, e.g. the peers known to the p2p agent store
+    pub network: Permission,
     /// Can access bindings.
     pub bindings: Permission,
     /// Can access the deterministic bindings.
     pub bindings_deterministic: Permission,
+    /// Can generate cryptographically secure random bytes
+    pub random: Permission,
     /// All other non-deterministic functions
     pub non_determinism: Permission,
     /// Access to functions that use the keystore in the conductor
     pub keystore: Permission,
     /// Access to deterministic keystore functions.
     pub keystore_deterministic: Permission,
+    /// Create new cells for the calling app, e.g. clones of existing ones
+    pub cell_provisioning: Permission,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -42,24 +50,32 @@ impl HostFnAccess {
         read_workspace: Permission,
         read_workspace_deterministic: Permission,
         write_workspace: Permission,
+        scheduling: Permission,
         write_network: Permission,
+        network: Permission,
         bindings: Permission,
         bindings_deterministic: Permission,
+        random: Permission,
         non_determinism: Permission,
         keystore: Permission,
         keystore_deterministic: Permission,
+        cell_provisioning: Permission,
     ) -> Self {
         Self {
             agent_info,
             read_workspace,
             read_workspace_deterministic,
             write_workspace,
+            scheduling,
             write_network,
+            network,
             bindings,
             bindings_deterministic,
+            random,
             non_determinism,
             keystore,
             keystore_deterministic,
+            cell_provisioning,
         }
     }
     /// Allow all access
@@ -68,13 +84,17 @@ impl HostFnAccess {
             read_workspace: Permission::Allow,
             read_workspace_deterministic: Permission::Allow,
             write_workspace: Permission::Allow,
+            scheduling: Permission::Allow,
             agent_info: Permission::Allow,
+            random: Permission::Allow,
             non_determinism: Permission::Allow,
             write_network: Permission::Allow,
+            network: Permission::Allow,
             keystore: Permission::Allow,
             keystore_deterministic: Permission::Allow,
             bindings: Permission::Allow,
             bindings_deterministic: Permission::Allow,
+            cell_provisioning: Permission::Allow,
         }
     }
 
@@ -84,13 +104,17 @@ impl HostFnAccess {
             read_workspace: Permission::Deny,
             read_workspace_deterministic: Permission::Deny,
             write_workspace: Permission::Deny,
+            scheduling: Permission::Deny,
             agent_info: Permission::Deny,
+            random: Permission::Deny,
             non_determinism: Permission::Deny,
             write_network: Permission::Deny,
+            network: Permission::Deny,
             keystore: Permission::Deny,
             keystore_deterministic: Permission::Deny,
             bindings: Permission::Deny,
             bindings_deterministic: Permission::Deny,
+            cell_provisioning: Permission::Deny,
         }
     }
 }
@@ -82,8 +82,8 @@ impl DnaFile {
         })
     }
 
-    /// Construct a DnaFile from its constituent parts
-    #[cfg(feature = "fixturators")]
+    /// Construct a DnaFile from its constituent parts.
+    /// The hashes are trusted, not recomputed.
     pub fn from_parts(dna: DnaDefHashed, code: WasmMap) -> Self {
         Self { dna, code }
     }
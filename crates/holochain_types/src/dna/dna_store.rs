@@ -2,10 +2,14 @@ use crate::prelude::*;
 
 /// A readable and writable store of DnaFiles and EntryDefs
 #[mockall::automock]
-pub trait DnaStore: Default + Send + Sync {
-    /// Add a DnaFile to the store
-    fn add_dna(&mut self, dna: DnaFile);
-    /// Add multiple DnaFiles to the store
+#[async_trait::async_trait]
+pub trait DnaStore: Send + Sync {
+    /// Add a DnaFile to the store, unless [`DnaStore::validate_on_add`] rejects it
+    fn add_dna(&mut self, dna: DnaFile) -> Result<(), DnaError>;
+    /// Check a DnaFile before it is added with [`DnaStore::add_dna`]
+    fn validate_on_add(&self, dna: &DnaFile) -> Result<(), DnaError>;
+    /// Add multiple DnaFiles to the store.
+    /// These are not validated, as they are expected to have been added before.
     fn add_dnas<T: IntoIterator<Item = (DnaHash, DnaFile)> + 'static>(&mut self, dnas: T);
     /// Add an EntryDef to the store
     fn add_entry_def(&mut self, k: EntryDefBufferKey, entry_def: EntryDef);
@@ -23,6 +27,58 @@ pub trait DnaStore: Default + Send + Sync {
     fn get_dna_file(&self, hash: &DnaHash) -> Option<DnaFile>;
     /// Get a particular EntryDef
     fn get_entry_def(&self, k: &EntryDefBufferKey) -> Option<EntryDef>;
+    /// Remove a DnaFile from the store, along with the EntryDefs of its zomes
+    /// which aren't used by any other DNA in the store
+    fn remove_dna(&mut self, hash: &DnaHash);
+    /// Remove all DnaFiles and EntryDefs from the store
+    fn clear(&mut self);
+    /// List all EntryDefs in the store
+    fn list_entry_defs(&self) -> Vec<(EntryDefBufferKey, EntryDef)>;
+    /// Get a particular ZomeDef of a particular DNA
+    fn get_zome_def(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> Option<ZomeDef> {
+        find_zome_def(self.get_dna_def(dna_hash)?, zome_name)
+    }
+    /// Get a particular DnaFile, or fetch and add it to the store if it's missing.
+    /// `fetch` is only called if the DnaFile isn't in the store yet, and returns
+    /// the DnaFile together with the EntryDefs of its zomes, which are added too,
+    /// just as registering a DNA with the conductor adds them.
+    /// A fetched DnaFile which the store rejects is not returned.
+    fn get_or_fetch<F>(&mut self, hash: &DnaHash, fetch: F) -> Option<DnaFile>
+    where
+        F: FnOnce(&DnaHash) -> Option<(DnaFile, Vec<(EntryDefBufferKey, EntryDef)>)> + 'static,
+    {
+        if let Some(dna) = self.get_dna_file(hash) {
+            return Some(dna);
+        }
+        let (dna, entry_defs) = fetch(hash)?;
+        self.add_dna(dna.clone()).ok()?;
+        self.add_entry_defs(entry_defs);
+        Some(dna)
+    }
+    /// Like [`DnaStore::get_or_fetch`] but for a fetch that needs to await.
+    async fn get_or_fetch_async<F, Fut>(&mut self, hash: &DnaHash, fetch: F) -> Option<DnaFile>
+    where
+        F: FnOnce(&DnaHash) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Option<(DnaFile, Vec<(EntryDefBufferKey, EntryDef)>)>>
+            + Send
+            + 'static,
+    {
+        if let Some(dna) = self.get_dna_file(hash) {
+            return Some(dna);
+        }
+        let (dna, entry_defs) = fetch(hash).await?;
+        self.add_dna(dna.clone()).ok()?;
+        self.add_entry_defs(entry_defs);
+        Some(dna)
+    }
+}
+
+fn find_zome_def(dna_def: DnaDef, zome_name: &ZomeName) -> Option<ZomeDef> {
+    dna_def
+        .zomes
+        .into_iter()
+        .find(|(name, _)| name == zome_name)
+        .map(|(_, zome_def)| zome_def)
 }
 
 impl MockDnaStore {
@@ -54,16 +110,61 @@ impl MockDnaStore {
 
         dna_store
     }
+
+    /// Construct a mock which serves several DnaFiles.
+    /// `list`, `get_dna_def`, `get_dna_file` and `get_zome_def`
+    /// can be called any number of times.
+    pub fn multi_dna<T>(dna_files: Vec<DnaFile>, add_dnas_calls: T, add_entry_defs_calls: T) -> Self
+    where
+        T: Into<::mockall::TimesRange>,
+    {
+        let files: std::collections::HashMap<DnaHash, DnaFile> = dna_files
+            .into_iter()
+            .map(|dna_file| (dna_file.dna_hash().clone(), dna_file))
+            .collect();
+        let files = std::sync::Arc::new(files);
+        let mut dna_store = MockDnaStore::new();
+
+        let hashes: Vec<_> = files.keys().cloned().collect();
+        dna_store.expect_list().returning(move || hashes.clone());
+        let f = files.clone();
+        dna_store
+            .expect_get_dna_def()
+            .returning(move |hash| f.get(hash).map(|d| d.dna_def().clone()));
+        let f = files.clone();
+        dna_store
+            .expect_get_dna_file()
+            .returning(move |hash| f.get(hash).cloned());
+        dna_store
+            .expect_get_zome_def()
+            .returning(move |hash, zome_name| {
+                find_zome_def(files.get(hash)?.dna_def().clone(), zome_name)
+            });
+        dna_store
+            .expect_add_dnas::<Vec<_>>()
+            .times(add_dnas_calls)
+            .return_const(());
+        dna_store
+            .expect_add_entry_defs::<Vec<_>>()
+            .times(add_entry_defs_calls)
+            .return_const(());
+
+        dna_store
+    }
 }
 
 /// Read-only access to a DnaStore, and only for DNAs
-pub trait DnaStoreRead: Default + Send + Sync {
+pub trait DnaStoreRead: Send + Sync {
     /// List all DNAs in the store
     fn list(&self) -> Vec<DnaHash>;
     /// Get a particular DnaDef
     fn get_dna_def(&self, hash: &DnaHash) -> Option<DnaDef>;
     /// Get a particular DnaFile
     fn get_dna_file(&self, hash: &DnaHash) -> Option<DnaFile>;
+    /// List all EntryDefs in the store
+    fn list_entry_defs(&self) -> Vec<(EntryDefBufferKey, EntryDef)>;
+    /// Get a particular ZomeDef of a particular DNA
+    fn get_zome_def(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> Option<ZomeDef>;
 }
 
 impl<DS: DnaStore> DnaStoreRead for DS {
@@ -78,6 +179,14 @@ impl<DS: DnaStore> DnaStoreRead for DS {
     fn get_dna_file(&self, hash: &DnaHash) -> Option<DnaFile> {
         DS::get_dna_file(self, hash)
     }
+
+    fn list_entry_defs(&self) -> Vec<(EntryDefBufferKey, EntryDef)> {
+        DS::list_entry_defs(self)
+    }
+
+    fn get_zome_def(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> Option<ZomeDef> {
+        DS::get_zome_def(self, dna_hash, zome_name)
+    }
 }
 
 /// Key for the [EntryDef] buffer
@@ -100,3 +209,67 @@ impl EntryDefBufferKey {
         }
     }
 }
+
+impl From<(ZomeDef, u8)> for EntryDefBufferKey {
+    fn from((zome, entry_def_position): (ZomeDef, u8)) -> Self {
+        Self::new(zome, EntryDefIndex(entry_def_position))
+    }
+}
+
+/// Displays as `zome[position]`, where the zome is shown by its WasmHash,
+/// or by its uuid for an inline zome, since the key doesn't hold a zome name.
+impl std::fmt::Display for EntryDefBufferKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.zome {
+            ZomeDef::Wasm(WasmZome { wasm_hash }) => {
+                write!(f, "{}[{}]", wasm_hash, self.entry_def_position.0)
+            }
+            ZomeDef::Inline(zome) => write!(f, "{}[{}]", zome.uuid(), self.entry_def_position.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wasm_zome(name: u8) -> ZomeDef {
+        ZomeDef::Wasm(WasmZome::new(WasmHash::from_raw_32(vec![name; 32])))
+    }
+
+    fn round_trip(key: &EntryDefBufferKey) -> EntryDefBufferKey {
+        let sb = SerializedBytes::try_from(key.clone()).unwrap();
+        EntryDefBufferKey::try_from(sb).unwrap()
+    }
+
+    #[test]
+    fn ordering_survives_serialization() {
+        // Ordered by zome first, then by position.
+        let keys: Vec<EntryDefBufferKey> = vec![
+            (wasm_zome(0), 0).into(),
+            (wasm_zome(0), 1).into(),
+            (wasm_zome(0), 255).into(),
+            (wasm_zome(1), 0).into(),
+            (wasm_zome(2), 7).into(),
+        ];
+        let round_tripped: Vec<_> = keys.iter().map(round_trip).collect();
+        assert_eq!(keys, round_tripped);
+
+        for (i, a) in round_tripped.iter().enumerate() {
+            for (j, b) in round_tripped.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{} and {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn display_shows_zome_and_position() {
+        let wasm_hash = WasmHash::from_raw_32(vec![1; 32]);
+        let key: EntryDefBufferKey = (ZomeDef::Wasm(WasmZome::new(wasm_hash.clone())), 3).into();
+        assert_eq!(key.to_string(), format!("{}[3]", wasm_hash));
+
+        let zome = InlineZome::new("inline_zome_uuid", vec![]);
+        let key: EntryDefBufferKey = (ZomeDef::from(zome), 0).into();
+        assert_eq!(key.to_string(), "inline_zome_uuid[0]");
+    }
+}
@@ -56,3 +56,26 @@ impl From<(DnaHash, AgentPubKey)> for CellId {
         Self(pair.0, pair.1)
     }
 }
+
+/// Input to the `create_clone_cell` host function.
+///
+/// Requests a clone of the cell filling the given role of the calling app,
+/// optionally with modified DNA properties. The properties are validated by
+/// the host as YAML properties before the clone is created.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct CreateCloneCellInput {
+    /// The role of the calling app whose DNA should be cloned.
+    pub role_id: String,
+    /// Properties to override on the cloned DNA, if any.
+    pub properties: Option<SerializedBytes>,
+}
+
+impl CreateCloneCellInput {
+    /// Constructor.
+    pub fn new(role_id: String, properties: Option<SerializedBytes>) -> Self {
+        Self {
+            role_id,
+            properties,
+        }
+    }
+}
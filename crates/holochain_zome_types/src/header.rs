@@ -33,8 +33,39 @@ pub enum HeaderError {
     NotNewEntry,
     #[error(transparent)]
     WrongHeaderError(#[from] WrongHeaderError),
-    #[error("{0}")]
-    Rebase(String),
+    #[error("A Dna header is always the first header of a chain, so it can't be rebased")]
+    CannotRebaseDna,
+    #[error("The header has no previous header")]
+    MissingPrevHeader,
+    #[error("The header timestamp is invalid: {reason}")]
+    InvalidTimestamp { reason: String },
+    #[error("Expected a header for an entry of type {expected:?} but it is for {found:?}")]
+    WrongEntryType {
+        expected: EntryType,
+        found: EntryType,
+    },
+    #[error("Expected header sequence number {expected} but found {found}")]
+    InvalidSequenceNumber { expected: u32, found: u32 },
+    #[error("Expected a header authored by {expected} but it was authored by {found}")]
+    AuthorMismatch {
+        expected: AgentPubKey,
+        found: AgentPubKey,
+    },
+    #[error("Expected a header for the entry {expected} but it is for {found}")]
+    EntryHashMismatch {
+        expected: EntryHash,
+        found: EntryHash,
+    },
+    #[error("The header's previous header is not the top of the chain")]
+    ChainTopMismatch,
+}
+
+impl From<crate::timestamp::TimestampError> for HeaderError {
+    fn from(e: crate::timestamp::TimestampError) -> Self {
+        Self::InvalidTimestamp {
+            reason: e.to_string(),
+        }
+    }
 }
 
 #[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
@@ -256,12 +287,11 @@ impl Header {
         new_prev_timestamp: Timestamp,
     ) -> Result<(), HeaderError> {
         let new_seq = new_prev_seq + 1;
-        let new_timestamp = self.timestamp().max(
-            (new_prev_timestamp + std::time::Duration::from_nanos(1))
-                .map_err(|e| HeaderError::Rebase(e.to_string()))?,
-        );
+        let new_timestamp = self
+            .timestamp()
+            .max((new_prev_timestamp + std::time::Duration::from_micros(1))?);
         match self {
-            Self::Dna(_) => return Err(HeaderError::Rebase("Rebased a DNA Header".to_string())),
+            Self::Dna(_) => return Err(HeaderError::CannotRebaseDna),
             Self::AgentValidationPkg(AgentValidationPkg {
                 timestamp,
                 header_seq,
@@ -324,6 +354,66 @@ impl Header {
         Ok(())
     }
 
+    /// Check that this header can be the next header after `prev_header`,
+    /// which has the hash `prev_header_hash`.
+    pub fn check_follows(
+        &self,
+        prev_header_hash: &HeaderHash,
+        prev_header: &Header,
+    ) -> Result<(), HeaderError> {
+        match self.prev_header() {
+            None => return Err(HeaderError::MissingPrevHeader),
+            Some(hash) if hash != prev_header_hash => return Err(HeaderError::ChainTopMismatch),
+            Some(_) => (),
+        }
+        if self.author() != prev_header.author() {
+            return Err(HeaderError::AuthorMismatch {
+                expected: prev_header.author().clone(),
+                found: self.author().clone(),
+            });
+        }
+        let expected = prev_header.header_seq() + 1;
+        if self.header_seq() != expected {
+            return Err(HeaderError::InvalidSequenceNumber {
+                expected,
+                found: self.header_seq(),
+            });
+        }
+        if self.timestamp() <= prev_header.timestamp() {
+            return Err(HeaderError::InvalidTimestamp {
+                reason: format!(
+                    "{} is not after the previous header's {}",
+                    self.timestamp(),
+                    prev_header.timestamp()
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check that this header creates or updates to an entry
+    /// of `entry_type` with the hash `entry_hash`.
+    pub fn check_entry(
+        &self,
+        entry_hash: &EntryHash,
+        entry_type: &EntryType,
+    ) -> Result<(), HeaderError> {
+        let (found_hash, found_type) = self.entry_data().ok_or(HeaderError::NotNewEntry)?;
+        if found_type != entry_type {
+            return Err(HeaderError::WrongEntryType {
+                expected: entry_type.clone(),
+                found: found_type.clone(),
+            });
+        }
+        if found_hash != entry_hash {
+            return Err(HeaderError::EntryHashMismatch {
+                expected: entry_hash.clone(),
+                found: found_hash.clone(),
+            });
+        }
+        Ok(())
+    }
+
     /// returns the sequence ordinal of this header
     pub fn header_seq(&self) -> u32 {
         match self {
@@ -728,3 +818,130 @@ impl rusqlite::ToSql for ZomeId {
         Ok(self.0.into())
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "fixturators")]
+mod tests {
+    use super::*;
+    use crate::fixt::*;
+    use ::fixt::prelude::*;
+
+    #[test]
+    fn rebase_errors() {
+        let mut dna = Header::Dna(fixt!(Dna));
+        assert!(matches!(
+            dna.rebase_on(fixt!(HeaderHash), 0, Timestamp::now()),
+            Err(HeaderError::CannotRebaseDna)
+        ));
+
+        let mut create = Header::Create(fixt!(Create));
+        let prev_header = fixt!(HeaderHash);
+        create
+            .rebase_on(prev_header.clone(), 5, Timestamp::now())
+            .unwrap();
+        assert_eq!(create.prev_header(), Some(&prev_header));
+        assert_eq!(create.header_seq(), 6);
+
+        // Rebasing after a later header moves the timestamp strictly past it.
+        let later = (create.timestamp() + std::time::Duration::from_secs(1)).unwrap();
+        create.rebase_on(fixt!(HeaderHash), 6, later).unwrap();
+        assert!(create.timestamp() > later);
+
+        let mut create = Header::Create(fixt!(Create));
+        assert!(matches!(
+            create.rebase_on(fixt!(HeaderHash), 5, Timestamp::MAX),
+            Err(HeaderError::InvalidTimestamp { .. })
+        ));
+    }
+
+    /// A header following `prev`, by the same author.
+    fn next_create(prev_hash: &HeaderHash, prev: &Header) -> Create {
+        let mut create = fixt!(Create);
+        create.author = prev.author().clone();
+        create.header_seq = prev.header_seq() + 1;
+        create.prev_header = prev_hash.clone();
+        create.timestamp = (prev.timestamp() + std::time::Duration::from_secs(1)).unwrap();
+        create
+    }
+
+    #[test]
+    fn check_follows_errors() {
+        let prev = Header::Create(fixt!(Create));
+        let prev_hash = fixt!(HeaderHash);
+        let next = next_create(&prev_hash, &prev);
+        Header::Create(next.clone())
+            .check_follows(&prev_hash, &prev)
+            .unwrap();
+
+        assert!(matches!(
+            Header::Dna(fixt!(Dna)).check_follows(&prev_hash, &prev),
+            Err(HeaderError::MissingPrevHeader)
+        ));
+        assert!(matches!(
+            Header::Create(next.clone()).check_follows(&fixt!(HeaderHash), &prev),
+            Err(HeaderError::ChainTopMismatch)
+        ));
+
+        let mut other_author = next.clone();
+        other_author.author = fixt!(AgentPubKey);
+        match Header::Create(other_author.clone()).check_follows(&prev_hash, &prev) {
+            Err(HeaderError::AuthorMismatch { expected, found }) => {
+                assert_eq!(&expected, prev.author());
+                assert_eq!(found, other_author.author);
+            }
+            r => panic!("Expected an author mismatch but got {:?}", r),
+        }
+
+        let mut skipped_seq = next.clone();
+        skipped_seq.header_seq += 1;
+        match Header::Create(skipped_seq).check_follows(&prev_hash, &prev) {
+            Err(HeaderError::InvalidSequenceNumber { expected, found }) => {
+                assert_eq!(expected, prev.header_seq() + 1);
+                assert_eq!(found, prev.header_seq() + 2);
+            }
+            r => panic!("Expected an invalid sequence number but got {:?}", r),
+        }
+
+        let mut same_time = next;
+        same_time.timestamp = prev.timestamp();
+        assert!(matches!(
+            Header::Create(same_time).check_follows(&prev_hash, &prev),
+            Err(HeaderError::InvalidTimestamp { .. })
+        ));
+    }
+
+    #[test]
+    fn check_entry_errors() {
+        let create = fixt!(Create);
+        let header = Header::Create(create.clone());
+        header
+            .check_entry(&create.entry_hash, &create.entry_type)
+            .unwrap();
+
+        let other_type = match create.entry_type {
+            EntryType::AgentPubKey => EntryType::CapClaim,
+            _ => EntryType::AgentPubKey,
+        };
+        match header.check_entry(&create.entry_hash, &other_type) {
+            Err(HeaderError::WrongEntryType { expected, found }) => {
+                assert_eq!(expected, other_type);
+                assert_eq!(found, create.entry_type);
+            }
+            r => panic!("Expected a wrong entry type but got {:?}", r),
+        }
+
+        let other_hash = fixt!(EntryHash);
+        match header.check_entry(&other_hash, &create.entry_type) {
+            Err(HeaderError::EntryHashMismatch { expected, found }) => {
+                assert_eq!(expected, other_hash);
+                assert_eq!(found, create.entry_hash);
+            }
+            r => panic!("Expected an entry hash mismatch but got {:?}", r),
+        }
+
+        assert!(matches!(
+            Header::Dna(fixt!(Dna)).check_entry(&create.entry_hash, &create.entry_type),
+            Err(HeaderError::NotNewEntry)
+        ));
+    }
+}
@@ -57,6 +57,12 @@ impl Default for ChainQueryFilterRange {
     }
 }
 
+impl From<std::ops::RangeInclusive<u32>> for ChainQueryFilterRange {
+    fn from(range: std::ops::RangeInclusive<u32>) -> Self {
+        Self::HeaderSeqRange(*range.start(), *range.end())
+    }
+}
+
 /// Query arguments
 #[derive(
     serde::Serialize, serde::Deserialize, SerializedBytes, Default, PartialEq, Clone, Debug,
@@ -69,14 +75,62 @@ pub struct ChainQueryFilter {
     // NB: if this filter is set, you can't verify the results, so don't
     //     use this in validation
     pub entry_type: Option<EntryType>,
+    /// Filter by the id of an app entry type, e.g. "post".
+    /// The host functions that take a query filter resolve this against the
+    /// entry defs of the calling zome, so it can't be combined with `entry_type`.
+    /// The source chain itself rejects a filter where this is still set.
+    // NB: if this filter is set, you can't verify the results, so don't
+    //     use this in validation
+    #[serde(default)]
+    pub entry_type_str: Option<String>,
     /// Filter by a list of `EntryHash`.
     pub entry_hashes: Option<HashSet<EntryHash>>,
     /// Filter by HeaderType
     // NB: if this filter is set, you can't verify the results, so don't
     //     use this in validation
     pub header_type: Option<HeaderType>,
+    /// Filter by any of several HeaderTypes.
+    /// This can be combined with `header_type`, in which case both must match.
+    // NB: if this filter is set, you can't verify the results, so don't
+    //     use this in validation
+    #[serde(default)]
+    pub header_types: Option<Vec<HeaderType>>,
     /// Include the entries in the elements
     pub include_entries: bool,
+    /// Only read the headers, even if `include_entries` is set.
+    /// This is the cheapest query when only header metadata such as
+    /// timestamps, sequence numbers and types is needed.
+    #[serde(default)]
+    pub headers_only: bool,
+    /// Skip this many matching elements, for paging through large chains.
+    #[serde(default)]
+    pub offset: Option<u32>,
+    /// Return at most this many matching elements.
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// Input to the `query_after` host function.
+#[derive(serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq, Clone, Debug)]
+pub struct QueryAfterInput {
+    /// The query to run. Its offset and limit are ignored.
+    pub filter: ChainQueryFilter,
+    /// Only return elements after the element with this header on the chain.
+    /// Start from genesis if this is `None`.
+    pub after_hash: Option<HeaderHash>,
+    /// Return at most this many elements.
+    pub limit: u32,
+}
+
+impl QueryAfterInput {
+    /// Constructor.
+    pub fn new(filter: ChainQueryFilter, after_hash: Option<HeaderHash>, limit: u32) -> Self {
+        Self {
+            filter,
+            after_hash,
+            limit,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -176,6 +230,32 @@ impl ChainQueryFilter {
         }
     }
 
+    /// Start building a ChainQueryFilter which is checked for
+    /// contradictory settings when it's built.
+    ///
+    /// ```
+    /// use holochain_zome_types::header::EntryType;
+    /// use holochain_zome_types::query::ChainQueryFilter;
+    ///
+    /// let filter = ChainQueryFilter::builder()
+    ///     .sequence_range(10..=20)
+    ///     .entry_type(EntryType::AgentPubKey)
+    ///     .limit(5)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(filter.limit, Some(5));
+    ///
+    /// // An offset past the end of the range can never match anything.
+    /// assert!(ChainQueryFilter::builder()
+    ///     .sequence_range(10..=20)
+    ///     .offset(11)
+    ///     .build()
+    ///     .is_err());
+    /// ```
+    pub fn builder() -> ChainQueryFilterBuilder {
+        ChainQueryFilterBuilder::default()
+    }
+
     /// Filter on sequence range.
     pub fn sequence_range(mut self, sequence_range: ChainQueryFilterRange) -> Self {
         self.sequence_range = sequence_range;
@@ -188,6 +268,12 @@ impl ChainQueryFilter {
         self
     }
 
+    /// Filter on the id of an app entry type defined by the calling zome.
+    pub fn entry_type_str<S: Into<String>>(mut self, entry_type_str: S) -> Self {
+        self.entry_type_str = Some(entry_type_str.into());
+        self
+    }
+
     /// Filter on entry hashes.
     pub fn entry_hashes(mut self, entry_hashes: HashSet<EntryHash>) -> Self {
         self.entry_hashes = Some(entry_hashes);
@@ -200,12 +286,36 @@ impl ChainQueryFilter {
         self
     }
 
+    /// Filter on any of several header types.
+    pub fn header_types(mut self, header_types: Vec<HeaderType>) -> Self {
+        self.header_types = Some(header_types);
+        self
+    }
+
     /// Include the entries in the ElementsVec that is returned.
     pub fn include_entries(mut self, include_entries: bool) -> Self {
         self.include_entries = include_entries;
         self
     }
 
+    /// Only read the headers of the elements that are returned.
+    pub fn headers_only(mut self, headers_only: bool) -> Self {
+        self.headers_only = headers_only;
+        self
+    }
+
+    /// Skip this many matching elements.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Return at most this many matching elements.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
     /// If the sequence range supports fork disambiguation, apply it to remove
     /// headers that are not in the correct branch.
     /// Numerical range bounds do NOT support fork disambiguation, and neither
@@ -272,6 +382,11 @@ impl ChainQueryFilter {
                     .as_ref()
                     .map(|header_type| header.header_type() == *header_type)
                     .unwrap_or(true)
+                    && self
+                        .header_types
+                        .as_ref()
+                        .map(|header_types| header_types.contains(&header.header_type()))
+                        .unwrap_or(true)
                     && self
                         .entry_type
                         .as_ref()
@@ -306,12 +421,143 @@ impl ChainQueryFilter {
             .filter(|element| header_hashset.contains(element.header_address()))
             .collect()
     }
+
+    /// Apply the offset and limit of the query to already filtered elements.
+    pub fn paginate(&self, elements: Vec<Element>) -> Vec<Element> {
+        elements
+            .into_iter()
+            .skip(self.offset.unwrap_or(0) as usize)
+            .take(self.limit.map_or(usize::MAX, |limit| limit as usize))
+            .collect()
+    }
+}
+
+/// A ChainQueryFilter that can't be built because its settings contradict
+/// each other or can never match anything.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ChainQueryFilterError {
+    /// The sequence range starts after it ends.
+    #[error("The sequence range {0}..={1} starts after it ends")]
+    EmptySequenceRange(u32, u32),
+    /// The offset skips every element in the sequence range.
+    #[error("The offset {offset} skips all {len} elements of the sequence range")]
+    OffsetPastSequenceRange {
+        /// The offset of the query.
+        offset: u32,
+        /// The number of elements in the sequence range.
+        len: u32,
+    },
+    /// A limit of zero never returns anything.
+    #[error("The limit is zero")]
+    ZeroLimit,
+    /// The entry type and the entry type id can't both be used.
+    #[error("Both entry_type and entry_type_str are set")]
+    EntryTypeAndEntryTypeStr,
+}
+
+/// Builds a [`ChainQueryFilter`] one setting at a time.
+/// See [`ChainQueryFilter::builder`].
+#[derive(Default, Debug, Clone)]
+pub struct ChainQueryFilterBuilder(ChainQueryFilter);
+
+impl ChainQueryFilterBuilder {
+    /// Filter on sequence range.
+    pub fn sequence_range<R: Into<ChainQueryFilterRange>>(
+        &mut self,
+        sequence_range: R,
+    ) -> &mut Self {
+        self.0.sequence_range = sequence_range.into();
+        self
+    }
+
+    /// Filter on entry type.
+    pub fn entry_type(&mut self, entry_type: EntryType) -> &mut Self {
+        self.0.entry_type = Some(entry_type);
+        self
+    }
+
+    /// Filter on the id of an app entry type defined by the calling zome.
+    pub fn entry_type_str<S: Into<String>>(&mut self, entry_type_str: S) -> &mut Self {
+        self.0.entry_type_str = Some(entry_type_str.into());
+        self
+    }
+
+    /// Filter on entry hashes.
+    pub fn entry_hashes(&mut self, entry_hashes: HashSet<EntryHash>) -> &mut Self {
+        self.0.entry_hashes = Some(entry_hashes);
+        self
+    }
+
+    /// Filter on header type.
+    pub fn header_type(&mut self, header_type: HeaderType) -> &mut Self {
+        self.0.header_type = Some(header_type);
+        self
+    }
+
+    /// Filter on any of several header types.
+    pub fn header_types(&mut self, header_types: Vec<HeaderType>) -> &mut Self {
+        self.0.header_types = Some(header_types);
+        self
+    }
+
+    /// Include the entries in the elements that are returned.
+    pub fn include_entries(&mut self, include_entries: bool) -> &mut Self {
+        self.0.include_entries = include_entries;
+        self
+    }
+
+    /// Only read the headers of the elements that are returned.
+    pub fn headers_only(&mut self, headers_only: bool) -> &mut Self {
+        self.0.headers_only = headers_only;
+        self
+    }
+
+    /// Skip this many matching elements.
+    pub fn offset(&mut self, offset: u32) -> &mut Self {
+        self.0.offset = Some(offset);
+        self
+    }
+
+    /// Return at most this many matching elements.
+    pub fn limit(&mut self, limit: u32) -> &mut Self {
+        self.0.limit = Some(limit);
+        self
+    }
+
+    /// Check the settings and build the ChainQueryFilter.
+    pub fn build(&self) -> Result<ChainQueryFilter, ChainQueryFilterError> {
+        let filter = &self.0;
+        if filter.entry_type.is_some() && filter.entry_type_str.is_some() {
+            return Err(ChainQueryFilterError::EntryTypeAndEntryTypeStr);
+        }
+        if filter.limit == Some(0) {
+            return Err(ChainQueryFilterError::ZeroLimit);
+        }
+        if let ChainQueryFilterRange::HeaderSeqRange(start, end) = filter.sequence_range {
+            if start > end {
+                return Err(ChainQueryFilterError::EmptySequenceRange(start, end));
+            }
+            // Compare against the last index rather than the length,
+            // which overflows for the full range `0..=u32::MAX`.
+            match filter.offset {
+                Some(offset) if offset > end - start => {
+                    return Err(ChainQueryFilterError::OffsetPastSequenceRange {
+                        offset,
+                        len: end - start + 1,
+                    })
+                }
+                _ => (),
+            }
+        }
+        Ok(filter.clone())
+    }
 }
 
 #[cfg(test)]
 #[cfg(feature = "fixturators")]
 mod tests {
     use super::ChainQueryFilter;
+    use super::ChainQueryFilterError;
     use crate::fixt::AppEntryTypeFixturator;
     use crate::fixt::*;
     use crate::header::EntryType;
@@ -444,6 +690,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn filter_by_header_types() {
+        let headers = fixtures();
+
+        let query_1 = ChainQueryFilter::new()
+            .header_types(vec![headers[0].header_type(), headers[1].header_type()]);
+        let query_2 = ChainQueryFilter::new().header_types(vec![headers[2].header_type()]);
+        let query_3 = ChainQueryFilter::new().header_types(vec![]);
+        // Both header filters must match.
+        let query_4 = ChainQueryFilter::new()
+            .header_type(headers[0].header_type())
+            .header_types(vec![headers[1].header_type(), headers[2].header_type()]);
+
+        assert_eq!(
+            map_query(&query_1, &headers),
+            [true, true, false, true, true, true, false].to_vec()
+        );
+        assert_eq!(
+            map_query(&query_2, &headers),
+            [false, false, true, false, false, false, true].to_vec()
+        );
+        assert_eq!(map_query(&query_3, &headers), [false; 7].to_vec());
+        assert_eq!(map_query(&query_4, &headers), [false; 7].to_vec());
+    }
+
     #[test]
     fn filter_by_chain_sequence() {
         let headers = fixtures();
@@ -560,4 +831,71 @@ mod tests {
             [true, false, false, false, true, true, false].to_vec()
         );
     }
+
+    #[test]
+    fn builder_rejects_contradictory_filters() {
+        assert_eq!(
+            ChainQueryFilter::builder()
+                .entry_type(EntryType::AgentPubKey)
+                .entry_type_str("post")
+                .build(),
+            Err(ChainQueryFilterError::EntryTypeAndEntryTypeStr)
+        );
+        assert_eq!(
+            ChainQueryFilter::builder()
+                .sequence_range(ChainQueryFilterRange::HeaderSeqRange(5, 4))
+                .build(),
+            Err(ChainQueryFilterError::EmptySequenceRange(5, 4))
+        );
+        assert_eq!(
+            ChainQueryFilter::builder().limit(0).build(),
+            Err(ChainQueryFilterError::ZeroLimit)
+        );
+        assert_eq!(
+            ChainQueryFilter::builder()
+                .sequence_range(0..=2)
+                .offset(3)
+                .build(),
+            Err(ChainQueryFilterError::OffsetPastSequenceRange { offset: 3, len: 3 })
+        );
+
+        let filter = ChainQueryFilter::builder()
+            .sequence_range(0..=2)
+            .offset(2)
+            .limit(1)
+            .include_entries(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            filter,
+            ChainQueryFilter::new()
+                .sequence_range(ChainQueryFilterRange::HeaderSeqRange(0, 2))
+                .offset(2)
+                .limit(1)
+                .include_entries(true)
+        );
+    }
+
+    #[test]
+    fn builder_accepts_full_sequence_range() {
+        let filter = ChainQueryFilter::builder()
+            .sequence_range(0..=u32::MAX)
+            .offset(u32::MAX)
+            .build()
+            .unwrap();
+        assert_eq!(
+            filter.sequence_range,
+            ChainQueryFilterRange::HeaderSeqRange(0, u32::MAX)
+        );
+        assert_eq!(
+            ChainQueryFilter::builder()
+                .sequence_range(1..=u32::MAX)
+                .offset(u32::MAX)
+                .build(),
+            Err(ChainQueryFilterError::OffsetPastSequenceRange {
+                offset: u32::MAX,
+                len: u32::MAX,
+            })
+        );
+    }
 }
@@ -1,6 +1,9 @@
 use crate::element::Element;
 use crate::CallbackResult;
+use crate::Timestamp;
+use holo_hash::AgentPubKey;
 use holo_hash::AnyDhtHash;
+use holo_hash::DhtOpHash;
 use holochain_serialized_bytes::prelude::*;
 use holochain_wasmer_common::WasmError;
 
@@ -24,6 +27,33 @@ pub enum ValidationStatus {
     Abandoned = 2,
 }
 
+/// Validation receipt content - to be signed.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    SerializedBytes,
+)]
+pub struct ValidationReceipt {
+    /// the op this validation receipt is for.
+    pub dht_op_hash: DhtOpHash,
+
+    /// the result of this validation.
+    pub validation_status: ValidationStatus,
+
+    /// the remote validator which is signing this receipt.
+    pub validators: Vec<AgentPubKey>,
+
+    /// Time when the op was integrated
+    pub when_integrated: Timestamp,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
 pub enum ValidateCallbackResult {
     Valid,
@@ -112,6 +112,9 @@ wasm_io_types! {
 
     fn create_x25519_keypair(()) -> zt::x_salsa20_poly1305::x25519::X25519PubKey;
 
+    // Clone a cell of the calling app, returning the CellId of the new clone.
+    fn create_clone_cell (zt::cell::CreateCloneCellInput) -> zt::cell::CellId;
+
     // The debug host import takes a TraceMsg to output wherever the host wants to display it.
     // TraceMsg includes line numbers. so the wasm tells the host about it's own code structure.
     fn trace (zt::trace::TraceMsg) -> ();
@@ -128,6 +131,12 @@ wasm_io_types! {
 
     fn get_agent_activity (zt::agent_activity::GetAgentActivityInput) -> zt::query::AgentActivity;
 
+    // The validation receipts received for one of this agent's published ops.
+    fn get_validation_receipts (holo_hash::DhtOpHash) -> Vec<zt::validate::ValidationReceipt>;
+
+    // The agents known to the p2p agent store for a dna, defaulting to the current one.
+    fn get_peers (Option<holo_hash::DnaHash>) -> Vec<holo_hash::AgentPubKey>;
+
     fn get_details (Vec<zt::entry::GetInput>) -> Vec<Option<zt::metadata::Details>>;
 
     fn get_link_details (Vec<zt::link::GetLinksInput>) -> Vec<zt::link::LinkDetails>;
@@ -153,6 +162,12 @@ wasm_io_types! {
     // Query the source chain for data.
     fn query (zt::query::ChainQueryFilter) -> Vec<crate::Element>;
 
+    // Count the elements a source chain query would return, ignoring paging.
+    fn query_count (zt::query::ChainQueryFilter) -> u32;
+
+    // Query the source chain for the elements after a header.
+    fn query_after (zt::query::QueryAfterInput) -> Vec<crate::Element>;
+
     // the length of random bytes to create
     fn random_bytes (u32) -> zt::bytes::Bytes;
 
@@ -165,6 +180,9 @@ wasm_io_types! {
     // Schedule a schedulable function if it is not already.
     fn schedule (String) -> ();
 
+    // Unschedule a scheduled function so it is not dispatched again.
+    fn unschedule (String) -> ();
+
     // @todo
     fn sleep (core::time::Duration) -> ();
 
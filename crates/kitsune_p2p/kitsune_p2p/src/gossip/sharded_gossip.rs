@@ -7,6 +7,7 @@ use crate::gossip::{decode_bloom_filter, encode_bloom_filter};
 use crate::types::event::*;
 use crate::types::gossip::*;
 use crate::types::*;
+use futures::future::{BoxFuture, FutureExt};
 use ghost_actor::dependencies::tracing;
 use governor::clock::DefaultClock;
 use governor::state::{InMemoryState, NotKeyed};
@@ -28,6 +29,7 @@ use tokio::time::Instant;
 
 pub use self::bandwidth::BandwidthThrottle;
 use self::ops::OpsBatchQueue;
+use self::round_throttle::RoundThrottle;
 use self::state_map::RoundStateMap;
 use crate::metrics::MetricsSync;
 
@@ -49,6 +51,7 @@ mod store;
 
 mod bandwidth;
 mod next_target;
+mod round_throttle;
 
 // dead_code and unused_imports are allowed here because when compiling this
 // code path due to test_utils, the helper functions defined in this module
@@ -173,13 +176,15 @@ impl ShardedGossip {
 
             async move {
                 let mut stats = Stats::reset();
+                let mut round_throttle =
+                    RoundThrottle::new(this.gossip.tuning_params.gossip_max_rounds_per_second);
                 while !this
                     .gossip
                     .closing
                     .load(std::sync::atomic::Ordering::Relaxed)
                 {
                     tokio::time::sleep(std::time::Duration::from_millis(10)).await;
-                    this.run_one_iteration().await;
+                    this.run_one_iteration(&mut round_throttle).await;
                     this.stats(&mut stats);
                 }
                 KitsuneResult::Ok(())
@@ -207,6 +212,12 @@ impl ShardedGossip {
                 }
             }
         };
+        if let ShardedGossipWire::MissingOps(MissingOps { ops, .. }) = &gossip {
+            self.gossip.inner.share_ref(|i| {
+                i.metrics.write().record_ops_sent(ops.len());
+                Ok(())
+            })?;
+        }
         let gossip = gossip.encode_vec().map_err(KitsuneError::other)?;
         let bytes = gossip.len();
         let gossip = wire::Wire::gossip(
@@ -272,8 +283,8 @@ impl ShardedGossip {
         Ok(())
     }
 
-    async fn run_one_iteration(&self) {
-        match self.gossip.try_initiate().await {
+    async fn run_one_iteration(&self, round_throttle: &mut RoundThrottle) {
+        match self.gossip.try_initiate_throttled(round_throttle).await {
             Ok(Some(outgoing)) => {
                 if let Err(err) = self.inner.share_mut(|i, _| {
                     i.outgoing.push_back(outgoing);
@@ -362,6 +373,48 @@ pub(crate) struct ShardedGossipTarget {
     pub(crate) when_initiated: Option<tokio::time::Instant>,
     #[allow(dead_code)]
     pub(crate) url: TxUrl,
+    /// Set if this round was asked for with [`AsGossipModule::trigger_gossip`].
+    pub(crate) forced: Option<ForcedRound>,
+}
+
+/// A round we have been asked to initiate with a remote agent.
+/// The requester is told when the round completes, and if the
+/// round is dropped before that it is told the round failed.
+#[derive(Debug)]
+pub(crate) struct ForcedRound {
+    pub(crate) agent: Arc<KitsuneAgent>,
+    done: Option<tokio::sync::oneshot::Sender<KitsuneResult<()>>>,
+}
+
+impl ForcedRound {
+    fn new(agent: Arc<KitsuneAgent>) -> (Self, tokio::sync::oneshot::Receiver<KitsuneResult<()>>) {
+        let (done, rx) = tokio::sync::oneshot::channel();
+        (
+            Self {
+                agent,
+                done: Some(done),
+            },
+            rx,
+        )
+    }
+
+    /// Tell the requester how the round ended.
+    pub(crate) fn finish(mut self, result: KitsuneResult<()>) {
+        if let Some(done) = self.done.take() {
+            let _ = done.send(result);
+        }
+    }
+}
+
+impl Drop for ForcedRound {
+    fn drop(&mut self) {
+        if let Some(done) = self.done.take() {
+            let _ = done.send(Err(KitsuneError::other(format!(
+                "The forced gossip round with {:?} ended before it completed",
+                self.agent
+            ))));
+        }
+    }
 }
 
 /// The internal mutable state for [`ShardedGossipLocal`]
@@ -371,6 +424,9 @@ pub struct ShardedGossipLocalState {
     local_agents: HashSet<Arc<KitsuneAgent>>,
     /// If Some, we are in the process of trying to initiate gossip with this target.
     initiate_tgt: Option<ShardedGossipTarget>,
+    /// Rounds to initiate next, ahead of the usual target selection.
+    /// They wait here while another round is being initiated.
+    forced_tgts: VecDeque<ForcedRound>,
     round_map: RoundStateMap,
     /// Metrics that track remote node states and help guide
     /// the next node to gossip with.
@@ -393,7 +449,10 @@ impl ShardedGossipLocalState {
             .map(|tgt| &tgt.cert == state_key)
             .unwrap_or(false);
         let remote_agent_list = if init_tgt {
-            let initiate_tgt = self.initiate_tgt.take().unwrap();
+            let mut initiate_tgt = self.initiate_tgt.take().unwrap();
+            if let (Some(forced), false) = (initiate_tgt.forced.take(), error) {
+                forced.finish(Ok(()));
+            }
             initiate_tgt.remote_agent_list
         } else {
             vec![]
@@ -551,7 +610,7 @@ impl ShardedGossipLocal {
                 .map(|tgt| &tgt.cert == id)
                 .unwrap_or(false)
             {
-                let initiate_tgt = i.initiate_tgt.take().unwrap();
+                let mut initiate_tgt = i.initiate_tgt.take().unwrap();
                 if error {
                     i.metrics
                         .write()
@@ -560,12 +619,31 @@ impl ShardedGossipLocal {
                     i.metrics
                         .write()
                         .record_success(&initiate_tgt.remote_agent_list);
+                    if let Some(forced) = initiate_tgt.forced.take() {
+                        forced.finish(Ok(()));
+                    }
                 }
             }
             Ok(())
         })
     }
 
+    /// Put the forced round of the current target back at the front of the
+    /// queue, for when the target's round is replaced by one the remote initiated.
+    fn requeue_forced_target(&self, id: &StateKey) -> KitsuneResult<()> {
+        self.inner.share_mut(|i, _| {
+            if let Some(forced) = i
+                .initiate_tgt
+                .as_mut()
+                .filter(|tgt| &tgt.cert == id)
+                .and_then(|tgt| tgt.forced.take())
+            {
+                i.forced_tgts.push_front(forced);
+            }
+            Ok(())
+        })
+    }
+
     /// If the round is still active then update the state.
     fn update_state_if_active(&self, key: StateKey, state: RoundState) -> KitsuneResult<()> {
         self.inner.share_mut(|i, _| {
@@ -717,14 +795,22 @@ impl ShardedGossipLocal {
             ShardedGossipWire::MissingOps(MissingOps { ops, finished }) => {
                 let mut gossip = Vec::with_capacity(0);
                 let finished = MissingOpsStatus::try_from(finished)?;
-                let state = match finished {
+                // Store the ops before the round can be finished below,
+                // so a finished round has stored everything it received.
+                if !ops.is_empty() && self.get_state(&cert)?.is_some() {
+                    self.inner.share_ref(|i| {
+                        i.metrics.write().record_ops_received(ops.len());
+                        Ok(())
+                    })?;
+                    self.incoming_missing_ops(ops).await?;
+                }
+                match finished {
                     // This is a single chunk of ops. No need to reply.
-                    MissingOpsStatus::ChunkComplete => self.get_state(&cert)?,
+                    MissingOpsStatus::ChunkComplete => (),
                     // This is the last chunk in the batch. Reply with [`OpsBatchReceived`]
                     // to get the next batch of missing ops.
                     MissingOpsStatus::BatchComplete => {
                         gossip = vec![ShardedGossipWire::ops_batch_received()];
-                        self.get_state(&cert)?
                     }
                     // All the batches of missing ops for the bloom this node sent
                     // to the remote node have been sent back to this node.
@@ -746,11 +832,7 @@ impl ShardedGossipLocal {
                             // Update the state.
                             self.update_state_if_active(cert.clone(), state.clone())?;
                         }
-                        state
                     }
-                };
-                if state.is_some() && !ops.is_empty() {
-                    self.incoming_missing_ops(ops).await?;
                 }
                 gossip
             }
@@ -760,6 +842,9 @@ impl ShardedGossipLocal {
                 Vec::with_capacity(0)
             }
             ShardedGossipWire::AlreadyInProgress(_) => {
+                // The remote's round with us goes ahead instead of ours,
+                // so any forced round still needs to be initiated.
+                self.requeue_forced_target(&cert)?;
                 self.remove_target(&cert, false)?;
                 Vec::with_capacity(0)
             }
@@ -1061,6 +1146,47 @@ impl AsGossipModule for ShardedGossip {
             Ok(())
         });
     }
+
+    fn trigger_gossip(
+        &self,
+        to_agent: Option<Arc<KitsuneAgent>>,
+    ) -> BoxFuture<'static, KitsuneResult<()>> {
+        let evt_sender = self.gossip.evt_sender.clone();
+        let space = self.gossip.space.clone();
+        let inner = self.gossip.inner.clone();
+        async move {
+            let agents = match to_agent {
+                Some(agent) => vec![agent],
+                None => {
+                    let local_agents = inner.share_mut(|i, _| Ok(i.local_agents.clone()))?;
+                    store::all_agent_info(&evt_sender, &space)
+                        .await?
+                        .into_iter()
+                        .map(|info| info.agent.clone())
+                        .filter(|agent| !local_agents.contains(agent))
+                        .collect()
+                }
+            };
+            let rounds: Vec<_> = inner.share_mut(move |i, _| {
+                let rounds = agents
+                    .into_iter()
+                    .map(|agent| {
+                        let (round, done) = ForcedRound::new(agent);
+                        i.forced_tgts.push_back(round);
+                        done
+                    })
+                    .collect();
+                let s = tracing::trace_span!("gossip_trigger", agents = ?i.show_local_agents(), msg = "Forced gossip");
+                s.in_scope(|| i.log_state());
+                Ok(rounds)
+            })?;
+            for done in rounds {
+                done.await.map_err(KitsuneError::other)??;
+            }
+            Ok(())
+        }
+        .boxed()
+    }
 }
 
 struct ShardedRecentGossipFactory {
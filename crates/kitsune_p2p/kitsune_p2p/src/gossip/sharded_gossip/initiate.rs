@@ -3,6 +3,22 @@ use rand::Rng;
 use super::*;
 
 impl ShardedGossipLocal {
+    /// Try to initiate gossip if the throttle allows a new round.
+    /// Only a round that is actually initiated counts towards the throttle.
+    pub(super) async fn try_initiate_throttled(
+        &self,
+        round_throttle: &mut RoundThrottle,
+    ) -> KitsuneResult<Option<Outgoing>> {
+        if !round_throttle.is_ready() {
+            return Ok(None);
+        }
+        let initiated = self.try_initiate().await?;
+        if initiated.is_some() {
+            round_throttle.initiated();
+        }
+        Ok(initiated)
+    }
+
     /// Try to initiate gossip if we don't currently
     /// have an outgoing gossip.
     pub(super) async fn try_initiate(&self) -> KitsuneResult<Option<Outgoing>> {
@@ -28,10 +44,19 @@ impl ShardedGossipLocal {
         // Get the local agents intervals.
         let intervals = store::local_arcs(&self.evt_sender, &self.space, &local_agents).await?;
 
-        // Choose a remote agent to gossip with.
-        let remote_agent = self
-            .find_remote_agent_within_arcset(Arc::new(intervals.clone().into()), &local_agents)
-            .await?;
+        // Choose a remote agent to gossip with,
+        // preferring any agent we have been asked to gossip with.
+        let (forced, remote_agent) = match self.next_forced_target().await? {
+            Some((forced, node)) => (Some(forced), Some(node)),
+            None => (
+                None,
+                self.find_remote_agent_within_arcset(
+                    Arc::new(intervals.clone().into()),
+                    &local_agents,
+                )
+                .await?,
+            ),
+        };
 
         let id = rand::thread_rng().gen();
 
@@ -59,6 +84,7 @@ impl ShardedGossipLocal {
                         tie_break: id,
                         when_initiated: Some(Instant::now()),
                         url: url.clone(),
+                        forced,
                     };
 
                     inner.initiate_tgt = Some(tgt);
@@ -72,6 +98,22 @@ impl ShardedGossipLocal {
         Ok(maybe_gossip)
     }
 
+    /// Take the next round we have been asked to initiate, along with
+    /// the node to initiate it with. Rounds with agents that we have no
+    /// address for are failed.
+    async fn next_forced_target(&self) -> KitsuneResult<Option<(ForcedRound, next_target::Node)>> {
+        while let Some(forced) = self.inner.share_mut(|i, _| Ok(i.forced_tgts.pop_front()))? {
+            match self.find_remote_agent(&forced.agent).await? {
+                Some(node) => return Ok(Some((forced, node))),
+                None => {
+                    let msg = format!("No address to gossip with {:?}", forced.agent);
+                    forced.finish(Err(KitsuneError::other(msg)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
     /// Receiving an incoming initiate.
     /// - Send back the accept, agent bloom and ops bloom gossip messages.
     /// - Only send the agent bloom if this is a recent gossip type.
@@ -111,6 +153,8 @@ impl ShardedGossipLocal {
             if our_id >= remote_id {
                 return Ok(Vec::with_capacity(0));
             } else {
+                // Initiate a forced round again once the remote's round is over.
+                self.requeue_forced_target(&peer_cert)?;
                 self.inner.share_mut(|i, _| {
                     i.initiate_tgt = None;
                     Ok(())
@@ -44,22 +44,7 @@ impl ShardedGossipLocal {
             .filter(|a| !a.storage_arc.interval().is_empty())
         {
             // Get an address if there is one.
-            let info = info
-                .url_list
-                .iter()
-                .filter_map(|url| {
-                    kitsune_p2p_proxy::ProxyUrl::from_full(url.as_str())
-                        .map_err(|e| tracing::error!("Failed to parse url {:?}", e))
-                        .ok()
-                        .map(|purl| {
-                            (
-                                info.clone(),
-                                Tx2Cert::from(purl.digest()),
-                                TxUrl::from(url.as_str()),
-                            )
-                        })
-                })
-                .next();
+            let info = first_address(&info).map(|(cert, url)| (info, cert, url));
 
             // dbg!(&info);
 
@@ -95,6 +80,38 @@ impl ShardedGossipLocal {
     }
 }
 
+impl ShardedGossipLocal {
+    /// Find the remote node of a specific agent.
+    pub(super) async fn find_remote_agent(
+        &self,
+        agent: &Arc<KitsuneAgent>,
+    ) -> KitsuneResult<Option<Node>> {
+        let agents = std::iter::once(agent.clone()).collect();
+        let node = store::query_agent_info(&self.evt_sender, &self.space, &agents)
+            .await?
+            .into_iter()
+            .filter(|info| info.agent == *agent)
+            .find_map(|info| {
+                first_address(&info).map(|(cert, url)| Node {
+                    agent_info_list: vec![info],
+                    cert,
+                    url,
+                })
+            });
+        Ok(node)
+    }
+}
+
+/// Get the first address in an agent info we can connect to.
+fn first_address(info: &AgentInfoSigned) -> Option<(Tx2Cert, TxUrl)> {
+    info.url_list.iter().find_map(|url| {
+        kitsune_p2p_proxy::ProxyUrl::from_full(url.as_str())
+            .map_err(|e| tracing::error!("Failed to parse url {:?}", e))
+            .ok()
+            .map(|purl| (Tx2Cert::from(purl.digest()), TxUrl::from(url.as_str())))
+    })
+}
+
 /// Find the next remote node to sync with.
 fn next_remote_node(
     mut remote_nodes: Vec<Node>,
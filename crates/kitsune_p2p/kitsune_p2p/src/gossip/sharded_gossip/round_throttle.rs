@@ -0,0 +1,79 @@
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Limits how often a gossip loop may initiate a new round.
+/// Only rounds that are actually initiated count towards the limit,
+/// so attempts that find nothing to initiate don't hold back the next round.
+pub(super) struct RoundThrottle {
+    /// The minimum time between initiated rounds, if there is a limit.
+    period: Option<Duration>,
+    /// When the next round may be initiated.
+    next_round: Option<Instant>,
+}
+
+impl RoundThrottle {
+    /// Allow at most this many rounds per second.
+    /// Zero, or anything that isn't a positive finite number, is unlimited.
+    pub(super) fn new(max_rounds_per_second: f64) -> Self {
+        let period = (max_rounds_per_second.is_finite() && max_rounds_per_second > 0.0)
+            .then(|| Duration::from_secs_f64(1.0 / max_rounds_per_second))
+            .filter(|period| !period.is_zero());
+        Self {
+            period,
+            next_round: None,
+        }
+    }
+
+    /// Check if a round may be initiated now.
+    /// This is always true until the first round is initiated.
+    pub(super) fn is_ready(&self) -> bool {
+        match self.next_round {
+            Some(next_round) => Instant::now() >= next_round,
+            None => true,
+        }
+    }
+
+    /// Record that a round was initiated.
+    pub(super) fn initiated(&mut self) {
+        if let Some(period) = self.period {
+            self.next_round = Some(Instant::now() + period);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Count the rounds initiated when trying every 10ms for the given time.
+    async fn count_rounds(throttle: &mut RoundThrottle, time: Duration) -> usize {
+        let step = Duration::from_millis(10);
+        let mut count = 0;
+        for _ in 0..(time.as_millis() / step.as_millis()) {
+            if throttle.is_ready() {
+                throttle.initiated();
+                count += 1;
+            }
+            tokio::time::advance(step).await;
+        }
+        count
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn limits_rounds_per_second() {
+        let mut throttle = RoundThrottle::new(1.0);
+        let count = count_rounds(&mut throttle, Duration::from_secs(3)).await;
+        // One round straight away and one per second after that.
+        assert!(count <= 4, "{} rounds were initiated in 3s", count);
+        assert!(count >= 3, "only {} rounds were initiated in 3s", count);
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn zero_is_unlimited() {
+        for max_rounds_per_second in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let mut throttle = RoundThrottle::new(max_rounds_per_second);
+            let count = count_rounds(&mut throttle, Duration::from_secs(1)).await;
+            assert_eq!(count, 100);
+        }
+    }
+}
@@ -679,3 +679,155 @@ async fn initiate_times_out() {
         })
         .unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+/// A forced round waits for the round in progress,
+/// then is initiated with the requested agent.
+async fn forced_round_waits_for_current_round() {
+    let agents = agents_with_infos(3).await;
+    let alice = setup_empty_player(
+        ShardedGossipLocalState {
+            local_agents: maplit::hashset!(agents[0].0.clone()),
+            ..Default::default()
+        },
+        agents.clone(),
+    )
+    .await;
+
+    // - Alice initiates a round with bob or carol.
+    let (first_cert, _, _) = alice.try_initiate().await.unwrap().unwrap();
+    // - The remote agent which isn't in that round.
+    let (forced_agent, forced_info) = if first_cert == cert_from_info(agents[1].1.clone()) {
+        agents[2].clone()
+    } else {
+        agents[1].clone()
+    };
+    let forced_cert = cert_from_info(forced_info);
+
+    // - A round with the other agent is forced while the first is in progress.
+    let (forced, mut done) = ForcedRound::new(forced_agent);
+    alice
+        .inner
+        .share_mut(|i, _| {
+            i.forced_tgts.push_back(forced);
+            Ok(())
+        })
+        .unwrap();
+    assert!(alice.try_initiate().await.unwrap().is_none());
+    assert!(done.try_recv().is_err());
+
+    // - Once the first round is over the forced round is initiated.
+    alice.remove_target(&first_cert, false).unwrap();
+    let (cert, _, _) = alice.try_initiate().await.unwrap().unwrap();
+    assert_eq!(cert, forced_cert);
+    assert!(done.try_recv().is_err());
+
+    // - The requester is told once the forced round completes.
+    alice.remove_target(&cert, false).unwrap();
+    assert!(done.await.unwrap().is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+/// A forced round with an agent we have no address for fails
+/// instead of being dropped.
+async fn forced_round_without_address_fails() {
+    let agents = agents_with_infos(2).await;
+    let alice = setup_empty_player(
+        ShardedGossipLocalState {
+            local_agents: maplit::hashset!(agents[0].0.clone()),
+            ..Default::default()
+        },
+        agents.clone(),
+    )
+    .await;
+
+    let (forced, done) = ForcedRound::new(Arc::new(fixt!(KitsuneAgent)));
+    alice
+        .inner
+        .share_mut(|i, _| {
+            i.forced_tgts.push_back(forced);
+            Ok(())
+        })
+        .unwrap();
+
+    // - Alice falls back to the usual target.
+    let (cert, _, _) = alice.try_initiate().await.unwrap().unwrap();
+    assert_eq!(cert, cert_from_info(agents[1].1.clone()));
+    assert!(done.await.unwrap().is_err());
+}
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+/// Test `gossip_max_rounds_per_second` limits the rounds that are initiated
+/// and attempts that can't initiate a round don't count towards the limit.
+async fn max_rounds_per_second_limits_rounds() {
+    let agents = agents_with_infos(2).await;
+    let mut alice = setup_empty_player(
+        ShardedGossipLocalState {
+            local_agents: maplit::hashset!(agents[0].0.clone()),
+            ..Default::default()
+        },
+        agents.clone(),
+    )
+    .await;
+    let mut tuning_params = tuning_params_struct::KitsuneP2pTuningParams::default();
+    tuning_params.gossip_max_rounds_per_second = 1.0;
+    alice.tuning_params = Arc::new(tuning_params);
+    let mut round_throttle = RoundThrottle::new(alice.tuning_params.gossip_max_rounds_per_second);
+
+    let step = std::time::Duration::from_millis(10);
+    let finish_round = |alice: &ShardedGossipLocal| {
+        alice
+            .inner
+            .share_mut(|i, _| {
+                i.initiate_tgt = None;
+                Ok(())
+            })
+            .unwrap()
+    };
+
+    // - The first round is initiated straight away.
+    let r = alice
+        .try_initiate_throttled(&mut round_throttle)
+        .await
+        .unwrap();
+    assert!(r.is_some());
+
+    // - Nothing is initiated while that round is in progress.
+    for _ in 0..150 {
+        let r = alice
+            .try_initiate_throttled(&mut round_throttle)
+            .await
+            .unwrap();
+        assert!(r.is_none());
+        tokio::time::advance(step).await;
+    }
+
+    // - Once it's finished the next round is initiated straight away
+    // because those attempts didn't use up the throttle.
+    finish_round(&alice);
+    let r = alice
+        .try_initiate_throttled(&mut round_throttle)
+        .await
+        .unwrap();
+    assert!(r.is_some());
+
+    // - Rounds that finish straight away are still only initiated once a second.
+    let mut rounds = 0;
+    for _ in 0..300 {
+        finish_round(&alice);
+        if alice
+            .try_initiate_throttled(&mut round_throttle)
+            .await
+            .unwrap()
+            .is_some()
+        {
+            rounds += 1;
+        }
+        tokio::time::advance(step).await;
+    }
+    assert!(
+        (2..=3).contains(&rounds),
+        "{} rounds were initiated in 3s",
+        rounds
+    );
+}
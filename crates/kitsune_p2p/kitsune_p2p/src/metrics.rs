@@ -124,6 +124,21 @@ struct NodeInfo {
     current_round: bool,
 }
 
+/// Totals of gossip activity for a space.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GossipMetrics {
+    /// Rounds we initiated that the remote accepted.
+    pub rounds_initiated: u64,
+    /// Rounds initiated by a remote that we accepted.
+    pub rounds_accepted: u64,
+    /// Ops we sent to remotes.
+    pub ops_sent: u64,
+    /// Ops we received from remotes.
+    pub ops_received: u64,
+    /// How long the last successful round took.
+    pub last_round_duration: std::time::Duration,
+}
+
 #[derive(Debug, Default)]
 /// Metrics tracking for remote nodes to help
 /// choose which remote node to initiate the next round with.
@@ -137,6 +152,9 @@ pub struct Metrics {
     // Number of times we need to force initiate
     // the next round.
     force_initiates: u8,
+
+    /// Totals of gossip activity.
+    gossip: GossipMetrics,
 }
 
 /// Outcome of a gossip round.
@@ -237,9 +255,25 @@ impl Metrics {
         serde_json::json!({
             "aggExtrapCov": *self.agg_extrap_cov,
             "agents": agents,
+            "gossip": self.gossip,
         })
     }
 
+    /// Totals of gossip activity.
+    pub fn gossip(&self) -> &GossipMetrics {
+        &self.gossip
+    }
+
+    /// Record ops we sent to a remote.
+    pub fn record_ops_sent(&mut self, count: usize) {
+        self.gossip.ops_sent += count as u64;
+    }
+
+    /// Record ops we received from a remote.
+    pub fn record_ops_received(&mut self, count: usize) {
+        self.gossip.ops_received += count as u64;
+    }
+
     /// Record an individual extrapolated coverage event
     /// (either from us or a remote)
     /// and add it to our running aggregate extrapolated coverage metric.
@@ -303,6 +337,7 @@ impl Metrics {
             record_instant(&mut info.initiates);
             info.current_round = true;
         }
+        self.gossip.rounds_initiated += 1;
     }
 
     /// Record a remote gossip round has started.
@@ -319,6 +354,7 @@ impl Metrics {
             record_instant(&mut info.remote_rounds);
             info.current_round = true;
         }
+        self.gossip.rounds_accepted += 1;
     }
 
     /// Record a gossip round has completed successfully.
@@ -335,6 +371,9 @@ impl Metrics {
                 .entry(agent_info.into().agent().clone())
                 .or_default();
             info.reachability_quotient.push(100);
+            if let Some(started) = info.round_start() {
+                self.gossip.last_round_duration = started.elapsed();
+            }
             record_instant(&mut info.complete_rounds);
             info.current_round = false;
             if info.is_initiate_round() {
@@ -471,6 +510,16 @@ impl NodeInfo {
             (Some(remote), Some(initiate)) => initiate > remote,
         }
     }
+
+    /// When the last round with this node started.
+    fn round_start(&self) -> Option<Instant> {
+        self.remote_rounds
+            .back()
+            .into_iter()
+            .chain(self.initiates.back())
+            .max()
+            .copied()
+    }
 }
 
 fn record_instant(buffer: &mut VecDeque<Instant>) {
@@ -658,4 +707,25 @@ mod tests {
         a5.push_n(1, 255);
         assert_eq!(1.0, *a5);
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_gossip_metrics() {
+        let alice = Arc::new(KitsuneAgent::new(vec![1; 36]));
+        let bob = Arc::new(KitsuneAgent::new(vec![2; 36]));
+        let mut metrics = Metrics::default();
+
+        metrics.record_initiate(&[alice.clone()]);
+        metrics.record_ops_sent(3);
+        metrics.record_success(&[alice]);
+        metrics.record_remote_round(&[bob.clone()]);
+        metrics.record_ops_received(2);
+        metrics.record_error(&[bob]);
+
+        let gossip = metrics.gossip();
+        assert_eq!(gossip.rounds_initiated, 1);
+        assert_eq!(gossip.rounds_accepted, 1);
+        assert_eq!(gossip.ops_sent, 3);
+        assert_eq!(gossip.ops_received, 2);
+        assert_eq!(metrics.dump()["gossip"]["ops_sent"], 3);
+    }
 }
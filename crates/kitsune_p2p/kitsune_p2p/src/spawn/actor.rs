@@ -859,6 +859,40 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         .into())
     }
 
+    fn handle_trigger_gossip(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+        to_agent: Option<Arc<KitsuneAgent>>,
+    ) -> KitsuneP2pHandlerResult<()> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(space)),
+            Some(space) => space.get(),
+        };
+        Ok(async move {
+            let (space_sender, _) = space_sender.await;
+            space_sender.trigger_gossip(space, to_agent).await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
+    fn handle_gossip_metrics(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+    ) -> KitsuneP2pHandlerResult<crate::metrics::GossipMetrics> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(space)),
+            Some(space) => space.get(),
+        };
+        Ok(async move {
+            let (space_sender, _) = space_sender.await;
+            space_sender.gossip_metrics(space).await
+        }
+        .boxed()
+        .into())
+    }
+
     fn handle_authority_for_hash(
         &mut self,
         space: Arc<KitsuneSpace>,
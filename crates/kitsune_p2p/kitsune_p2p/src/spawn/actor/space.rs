@@ -1018,6 +1018,26 @@ impl KitsuneP2pHandler for Space {
         unit_ok_fut()
     }
 
+    fn handle_trigger_gossip(
+        &mut self,
+        _: KSpace,
+        to_agent: Option<KAgent>,
+    ) -> InternalHandlerResult<()> {
+        let rounds: Vec<_> = self
+            .gossip_mod
+            .values()
+            .map(|module| module.trigger_gossip(to_agent.clone()))
+            .collect();
+        Ok(async move {
+            for result in futures::future::join_all(rounds).await {
+                result?;
+            }
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     fn handle_authority_for_hash(
         &mut self,
         _space: Arc<KitsuneSpace>,
@@ -1046,6 +1066,14 @@ impl KitsuneP2pHandler for Space {
         .boxed()
         .into())
     }
+
+    fn handle_gossip_metrics(
+        &mut self,
+        _space: Arc<KitsuneSpace>,
+    ) -> KitsuneP2pHandlerResult<crate::metrics::GossipMetrics> {
+        let metrics = self.ro_inner.metrics.read().gossip().clone();
+        Ok(async move { Ok(metrics) }.boxed().into())
+    }
 }
 
 pub(crate) struct SpaceReadOnlyInner {
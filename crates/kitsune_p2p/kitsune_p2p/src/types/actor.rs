@@ -130,6 +130,13 @@ ghost_actor::ghost_chan! {
         /// New data has been integrated and is ready for gossiping.
         fn new_integrated_data(space: KSpace) -> ();
 
+        /// Initiate gossip with `to_agent` as soon as possible
+        /// instead of waiting for it to be chosen.
+        /// If `to_agent` is `None` gossip with every known remote agent.
+        /// Resolves once the rounds have completed, or with an error if
+        /// there is no address for an agent.
+        fn trigger_gossip(space: KSpace, to_agent: Option<KAgent>) -> ();
+
         /// Check if an agent is an authority for a hash.
         fn authority_for_hash(
             space: KSpace,
@@ -140,5 +147,8 @@ ghost_actor::ghost_chan! {
         fn dump_network_metrics(
             space: KSpaceOpt,
         ) -> serde_json::Value;
+
+        /// Get the totals of gossip activity for a space.
+        fn gossip_metrics(space: KSpace) -> crate::metrics::GossipMetrics;
     }
 }
@@ -1,5 +1,6 @@
 use crate::metrics::*;
 use crate::types::*;
+use futures::future::{BoxFuture, FutureExt};
 use kitsune_p2p_types::config::*;
 use kitsune_p2p_types::tx2::tx2_api::*;
 use kitsune_p2p_types::tx2::tx2_utils::TxUrl;
@@ -29,6 +30,12 @@ pub trait AsGossipModule: 'static + Send + Sync {
     fn local_agent_join(&self, a: Arc<KitsuneAgent>);
     fn local_agent_leave(&self, a: Arc<KitsuneAgent>);
     fn new_integrated_data(&self) {}
+    fn trigger_gossip(
+        &self,
+        _to_agent: Option<Arc<KitsuneAgent>>,
+    ) -> BoxFuture<'static, KitsuneResult<()>> {
+        async { Ok(()) }.boxed()
+    }
 }
 
 #[derive(Clone)]
@@ -60,6 +67,16 @@ impl GossipModule {
     pub fn new_integrated_data(&self) {
         self.0.new_integrated_data();
     }
+
+    /// Initiate a round with `to_agent` as soon as possible,
+    /// or with every known remote agent if `None`.
+    /// Resolves once the rounds have completed.
+    pub fn trigger_gossip(
+        &self,
+        to_agent: Option<Arc<KitsuneAgent>>,
+    ) -> BoxFuture<'static, KitsuneResult<()>> {
+        self.0.trigger_gossip(to_agent)
+    }
 }
 
 impl std::fmt::Debug for GossipModule {
@@ -116,6 +116,10 @@ pub mod tuning_params_struct {
         /// be too long. [Default: 1 minutes]
         gossip_local_sync_delay_ms: u32 = 1000 * 60,
 
+        /// The most gossip rounds each gossip loop will initiate
+        /// per second. Zero means unlimited. [Default: 0.0]
+        gossip_max_rounds_per_second: f64 = 0.0,
+
         /// Should gossip dynamically resize storage arcs?
         gossip_dynamic_arcs: bool = true,
 
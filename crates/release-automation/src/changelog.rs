@@ -287,6 +287,41 @@ where
             .flatten())
     }
 
+    /// Returns the releases from `from` up to `to`, both inclusive, in the order they appear in the changelog.
+    ///
+    /// Releases are ordered by their position in the changelog, newest first, so `to` must not be listed below `from`.
+    /// Titles are matched without their brackets.
+    pub(crate) fn releases_between(&'a self, from: &str, to: &str) -> Fallible<Vec<ReleaseChange>> {
+        let releases = self
+            .changes_filtered(|change| matches!(change, ChangeT::Release(_)))?
+            .into_iter()
+            .filter_map(Into::<Option<ReleaseChange>>::into)
+            .collect::<Vec<_>>();
+
+        let position = |title: &str| {
+            releases
+                .iter()
+                .position(|release| normalize_heading_name(release.title()) == title)
+                .ok_or_else(|| anyhow::anyhow!("release '{}' not found in the changelog", title))
+        };
+        let (from_pos, to_pos) = (position(from)?, position(to)?);
+        if to_pos > from_pos {
+            bail!(
+                "the range '{}..{}' is reversed: '{}' is an older release than '{}'",
+                from,
+                to,
+                to,
+                from
+            );
+        }
+
+        Ok(releases
+            .into_iter()
+            .skip(to_pos)
+            .take(from_pos - to_pos + 1)
+            .collect())
+    }
+
     /// Find and parse the frontmatter of this crate's changelog file.
     pub(crate) fn front_matter(&'a self) -> Fallible<Option<Frontmatter>> {
         for (i, node) in self.root()?.children().enumerate() {
@@ -1359,4 +1394,112 @@ mod tests {
             changes
         );
     }
+
+    #[test]
+    fn find_workspace_releases_between() {
+        let workspace_mocker = example_workspace_1().unwrap();
+        workspace_mocker.add_or_replace_file(
+            "CHANGELOG.md",
+            indoc::indoc! {r#"
+            # Changelog
+
+            # [Unreleased]
+
+            # [20210401.100000]
+
+            ## [crate_a-0.0.3](crates/crate_a/CHANGELOG.md#0.0.3)
+
+            # [20210315.100000]
+
+            ## [crate_a-0.0.2](crates/crate_a/CHANGELOG.md#0.0.2)
+            ## [crate_b-0.0.1](crates/crate_b/CHANGELOG.md#0.0.1)
+
+            # [20210304.120604]
+
+            ## [hdk-0.0.100](crates/hdk/CHANGELOG.md#0.0.100)
+            "#,
+            },
+        );
+
+        let changelog_path = workspace_mocker.root().join("CHANGELOG.md");
+        let changelog = ChangelogT::<WorkspaceChangelog>::at_path(&changelog_path);
+        let releases = changelog
+            .releases_between("20210304.120604", "20210315.100000")
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                ReleaseChange::WorkspaceReleaseChange(
+                    "[20210315.100000]".to_string(),
+                    vec!["crate_a-0.0.2".to_string(), "crate_b-0.0.1".to_string()]
+                ),
+                ReleaseChange::WorkspaceReleaseChange(
+                    "[20210304.120604]".to_string(),
+                    vec!["hdk-0.0.100".to_string()]
+                ),
+            ],
+            releases
+        );
+    }
+
+    #[test]
+    fn find_workspace_releases_between_by_position() {
+        let workspace_mocker = example_workspace_1().unwrap();
+        workspace_mocker.add_or_replace_file(
+            "CHANGELOG.md",
+            indoc::indoc! {r#"
+            # Changelog
+
+            # [Unreleased]
+
+            # [0.0.11]
+
+            ## [crate_a-0.0.3](crates/crate_a/CHANGELOG.md#0.0.3)
+
+            # [0.0.10]
+
+            ## [crate_a-0.0.2](crates/crate_a/CHANGELOG.md#0.0.2)
+
+            # [0.0.9]
+
+            ## [crate_b-0.0.1](crates/crate_b/CHANGELOG.md#0.0.1)
+
+            # [0.0.8]
+
+            ## [hdk-0.0.100](crates/hdk/CHANGELOG.md#0.0.100)
+            "#,
+            },
+        );
+
+        let changelog_path = workspace_mocker.root().join("CHANGELOG.md");
+        let changelog = ChangelogT::<WorkspaceChangelog>::at_path(&changelog_path);
+
+        // "0.0.10" sorts before "0.0.9" as a string, but comes after it in the changelog
+        let releases = changelog.releases_between("0.0.9", "0.0.10").unwrap();
+        assert_eq!(
+            vec![
+                ReleaseChange::WorkspaceReleaseChange(
+                    "[0.0.10]".to_string(),
+                    vec!["crate_a-0.0.2".to_string()]
+                ),
+                ReleaseChange::WorkspaceReleaseChange(
+                    "[0.0.9]".to_string(),
+                    vec!["crate_b-0.0.1".to_string()]
+                ),
+            ],
+            releases
+        );
+
+        let err = changelog
+            .releases_between("0.0.10", "0.0.9")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("is reversed"), "{}", err);
+
+        let err = changelog
+            .releases_between("0.0.9", "0.0.12")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not found in the changelog"), "{}", err);
+    }
 }
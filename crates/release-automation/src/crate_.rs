@@ -4,10 +4,11 @@ use cargo::util::VersionExt;
 use log::{debug, info, warn};
 use semver::Version;
 use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use structopt::StructOpt;
 
 use crate::{
-    crate_selection::Crate,
+    crate_selection::{aliases::CargoDepKind, Crate},
     release::{crates_index_helper, ReleaseWorkspace},
     CommandResult, Fallible,
 };
@@ -29,6 +30,67 @@ pub(crate) struct CrateSetVersionArgs {
 
 pub(crate) static DEFAULT_DEV_SUFFIX: &str = "dev.0";
 
+/// Determines which component of a version is incremented.
+#[derive(enum_utils::FromStr, Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum BumpKind {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl BumpKind {
+    /// Increments the version according to the bump kind.
+    pub(crate) fn apply(&self, v: &mut semver::Version) {
+        match self {
+            BumpKind::Patch => increment_patch(v),
+            BumpKind::Minor => increment_minor(v),
+            BumpKind::Major => increment_major(v),
+        }
+    }
+}
+
+/// Determines how the status of a command is reported.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// Parses an input string to an output format.
+pub(crate) fn parse_output_format(input: &str) -> Fallible<OutputFormat> {
+    match input.to_lowercase().as_str() {
+        "text" => Ok(OutputFormat::Text),
+        "json" => Ok(OutputFormat::Json),
+        invalid => bail!("invalid output format: {}", invalid),
+    }
+}
+
+/// A version change that was applied to a crate.
+#[derive(serde::Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CrateBump {
+    pub(crate) name: String,
+    pub(crate) old_version: String,
+    pub(crate) new_version: String,
+}
+
+/// Machine readable summary of the version changes applied by a command.
+///
+/// If `dry_run` is set the changes are a preview and haven't been written.
+#[derive(serde::Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ReleaseStatusReport {
+    pub(crate) crates_bumped: Vec<CrateBump>,
+    pub(crate) dry_run: bool,
+}
+
+impl ReleaseStatusReport {
+    /// Writes the report as JSON to stdout.
+    pub(crate) fn print_json(&self) -> Fallible<()> {
+        println!("{}", serde_json::to_string_pretty(self)?);
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, StructOpt)]
 pub(crate) struct CrateApplyDevVersionsArgs {
     #[structopt(long, default_value = DEFAULT_DEV_SUFFIX)]
@@ -42,6 +104,23 @@ pub(crate) struct CrateApplyDevVersionsArgs {
 
     #[structopt(long)]
     pub(crate) no_verify: bool,
+
+    /// The version component to increment before applying the dev suffix.
+    /// Valid values are: Patch, Minor, Major
+    #[structopt(long, default_value = "Patch", parse(try_from_str = parse_bump_kind))]
+    pub(crate) bump_kind: BumpKind,
+
+    /// Valid values are: text, json
+    #[structopt(long, default_value = "text", parse(try_from_str = parse_output_format))]
+    pub(crate) output_format: OutputFormat,
+}
+
+/// Parses an input string to a bump kind.
+pub(crate) fn parse_bump_kind(input: &str) -> Fallible<BumpKind> {
+    use std::str::FromStr;
+
+    BumpKind::from_str(input)
+        .map_err(|_| anyhow::anyhow!("could not parse '{}' as BumpKind", input))
 }
 
 #[derive(Debug)]
@@ -49,11 +128,20 @@ pub(crate) enum FixupReleases {
     Latest,
     All,
     Selected(Vec<String>),
+    /// The releases between the two given tags, both inclusive.
+    Range(String, String),
 }
 
 /// Parses an input string to an ordered set of release steps.
 pub(crate) fn parse_fixup_releases(input: &str) -> Fallible<FixupReleases> {
-    use std::str::FromStr;
+    if let Some((from, to)) = input.split_once("..") {
+        let (from, to) = (from.trim(), to.trim());
+        if from.is_empty() || to.is_empty() {
+            bail!("expected a range of the form 'from..to', got '{}'", input);
+        }
+
+        return Ok(FixupReleases::Range(from.to_string(), to.to_string()));
+    }
 
     let words = input
         .split(',')
@@ -88,17 +176,119 @@ pub(crate) struct CrateFixupReleases {
 
     #[structopt(long)]
     pub(crate) no_verify: bool,
+
+    /// Valid values are: text, json
+    #[structopt(long, default_value = "text", parse(try_from_str = parse_output_format))]
+    pub(crate) output_format: OutputFormat,
+
+    /// Crates for which the publish state couldn't be determined are appended to this file.
+    /// These can be processed later on using the `retry-failed` subcommand.
+    #[structopt(long)]
+    pub(crate) retry_file: Option<PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+pub(crate) struct CrateRetryFailedArgs {
+    /// The file that was previously passed via `--retry-file` to `fixup-releases`.
+    #[structopt(long)]
+    pub(crate) retry_file: PathBuf,
+
+    #[structopt(long, default_value = DEFAULT_DEV_SUFFIX)]
+    pub(crate) dev_suffix: String,
+
+    #[structopt(long)]
+    pub(crate) dry_run: bool,
+
+    #[structopt(long)]
+    pub(crate) commit: bool,
+
+    #[structopt(long)]
+    pub(crate) no_verify: bool,
+
+    /// Valid values are: text, json
+    #[structopt(long, default_value = "text", parse(try_from_str = parse_output_format))]
+    pub(crate) output_format: OutputFormat,
+}
+
+/// A crate version for which the fixup failed.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RetryEntry {
+    pub(crate) name: String,
+    pub(crate) version: String,
+}
+
+/// The human-editable TOML file that keeps track of failed fixup attempts.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct RetryFile {
+    #[serde(default)]
+    pub(crate) entry: Vec<RetryEntry>,
+}
+
+impl RetryFile {
+    /// Reads the retry file at the given path, which is considered empty if it doesn't exist.
+    pub(crate) fn load(path: &Path) -> Fallible<Self> {
+        if !path.exists() {
+            return Ok(Default::default());
+        }
+
+        let content =
+            std::fs::read_to_string(path).context(format!("reading retry file at {:?}", path))?;
+
+        toml::from_str(&content).context(format!("parsing retry file at {:?}", path))
+    }
+
+    /// Writes the retry file to the given path.
+    pub(crate) fn store(&self, path: &Path) -> Fallible<()> {
+        std::fs::write(path, toml::to_string(self)?)
+            .context(format!("writing retry file at {:?}", path))?;
+
+        Ok(())
+    }
+
+    /// Appends the given entries to the retry file at the given path.
+    pub(crate) fn append(path: &Path, entries: Vec<RetryEntry>) -> Fallible<()> {
+        let mut retry_file = Self::load(path)?;
+        retry_file.entry.extend(entries);
+        retry_file.store(path)
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub(crate) struct CrateBumpAllPatchArgs {
+    #[structopt(long)]
+    pub(crate) dry_run: bool,
+
+    /// Valid values are: text, json
+    #[structopt(long, default_value = "text", parse(try_from_str = parse_output_format))]
+    pub(crate) output_format: OutputFormat,
 }
 
 #[derive(Debug, StructOpt)]
 pub(crate) struct CrateCheckArgs {
+    /// Skips the check of the workspace-local dependencies against the crates index and runs `cargo check` offline.
+    #[structopt(long)]
+    offline: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub(crate) struct CrateListUnpublishedArgs {
+    /// Don't query the crates index and report all crates as unpublished.
     #[structopt(long)]
     offline: bool,
+
+    /// Print the crates as a JSON array instead of one per line.
+    #[structopt(long)]
+    json: bool,
 }
 
 pub(crate) const MINIMUM_CRATE_OWNERS: &str =
     "github:holochain:core-dev,holochain-release-automation,holochain-release-automation2,zippy,steveej";
 
+pub(crate) const DEFAULT_OWNER_LISTING_PARALLELISM: usize = 4;
+
+static DEFAULT_OWNER_LISTING_PARALLELISM_ARG: once_cell::sync::Lazy<String> =
+    once_cell::sync::Lazy::new(|| DEFAULT_OWNER_LISTING_PARALLELISM.to_string());
+
 #[derive(Debug, StructOpt)]
 pub(crate) struct EnsureCrateOwnersArgs {
     #[structopt(long)]
@@ -113,6 +303,10 @@ pub(crate) struct EnsureCrateOwnersArgs {
 
     )]
     minimum_crate_owners: Vec<String>,
+
+    /// The maximum number of concurrently running `cargo owner --list` processes.
+    #[structopt(long, default_value = DEFAULT_OWNER_LISTING_PARALLELISM_ARG.as_str())]
+    parallelism: usize,
 }
 
 #[derive(Debug, StructOpt)]
@@ -125,6 +319,15 @@ pub(crate) enum CrateCommands {
 
     Check(CrateCheckArgs),
     EnsureCrateOwners(EnsureCrateOwnersArgs),
+
+    /// list the workspace crates whose current version isn't published yet.
+    ListUnpublished(CrateListUnpublishedArgs),
+
+    /// re-run the fixup for the crates that were written to a retry file by `fixup-releases`.
+    RetryFailed(CrateRetryFailedArgs),
+
+    /// increment the patch version of every workspace crate that doesn't have a pre-release version.
+    BumpAllPatch(CrateBumpAllPatchArgs),
 }
 
 pub(crate) fn cmd(args: &crate::cli::Args, cmd_args: &CrateArgs) -> CommandResult {
@@ -146,9 +349,11 @@ pub(crate) fn cmd(args: &crate::cli::Args, cmd_args: &CrateArgs) -> CommandResul
         CrateCommands::ApplyDevVersions(subcmd_args) => apply_dev_versions(
             &ws,
             &subcmd_args.dev_suffix,
+            subcmd_args.bump_kind,
             subcmd_args.dry_run,
             subcmd_args.commit,
             subcmd_args.no_verify,
+            subcmd_args.output_format,
         ),
 
         CrateCommands::FixupReleases(subcmd_args) => fixup_releases(
@@ -158,9 +363,24 @@ pub(crate) fn cmd(args: &crate::cli::Args, cmd_args: &CrateArgs) -> CommandResul
             subcmd_args.dry_run,
             subcmd_args.commit,
             subcmd_args.no_verify,
+            subcmd_args.output_format,
+            subcmd_args.retry_file.as_deref(),
         ),
 
         CrateCommands::Check(subcmd_args) => {
+            if !subcmd_args.offline {
+                let errors = verify_publish_consistency(&ws)?;
+                if !errors.is_empty() {
+                    bail!(
+                        "workspace-local dependencies are inconsistent with the crates index: {}",
+                        errors
+                            .iter()
+                            .map(|error| format!("\n- {}", error))
+                            .collect::<String>()
+                    );
+                }
+            }
+
             ws.cargo_check(subcmd_args.offline, std::iter::empty::<&str>())?;
 
             Ok(())
@@ -176,11 +396,207 @@ pub(crate) fn cmd(args: &crate::cli::Args, cmd_args: &CrateArgs) -> CommandResul
                     .map(|s| s.as_str())
                     .collect::<Vec<_>>()
                     .as_slice(),
+                subcmd_args.parallelism,
             )?;
 
             Ok(())
         }
+        CrateCommands::ListUnpublished(subcmd_args) => {
+            let unpublished_crates = list_unpublished_crates(&ws, subcmd_args.offline)?;
+
+            if subcmd_args.json {
+                println!(
+                    "{}",
+                    serde_json::to_string(&unpublished_crates_json(&unpublished_crates))?
+                );
+            } else {
+                for crt in unpublished_crates {
+                    println!("{}", crt.name_version());
+                }
+            }
+
+            Ok(())
+        }
+        CrateCommands::RetryFailed(subcmd_args) => retry_failed(
+            &ws,
+            &subcmd_args.retry_file,
+            &subcmd_args.dev_suffix,
+            subcmd_args.dry_run,
+            subcmd_args.commit,
+            subcmd_args.no_verify,
+            subcmd_args.output_format,
+        ),
+        CrateCommands::BumpAllPatch(subcmd_args) => {
+            let crates_bumped = increment_patch_for_workspace(&ws, subcmd_args.dry_run)?
+                .into_iter()
+                .map(|(name, old_version, new_version)| CrateBump {
+                    name,
+                    old_version: old_version.to_string(),
+                    new_version: new_version.to_string(),
+                })
+                .collect::<Vec<_>>();
+
+            match subcmd_args.output_format {
+                OutputFormat::Json => ReleaseStatusReport {
+                    crates_bumped,
+                    dry_run: subcmd_args.dry_run,
+                }
+                .print_json()?,
+                OutputFormat::Text => {
+                    for bump in crates_bumped {
+                        info!(
+                            "[{}] bumped version {} -> {}",
+                            bump.name, bump.old_version, bump.new_version
+                        );
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Returns the workspace members whose current version is not found on the crates index.
+///
+/// In offline mode the index isn't queried and all members are returned.
+pub(crate) fn list_unpublished_crates<'a>(
+    ws: &'a ReleaseWorkspace<'a>,
+    offline: bool,
+) -> Fallible<Vec<&'a Crate<'a>>> {
+    list_unpublished_crates_with(ws, offline, |crt| {
+        crates_index_helper::is_version_published(crt, false)
+    })
+}
+
+/// Returns the workspace members for which `is_version_published` returns false.
+///
+/// In offline mode `is_version_published` isn't called and all members are returned.
+pub(crate) fn list_unpublished_crates_with<'a, F>(
+    ws: &'a ReleaseWorkspace<'a>,
+    offline: bool,
+    is_version_published: F,
+) -> Fallible<Vec<&'a Crate<'a>>>
+where
+    F: Fn(&Crate) -> Fallible<bool>,
+{
+    let mut unpublished_crates = vec![];
+
+    for crt in ws.members()? {
+        if offline || !is_version_published(crt)? {
+            unpublished_crates.push(*crt);
+        }
+    }
+
+    Ok(unpublished_crates)
+}
+
+/// Formats the given crates as a JSON array of `{"name": .., "version": ..}` objects.
+pub(crate) fn unpublished_crates_json(crates: &[&Crate]) -> serde_json::Value {
+    crates
+        .iter()
+        .map(|crt| {
+            serde_json::json!({
+                "name": crt.name(),
+                "version": crt.version().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// An inconsistency between the workspace-local dependencies of a crate and the crates index.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub(crate) enum ConsistencyError {
+    #[error("{dependent}: no published version of '{dependency}' matches '{required_version}'")]
+    UnpublishedDep {
+        dependent: String,
+        dependency: String,
+        required_version: String,
+    },
+    #[error(
+        "{dep}: the registry resolves to {registry_version} instead of the local {local_version}"
+    )]
+    VersionMismatch {
+        dep: String,
+        registry_version: String,
+        local_version: String,
+    },
+}
+
+/// Checks the workspace-local dependencies of all workspace members against the crates index.
+pub(crate) fn verify_publish_consistency<'a>(
+    ws: &'a ReleaseWorkspace<'a>,
+) -> Fallible<Vec<ConsistencyError>> {
+    verify_publish_consistency_with(ws, |name| {
+        crates_index_helper::registry_versions(name, false)
+    })
+}
+
+/// Checks the workspace-local dependencies of all workspace members against the versions returned by `registry_versions`.
+///
+/// A dependency is reported as unpublished if none of its registry versions matches the requirement,
+/// and as mismatched if a registry version matches but the local version itself isn't published.
+pub(crate) fn verify_publish_consistency_with<'a, F>(
+    ws: &'a ReleaseWorkspace<'a>,
+    registry_versions: F,
+) -> Fallible<Vec<ConsistencyError>>
+where
+    F: Fn(&str) -> Fallible<Vec<Version>>,
+{
+    let members = ws
+        .members()?
+        .iter()
+        .map(|crt| (crt.name(), *crt))
+        .collect::<HashMap<_, _>>();
+
+    let mut errors = vec![];
+    let mut mismatched = HashSet::new();
+
+    for crt in ws.members()? {
+        for dep in crt.package().dependencies() {
+            let dep_name = dep.package_name().to_string();
+
+            let dep_crt = match members.get(&dep_name) {
+                Some(dep_crt) if dep.source_id().is_path() => dep_crt,
+                _ => continue,
+            };
+
+            // unversioned dev-dependencies are ignored by `cargo publish`
+            if let CargoDepKind::Development = dep.kind() {
+                if !dep.specified_req() {
+                    continue;
+                }
+            }
+
+            let versions = registry_versions(&dep_name)?;
+            let local_version = dep_crt.version();
+
+            match versions
+                .iter()
+                .filter(|version| dep.version_req().matches(version))
+                .max()
+            {
+                None => errors.push(ConsistencyError::UnpublishedDep {
+                    dependent: crt.name(),
+                    dependency: dep_name,
+                    required_version: dep.version_req().to_string(),
+                }),
+                Some(registry_version)
+                    if !versions.contains(&local_version)
+                        && mismatched.insert(dep_name.clone()) =>
+                {
+                    errors.push(ConsistencyError::VersionMismatch {
+                        dep: dep_name,
+                        registry_version: registry_version.to_string(),
+                        local_version: local_version.to_string(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
     }
+
+    Ok(errors)
 }
 
 /// Scans the workspace for crates that have changed since their previous release and bumps their version to a dev version.
@@ -197,9 +613,11 @@ pub(crate) fn cmd(args: &crate::cli::Args, cmd_args: &CrateArgs) -> CommandResul
 pub(crate) fn apply_dev_versions<'a>(
     ws: &'a ReleaseWorkspace<'a>,
     dev_suffix: &str,
+    bump_kind: BumpKind,
     dry_run: bool,
     commit: bool,
     no_verify: bool,
+    output_format: OutputFormat,
 ) -> Fallible<()> {
     let applicable_crates = ws
         .members()?
@@ -208,7 +626,17 @@ pub(crate) fn apply_dev_versions<'a>(
         .cloned()
         .collect::<Vec<_>>();
 
-    let msg = apply_dev_vesrions_to_selection(applicable_crates, dev_suffix, dry_run)?;
+    let crates_bumped =
+        apply_dev_vesrions_to_selection(applicable_crates, dev_suffix, bump_kind, dry_run)?;
+    let msg = format_crate_bumps(&crates_bumped);
+
+    if output_format == OutputFormat::Json {
+        ReleaseStatusReport {
+            crates_bumped,
+            dry_run,
+        }
+        .print_json()?;
+    }
 
     if !msg.is_empty() {
         let commit_msg = indoc::formatdoc! {r#"
@@ -220,7 +648,9 @@ pub(crate) fn apply_dev_versions<'a>(
         "#, msg,
         };
 
-        info!("creating commit with message '{}' ", commit_msg);
+        if output_format == OutputFormat::Text {
+            info!("creating commit with message '{}' ", commit_msg);
+        }
 
         if !dry_run {
             // this checks consistency and also updates the Cargo.lock file(s)
@@ -240,15 +670,20 @@ pub(crate) fn apply_dev_versions<'a>(
 pub(crate) fn apply_dev_vesrions_to_selection<'a>(
     applicable_crates: Vec<&'a Crate<'a>>,
     dev_suffix: &str,
+    bump_kind: BumpKind,
     dry_run: bool,
-) -> Fallible<String> {
+) -> Fallible<Vec<CrateBump>> {
+    // bail before any version is written if the selection can't be published in a consistent order
+    let publish_order = verify_publish_order(&applicable_crates)?;
+
     let mut applicable_crates = applicable_crates
         .iter()
         .map(|crt| (crt.name(), *crt))
         .collect::<HashMap<_, _>>();
 
-    let mut queue = applicable_crates.values().copied().collect::<Vec<_>>();
-    let mut msg = String::new();
+    // the queue is popped from the back, so dependencies are bumped before their dependants
+    let mut queue = publish_order.into_iter().rev().collect::<Vec<_>>();
+    let mut crates_bumped = vec![];
 
     while let Some(crt) = queue.pop() {
         let mut version = crt.version();
@@ -263,7 +698,7 @@ pub(crate) fn apply_dev_vesrions_to_selection<'a>(
             continue;
         }
 
-        increment_patch(&mut version);
+        bump_kind.apply(&mut version);
         version = semver::Version::parse(&format!("{}-{}", version, dev_suffix))?;
 
         debug!(
@@ -283,11 +718,169 @@ pub(crate) fn apply_dev_vesrions_to_selection<'a>(
             }
         }
 
-        // todo: can we mutate crt and use crt.name_version() here instead?
-        msg += format!("\n- {}-{}", crt.name(), version).as_str();
+        crates_bumped.push(CrateBump {
+            name: crt.name(),
+            old_version: crt.version().to_string(),
+            new_version: version.to_string(),
+        });
+    }
+
+    Ok(crates_bumped)
+}
+
+/// Formats the given bumps as a list for commit messages.
+pub(crate) fn format_crate_bumps(crates_bumped: &[CrateBump]) -> String {
+    crates_bumped
+        .iter()
+        .map(|bump| format!("\n- {}-{}", bump.name, bump.new_version))
+        .collect()
+}
+
+/// Increments the patch version of every workspace member and returns the `(name, old_version, new_version)` of each.
+///
+/// Members with a pre-release version are skipped.
+pub(crate) fn increment_patch_for_workspace<'a>(
+    ws: &'a ReleaseWorkspace<'a>,
+    dry_run: bool,
+) -> Fallible<Vec<(String, Version, Version)>> {
+    let mut crates_bumped = vec![];
+
+    for crt in ws.members()? {
+        let old_version = crt.version();
+
+        if old_version.is_prerelease() {
+            debug!(
+                "[{}] ignoring due to prerelease version '{}'",
+                crt.name(),
+                old_version
+            );
+
+            continue;
+        }
+
+        let mut new_version = old_version.clone();
+        increment_patch(&mut new_version);
+
+        debug!(
+            "[{}] rewriting version {} -> {}",
+            crt.name(),
+            old_version,
+            new_version,
+        );
+
+        crate::common::set_version(dry_run, crt, &new_version)?;
+
+        crates_bumped.push((crt.name(), old_version, new_version));
+    }
+
+    Ok(crates_bumped)
+}
+
+/// Sorts the given crates so that each crate comes after all of its workspace-local normal and build dependencies within the given selection.
+///
+/// Fails with a description of the cycle if the dependencies between the crates are cyclic.
+pub(crate) fn verify_publish_order<'a>(crates: &[&'a Crate<'a>]) -> Fallible<Vec<&'a Crate<'a>>> {
+    let names = crates.iter().map(|crt| crt.name()).collect::<Vec<_>>();
+    let selected = names.iter().cloned().collect::<HashSet<_>>();
+
+    // dev-dependencies are stripped by `cargo publish` and don't constrain the publish order
+    let mut dependencies = HashMap::<String, HashSet<String>>::new();
+    for crt in crates {
+        dependencies.insert(
+            crt.name(),
+            crt.package()
+                .dependencies()
+                .iter()
+                .filter(|dep| {
+                    dep.source_id().is_path() && !matches!(dep.kind(), CargoDepKind::Development)
+                })
+                .map(|dep| dep.package_name().to_string())
+                .filter(|name| selected.contains(name) && *name != crt.name())
+                .collect(),
+        );
+    }
+
+    let crates_by_name = crates
+        .iter()
+        .map(|crt| (crt.name(), *crt))
+        .collect::<HashMap<_, _>>();
+
+    Ok(topological_sort(&names, &dependencies)?
+        .into_iter()
+        .filter_map(|name| crates_by_name.get(&name).copied())
+        .collect())
+}
+
+/// Sorts the given nodes using Kahn's algorithm so that each node comes after all of its dependencies.
+///
+/// Nodes without any ordering constraint between them keep their relative input order.
+pub(crate) fn topological_sort(
+    nodes: &[String],
+    dependencies: &HashMap<String, HashSet<String>>,
+) -> Fallible<Vec<String>> {
+    let mut in_degree = nodes
+        .iter()
+        .map(|node| {
+            (
+                node.as_str(),
+                dependencies.get(node).map(HashSet::len).unwrap_or_default(),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut queue = nodes
+        .iter()
+        .filter(|node| in_degree.get(node.as_str()) == Some(&0))
+        .collect::<std::collections::VecDeque<_>>();
+    let mut sorted = Vec::with_capacity(nodes.len());
+
+    while let Some(node) = queue.pop_front() {
+        sorted.push(node.clone());
+
+        for dependant in nodes.iter().filter(|candidate| {
+            dependencies
+                .get(*candidate)
+                .map(|deps| deps.contains(node))
+                .unwrap_or_default()
+        }) {
+            if let Some(degree) = in_degree.get_mut(dependant.as_str()) {
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(dependant);
+                }
+            }
+        }
+    }
+
+    if sorted.len() < nodes.len() {
+        let remaining = nodes
+            .iter()
+            .filter(|node| !sorted.contains(node))
+            .collect::<HashSet<_>>();
+
+        // every remaining node has at least one remaining dependency, so following them must lead into a cycle
+        let mut path: Vec<&String> = vec![];
+        let mut current = *remaining.iter().next().expect("at least one node remains");
+        while !path.contains(&current) {
+            path.push(current);
+            current = dependencies
+                .get(current)
+                .and_then(|deps| deps.iter().find(|dep| remaining.contains(dep)))
+                .expect("remaining nodes have remaining dependencies");
+        }
+
+        let cycle_start = path.iter().position(|node| *node == current).unwrap_or(0);
+        let cycle = path[cycle_start..]
+            .iter()
+            .chain(std::iter::once(&current))
+            .map(|node| node.as_str())
+            .collect::<Vec<_>>()
+            .join(" -> ");
+
+        bail!("dependency cycle detected: {}", cycle);
     }
 
-    Ok(msg)
+    Ok(sorted)
 }
 
 pub(crate) fn increment_patch(v: &mut semver::Version) {
@@ -296,6 +889,21 @@ pub(crate) fn increment_patch(v: &mut semver::Version) {
     v.build = semver::BuildMetadata::EMPTY;
 }
 
+pub(crate) fn increment_minor(v: &mut semver::Version) {
+    v.minor += 1;
+    v.patch = 0;
+    v.pre = semver::Prerelease::EMPTY;
+    v.build = semver::BuildMetadata::EMPTY;
+}
+
+pub(crate) fn increment_major(v: &mut semver::Version) {
+    v.major += 1;
+    v.minor = 0;
+    v.patch = 0;
+    v.pre = semver::Prerelease::EMPTY;
+    v.build = semver::BuildMetadata::EMPTY;
+}
+
 pub(crate) fn fixup_releases<'a>(
     ws: &'a ReleaseWorkspace<'a>,
     dev_suffix: &str,
@@ -303,13 +911,45 @@ pub(crate) fn fixup_releases<'a>(
     dry_run: bool,
     commit: bool,
     no_verify: bool,
+    output_format: OutputFormat,
+    retry_file: Option<&Path>,
 ) -> Fallible<()> {
+    fixup_releases_with(
+        ws,
+        dev_suffix,
+        fixup,
+        dry_run,
+        commit,
+        no_verify,
+        output_format,
+        retry_file,
+        |crt| crates_index_helper::is_version_published(crt, false),
+    )
+}
+
+/// Applies dev versions to the unpublished crates of the selected releases, using `is_version_published` to determine the publish state of each crate.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn fixup_releases_with<'a, F>(
+    ws: &'a ReleaseWorkspace<'a>,
+    dev_suffix: &str,
+    fixup: &FixupReleases,
+    dry_run: bool,
+    commit: bool,
+    no_verify: bool,
+    output_format: OutputFormat,
+    retry_file: Option<&Path>,
+    is_version_published: F,
+) -> Fallible<()>
+where
+    F: Fn(&Crate) -> Fallible<bool>,
+{
+    let mut failed_crates = vec![];
     let mut unpublished_crates: std::collections::BTreeMap<
         String,
         Vec<&'a crate::crate_selection::Crate>,
     > = Default::default();
 
-    match fixup {
+    let releases = match fixup {
         FixupReleases::Latest => {
             let (release_title, crate_release_titles) = match ws
                 .changelog()
@@ -331,25 +971,75 @@ pub(crate) fn fixup_releases<'a>(
                 unexpected => bail!("unexpected topmost release: {:?}", unexpected),
             };
 
-            debug!("{}: {:#?}", release_title, crate_release_titles);
+            vec![(release_title, crate_release_titles)]
+        }
+        FixupReleases::Range(from, to) => {
+            for tag in [from, to] {
+                ws.git_repo()
+                    .revparse_single(tag)
+                    .context(format!("tag '{}' not found in the git repository", tag))?;
+            }
+
+            ws.changelog()
+                .ok_or_else(|| anyhow::anyhow!("no workspace changelog found"))?
+                .releases_between(from, to)?
+                .into_iter()
+                .map(|release| match release {
+                    crate::changelog::ReleaseChange::WorkspaceReleaseChange(title, releases) => {
+                        Ok((
+                            title,
+                            releases
+                                .into_iter()
+                                .collect::<std::collections::BTreeSet<_>>(),
+                        ))
+                    }
+                    unexpected => bail!("unexpected release: {:?}", unexpected),
+                })
+                .collect::<Fallible<Vec<_>>>()?
+        }
+        other => bail!("{:?} not implemented", other),
+    };
 
-            let crates = ws
-                .members()?
-                .iter()
-                .filter(|crt| crate_release_titles.contains(&crt.name_version()))
-                .cloned()
-                .collect::<Vec<_>>();
+    for (release_title, crate_release_titles) in releases {
+        debug!("{}: {:#?}", release_title, crate_release_titles);
 
-            for crt in crates {
-                if !crate::release::crates_index_helper::is_version_published(crt, false)? {
-                    unpublished_crates
-                        .entry(release_title.clone())
-                        .or_default()
-                        .push(crt);
+        let crates = ws
+            .members()?
+            .iter()
+            .filter(|crt| crate_release_titles.contains(&crt.name_version()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for crt in crates {
+            match is_version_published(crt) {
+                Ok(true) => {}
+                Ok(false) => unpublished_crates
+                    .entry(release_title.clone())
+                    .or_default()
+                    .push(crt),
+                Err(e) if retry_file.is_some() => {
+                    warn!("[{}] failed to check publish state: {:?}", crt.name(), e);
+
+                    failed_crates.push(RetryEntry {
+                        name: crt.name(),
+                        version: crt.version().to_string(),
+                    });
                 }
+                Err(e) => return Err(e),
             }
         }
-        other => bail!("{:?} not implemented", other),
+    }
+
+    if let Some(retry_file) = retry_file {
+        if !failed_crates.is_empty() {
+            info!(
+                "writing {} failed crate(s) to {:?}",
+                failed_crates.len(),
+                retry_file
+            );
+
+            RetryFile::append(retry_file, failed_crates)?;
+        }
     }
 
     info!(
@@ -365,14 +1055,127 @@ pub(crate) fn fixup_releases<'a>(
             .collect::<Vec<_>>()
     );
 
-    // bump their versions to dev versions
-    let msg = apply_dev_vesrions_to_selection(
-        // TOOD: change this once more than "latest" is supported above
-        unpublished_crates.into_iter().next().unwrap_or_default().1,
+    apply_dev_versions_to_unpublished(
+        ws,
+        unpublished_crates.into_values().flatten().collect(),
+        dev_suffix,
+        dry_run,
+        commit,
+        no_verify,
+        output_format,
+    )
+}
+
+/// Re-runs the fixup for the crates in the given retry file.
+///
+/// Entries are removed from the file once their publish state could be determined.
+pub(crate) fn retry_failed<'a>(
+    ws: &'a ReleaseWorkspace<'a>,
+    retry_file: &Path,
+    dev_suffix: &str,
+    dry_run: bool,
+    commit: bool,
+    no_verify: bool,
+    output_format: OutputFormat,
+) -> Fallible<()> {
+    retry_failed_with(
+        ws,
+        retry_file,
+        dev_suffix,
+        dry_run,
+        commit,
+        no_verify,
+        output_format,
+        |crt| crates_index_helper::is_version_published(crt, false),
+    )
+}
+
+/// Re-runs the fixup for the crates in the given retry file, using `is_version_published` to determine the publish state of each crate.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn retry_failed_with<'a, F>(
+    ws: &'a ReleaseWorkspace<'a>,
+    retry_file: &Path,
+    dev_suffix: &str,
+    dry_run: bool,
+    commit: bool,
+    no_verify: bool,
+    output_format: OutputFormat,
+    is_version_published: F,
+) -> Fallible<()>
+where
+    F: Fn(&Crate) -> Fallible<bool>,
+{
+    let mut retry_entries = RetryFile::load(retry_file)?;
+    let mut unpublished_crates = vec![];
+
+    let mut remaining = vec![];
+    for entry in retry_entries.entry.drain(..) {
+        let crt = match ws
+            .members()?
+            .iter()
+            .find(|crt| crt.name() == entry.name && crt.version().to_string() == entry.version)
+        {
+            Some(crt) => *crt,
+            None => {
+                warn!(
+                    "[{}] version {} not found in the workspace, dropping it from the retry file",
+                    entry.name, entry.version
+                );
+                continue;
+            }
+        };
+
+        match is_version_published(crt) {
+            Ok(true) => {}
+            Ok(false) => unpublished_crates.push(crt),
+            Err(e) => {
+                warn!("[{}] failed to check publish state: {:?}", crt.name(), e);
+                remaining.push(entry);
+            }
+        }
+    }
+
+    apply_dev_versions_to_unpublished(
+        ws,
+        unpublished_crates,
         dev_suffix,
         dry_run,
+        commit,
+        no_verify,
+        output_format,
     )?;
 
+    if !dry_run {
+        retry_entries.entry = remaining;
+        retry_entries.store(retry_file)?;
+    }
+
+    Ok(())
+}
+
+/// Bumps the given unpublished crates to their dev versions and optionally commits the change.
+fn apply_dev_versions_to_unpublished<'a>(
+    ws: &'a ReleaseWorkspace<'a>,
+    unpublished_crates: Vec<&'a Crate<'a>>,
+    dev_suffix: &str,
+    dry_run: bool,
+    commit: bool,
+    no_verify: bool,
+    output_format: OutputFormat,
+) -> Fallible<()> {
+    // bump their versions to dev versions
+    let crates_bumped =
+        apply_dev_vesrions_to_selection(unpublished_crates, dev_suffix, BumpKind::Patch, dry_run)?;
+    let msg = format_crate_bumps(&crates_bumped);
+
+    if output_format == OutputFormat::Json {
+        ReleaseStatusReport {
+            crates_bumped,
+            dry_run,
+        }
+        .print_json()?;
+    }
+
     if !msg.is_empty() {
         let commit_msg = indoc::formatdoc! {r#"
             applying develop versions to unpublished crates
@@ -382,7 +1185,9 @@ pub(crate) fn fixup_releases<'a>(
         "#, msg,
         };
 
-        info!("creating commit with message '{}' ", commit_msg);
+        if output_format == OutputFormat::Text {
+            info!("creating commit with message '{}' ", commit_msg);
+        }
 
         if !dry_run {
             // this checks consistency and also updates the Cargo.lock file(s)
@@ -400,73 +1205,202 @@ pub(crate) fn fixup_releases<'a>(
 }
 
 /// Ensures that the given crates have at least sent an invite to the given crate.io usernames.
+///
+/// The current owners are listed with up to `parallelism` concurrent `cargo owner --list` processes.
+/// Missing owners are then invited sequentially.
+/// Errors for individual crates don't abort the process and are reported at the end.
 pub(crate) fn ensure_crate_io_owners<'a>(
     _ws: &'a ReleaseWorkspace<'a>,
     dry_run: bool,
     crates: &[&Crate],
     minimum_crate_owners: &[&str],
+    parallelism: usize,
 ) -> Fallible<()> {
-    let desired_owners = minimum_crate_owners
-        .iter()
-        .map(|s| s.to_string())
-        .collect::<HashSet<_>>();
+    let mut errors: Vec<anyhow::Error> = vec![];
 
+    let mut published_crates = vec![];
     for crt in crates {
-        if !crates_index_helper::is_version_published(crt, false)? {
-            warn!("{} is not published, skipping..", crt.name());
-            continue;
+        match crates_index_helper::is_version_published(crt, false) {
+            Ok(true) => published_crates.push(crt.name()),
+            Ok(false) => warn!("{} is not published, skipping..", crt.name()),
+            Err(e) => errors.push(e.context(format!(
+                "[{}] failed to check whether it is published",
+                crt.name()
+            ))),
         }
+    }
+
+    errors.extend(ensure_owners(
+        published_crates,
+        minimum_crate_owners,
+        parallelism,
+        list_crate_io_owners,
+        |name, owner| {
+            if dry_run {
+                info!("[{}] would add owner '{}'", name, owner);
+                Ok(())
+            } else {
+                add_crate_io_owner(name, owner)
+            }
+        },
+    ));
+
+    if !errors.is_empty() {
+        bail!(
+            "{} error(s) while ensuring crate owners:\n{}",
+            errors.len(),
+            errors
+                .iter()
+                .map(|e| format!("{:#}", e))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
 
-        let mut cmd = std::process::Command::new("cargo");
-        cmd.args(&["owner", "--list", &crt.name()]);
+    Ok(())
+}
 
-        debug!("[{}] running command: {:?}", crt.name(), cmd);
-        let output = cmd.output().context("process exitted unsuccessfully")?;
-        if !output.status.success() {
-            warn!(
-                "[{}] failed list owners: {}",
-                crt.name(),
-                String::from_utf8_lossy(&output.stderr)
-            );
+/// Lists the owners of the given crates concurrently with `list_owners`,
+/// then calls `add_owner` sequentially for each of the `desired_owners` a crate is missing.
+///
+/// Returns the errors of all crates instead of stopping at the first one.
+pub(crate) fn ensure_owners<L, A>(
+    names: Vec<String>,
+    desired_owners: &[&str],
+    parallelism: usize,
+    list_owners: L,
+    mut add_owner: A,
+) -> Vec<anyhow::Error>
+where
+    L: Fn(&str) -> Fallible<HashSet<String>> + Send + Sync + 'static,
+    A: FnMut(&str, &str) -> Fallible<()>,
+{
+    let desired_owners = desired_owners
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<HashSet<_>>();
 
-            continue;
-        }
+    let mut errors = vec![];
+
+    for (name, current_owners) in list_owners_concurrently(names, parallelism, list_owners) {
+        let current_owners = match current_owners {
+            Ok(current_owners) => current_owners,
+            Err(e) => {
+                errors.push(e.context(format!("[{}] failed to list owners", name)));
+                continue;
+            }
+        };
 
-        let current_owners = output
-            .stdout
-            .lines()
-            .map(|line| {
-                line.words_with_breaks()
-                    .take_while(|item| *item != " ")
-                    .collect::<String>()
-            })
-            .collect::<HashSet<_>>();
         let diff = desired_owners.difference(&current_owners);
         info!(
             "[{}] current owners {:?}, missing owners: {:?}",
-            crt.name(),
-            current_owners,
-            diff
+            name, current_owners, diff
         );
 
         for owner in diff {
-            let mut cmd = std::process::Command::new("cargo");
-            cmd.args(&["owner", "--add", owner, &crt.name()]);
-
-            debug!("[{}] running command: {:?}", crt.name(), cmd);
-            if !dry_run {
-                let output = cmd.output().context("process exitted unsuccessfully")?;
-                if !output.status.success() {
-                    warn!(
-                        "[{}] failed to add owner '{}': {}",
-                        crt.name(),
-                        owner,
-                        String::from_utf8_lossy(&output.stderr)
-                    );
-                }
+            if let Err(e) = add_owner(&name, owner) {
+                errors.push(e.context(format!("[{}] failed to add owner '{}'", name, owner)));
             }
         }
     }
 
+    errors
+}
+
+/// Invites the given crates.io user to own the given crate using `cargo owner --add`.
+fn add_crate_io_owner(name: &str, owner: &str) -> Fallible<()> {
+    let mut cmd = std::process::Command::new("cargo");
+    cmd.args(["owner", "--add", owner, name]);
+
+    debug!("[{}] running command: {:?}", name, cmd);
+    let output = cmd.output().context("process exitted unsuccessfully")?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+
     Ok(())
 }
+
+/// Lists the current owners of the given crate using `cargo owner --list`.
+fn list_crate_io_owners(name: &str) -> Fallible<HashSet<String>> {
+    let mut cmd = std::process::Command::new("cargo");
+    cmd.args(["owner", "--list", name]);
+
+    debug!("[{}] running command: {:?}", name, cmd);
+    let output = cmd.output().context("process exitted unsuccessfully")?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    Ok(output
+        .stdout
+        .lines()
+        .map(|line| {
+            line.words_with_breaks()
+                .take_while(|item| *item != " ")
+                .collect::<String>()
+        })
+        .collect::<HashSet<_>>())
+}
+
+/// Calls `list_owners` for each of the given crate names on up to `parallelism` threads.
+///
+/// The results are returned in the same order as the given names.
+pub(crate) fn list_owners_concurrently<F>(
+    names: Vec<String>,
+    parallelism: usize,
+    list_owners: F,
+) -> Vec<(String, Fallible<HashSet<String>>)>
+where
+    F: Fn(&str) -> Fallible<HashSet<String>> + Send + Sync + 'static,
+{
+    use std::sync::{Arc, Mutex};
+
+    let list_owners = Arc::new(list_owners);
+    let queue = Arc::new(Mutex::new(
+        names.iter().cloned().enumerate().collect::<Vec<_>>(),
+    ));
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    let workers = (0..parallelism.max(1).min(names.len()))
+        .map(|_| {
+            let list_owners = list_owners.clone();
+            let queue = queue.clone();
+            let tx = tx.clone();
+
+            std::thread::spawn(move || loop {
+                let next = match queue.lock() {
+                    Ok(mut queue) => queue.pop(),
+                    Err(_) => None,
+                };
+
+                match next {
+                    Some((i, name)) => {
+                        let result = list_owners(&name);
+                        if tx.send((i, result)).is_err() {
+                            break;
+                        }
+                    }
+                    None => break,
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    drop(tx);
+
+    let mut results = names
+        .into_iter()
+        .map(|name| (name, Err(anyhow::anyhow!("owners were not listed"))))
+        .collect::<Vec<_>>();
+    for (i, result) in rx {
+        results[i].1 = result;
+    }
+
+    for worker in workers {
+        if worker.join().is_err() {
+            warn!("owner listing thread panicked");
+        }
+    }
+
+    results
+}
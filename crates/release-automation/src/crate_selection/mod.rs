@@ -889,7 +889,7 @@ impl<'a> ReleaseWorkspace<'a> {
         Ok(release_selection)
     }
 
-    fn members_unsorted(&'a self) -> Fallible<&'a Vec<Crate<'a>>> {
+    pub(crate) fn members_unsorted(&'a self) -> Fallible<&'a Vec<Crate<'a>>> {
         self.members_unsorted.get_or_try_init(|| {
             let mut members = vec![];
 
@@ -115,6 +115,7 @@ pub(crate) fn cmd(args: &crate::cli::Args, cmd_args: &crate::cli::ReleaseArgs) -
                     .split(",")
                     .collect::<Vec<_>>()
                     .as_slice(),
+                crate_::DEFAULT_OWNER_LISTING_PARALLELISM,
             )?,
 
             // ReleaseSteps::CreateCrateTags => create_crate_tags(&ws, cmd_args)?,
@@ -703,6 +704,24 @@ pub(crate) mod crates_index_helper {
             })
             .unwrap_or_default())
     }
+
+    /// Returns all versions of the given crate name that are found on the crates index.
+    pub(crate) fn registry_versions(name: &str, update: bool) -> Fallible<Vec<semver::Version>> {
+        let index_lock = index(update)?
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to lock the index: {}", e))?;
+
+        index_lock
+            .crate_(name)
+            .map(|indexed_crate| {
+                indexed_crate
+                    .versions()
+                    .iter()
+                    .map(|version| Ok(semver::Version::parse(version.version())?))
+                    .collect()
+            })
+            .unwrap_or_else(|| Ok(vec![]))
+    }
 }
 
 /// Try to publish the given manifests to crates.io.
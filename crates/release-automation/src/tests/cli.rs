@@ -732,6 +732,82 @@ fn apply_dev_versions_works() {
     assert_eq!(get_crate_a_version(), "0.0.2-dev.0");
 }
 
+#[test]
+fn apply_dev_versions_is_idempotent() {
+    let workspace_mocker = example_workspace_1().unwrap();
+
+    let get_versions = || -> Vec<String> {
+        let workspace = ReleaseWorkspace::try_new(workspace_mocker.root()).unwrap();
+        workspace
+            .members()
+            .unwrap()
+            .iter()
+            .map(|m| m.name_version())
+            .collect::<Vec<_>>()
+    };
+
+    let apply_dev_versions = || {
+        let mut cmd = assert_cmd::Command::cargo_bin("release-automation").unwrap();
+        let cmd = cmd.args(&[
+            &format!("--workspace-path={}", workspace_mocker.root().display()),
+            "--log-level=debug",
+            "crate",
+            "apply-dev-versions",
+            "--commit",
+        ]);
+        let output = assert_cmd_success!(cmd);
+        println!("stderr:\n'{}'\n---\nstdout:\n'{}'\n---", output.0, output.1);
+    };
+
+    apply_dev_versions();
+    let versions_after_first_run = get_versions();
+    assert!(versions_after_first_run.contains(&"crate_a-0.0.2-dev.0".to_string()));
+
+    // a second run must not bump the already bumped crates again
+    apply_dev_versions();
+    assert_eq!(get_versions(), versions_after_first_run);
+}
+
+#[test]
+fn bump_all_patch_increments_non_prerelease_members_once() {
+    let workspace_mocker = example_workspace_1().unwrap();
+
+    let get_versions = || -> Vec<(String, semver::Version)> {
+        let workspace = ReleaseWorkspace::try_new(workspace_mocker.root()).unwrap();
+        workspace
+            .members()
+            .unwrap()
+            .iter()
+            .map(|m| (m.name(), m.version()))
+            .collect::<Vec<_>>()
+    };
+
+    let versions_before = get_versions();
+    assert!(versions_before
+        .iter()
+        .any(|(_, version)| version.is_prerelease()));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("release-automation").unwrap();
+    let cmd = cmd.args(&[
+        &format!("--workspace-path={}", workspace_mocker.root().display()),
+        "--log-level=debug",
+        "crate",
+        "bump-all-patch",
+    ]);
+    let output = assert_cmd_success!(cmd);
+    println!("stderr:\n'{}'\n---\nstdout:\n'{}'\n---", output.0, output.1);
+
+    let versions_after = get_versions();
+    assert_eq!(versions_before.len(), versions_after.len());
+    for ((name, before), (_, after)) in versions_before.iter().zip(versions_after.iter()) {
+        let mut expected = before.clone();
+        if !before.is_prerelease() {
+            expected.patch += 1;
+        }
+        assert_eq!(after, &expected, "unexpected version for {}", name);
+    }
+}
+
 #[test]
 fn release_dry_run_fails_on_unallowed_conditions() {
     let workspace_mocker = example_workspace_4().unwrap();
@@ -772,3 +848,93 @@ fn release_dry_run_fails_on_unallowed_conditions() {
         cmd.assert().failure();
     }
 }
+
+#[test]
+fn list_unpublished_offline_json() {
+    let workspace_mocker = example_workspace_1().unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("release-automation").unwrap();
+    let cmd = cmd.args(&[
+        &format!("--workspace-path={}", workspace_mocker.root().display()),
+        "crate",
+        "list-unpublished",
+        "--offline",
+        "--json",
+    ]);
+    let (_, stdout) = assert_cmd_success!(cmd);
+
+    let mut listed = serde_json::from_str::<Vec<serde_json::Value>>(&stdout).unwrap();
+    listed.sort_by_key(|entry| entry["name"].as_str().unwrap_or_default().to_string());
+
+    let expected = serde_json::json!([
+        { "name": "crate_a", "version": "0.0.1" },
+        { "name": "crate_b", "version": "0.0.0-alpha.1" },
+        { "name": "crate_c", "version": "0.0.1" },
+        { "name": "crate_e", "version": "0.0.1" },
+        { "name": "crate_f", "version": "0.1.0" },
+    ]);
+
+    assert_eq!(serde_json::Value::Array(listed), expected);
+}
+
+#[test]
+fn apply_dev_versions_json_report() {
+    let workspace_mocker = example_workspace_1().unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("release-automation").unwrap();
+    let cmd = cmd.args(&[
+        &format!("--workspace-path={}", workspace_mocker.root().display()),
+        "crate",
+        "apply-dev-versions",
+        "--dry-run",
+        "--output-format=json",
+    ]);
+    let (_, stdout) = assert_cmd_success!(cmd);
+
+    insta::assert_snapshot!(stdout);
+
+    // the dry-run must not have changed the manifest
+    let workspace = ReleaseWorkspace::try_new(workspace_mocker.root()).unwrap();
+    assert_eq!(get_crate_versions(&["crate_a"], &workspace), vec!["0.0.1"]);
+}
+
+#[test]
+fn apply_dev_versions_json_report_applied() {
+    let workspace_mocker = example_workspace_1().unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("release-automation").unwrap();
+    let cmd = cmd.args(&[
+        &format!("--workspace-path={}", workspace_mocker.root().display()),
+        "crate",
+        "apply-dev-versions",
+        "--no-verify",
+        "--output-format=json",
+    ]);
+    let (_, stdout) = assert_cmd_success!(cmd);
+
+    insta::assert_snapshot!(stdout);
+
+    let workspace = ReleaseWorkspace::try_new(workspace_mocker.root()).unwrap();
+    assert_eq!(
+        get_crate_versions(&["crate_a"], &workspace),
+        vec!["0.0.2-dev.0"]
+    );
+}
+
+#[test]
+fn fixup_releases_range_requires_existing_tags() {
+    let workspace_mocker = example_workspace_1().unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("release-automation").unwrap();
+    let cmd = cmd.args(&[
+        &format!("--workspace-path={}", workspace_mocker.root().display()),
+        "crate",
+        "fixup-releases",
+        "--fixup-releases=20210304.120604..20210315.100000",
+        "--dry-run",
+    ]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("not found in the git repository"));
+}
@@ -185,3 +185,599 @@ fn parse_publish_error() {
         assert_eq!(case.expected_error, result);
     }
 }
+
+#[test]
+fn bump_kinds_erase_pre_and_build() {
+    use crate::crate_::BumpKind;
+
+    let cases = [
+        (BumpKind::Patch, "0.1.2"),
+        (BumpKind::Minor, "0.2.0"),
+        (BumpKind::Major, "1.0.0"),
+    ];
+
+    for (bump_kind, expected) in cases {
+        let mut version = semver::Version::parse("0.1.1-dev.0+build.1").unwrap();
+        bump_kind.apply(&mut version);
+
+        assert_eq!(version, semver::Version::parse(expected).unwrap());
+        assert!(version.pre.is_empty());
+        assert!(version.build.is_empty());
+    }
+}
+
+#[test]
+fn list_owners_concurrently_respects_parallelism() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let parallelism = 3;
+    let running = Arc::new(AtomicUsize::new(0));
+    let max_running = Arc::new(AtomicUsize::new(0));
+
+    let names = (0..12).map(|i| format!("crate_{}", i)).collect::<Vec<_>>();
+
+    let results = {
+        let running = running.clone();
+        let max_running = max_running.clone();
+
+        crate::crate_::list_owners_concurrently(names.clone(), parallelism, move |name| {
+            let current = running.fetch_add(1, Ordering::SeqCst) + 1;
+            max_running.fetch_max(current, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            running.fetch_sub(1, Ordering::SeqCst);
+
+            if name == "crate_5" {
+                anyhow::bail!("mocked failure");
+            }
+
+            Ok(std::iter::once(format!("{}_owner", name)).collect())
+        })
+    };
+
+    assert!(max_running.load(Ordering::SeqCst) <= parallelism);
+    assert!(max_running.load(Ordering::SeqCst) > 1);
+
+    assert_eq!(
+        names,
+        results
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>()
+    );
+
+    for (name, result) in results {
+        if name == "crate_5" {
+            assert!(result.is_err());
+        } else {
+            assert!(result.unwrap().contains(&format!("{}_owner", name)));
+        }
+    }
+}
+
+#[test]
+fn ensure_owners_collects_errors_per_crate() {
+    let names = (0..4).map(|i| format!("crate_{}", i)).collect::<Vec<_>>();
+    let mut added = vec![];
+
+    let errors = crate::crate_::ensure_owners(
+        names,
+        &["alice", "bob"],
+        2,
+        |name| {
+            if name == "crate_1" {
+                anyhow::bail!("mocked listing failure");
+            }
+
+            Ok(std::iter::once("alice".to_string()).collect())
+        },
+        |name, owner| {
+            if name == "crate_2" {
+                anyhow::bail!("mocked invite failure");
+            }
+
+            added.push((name.to_string(), owner.to_string()));
+            Ok(())
+        },
+    );
+
+    let errors = errors
+        .iter()
+        .map(|e| format!("{:#}", e))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        vec![
+            "[crate_1] failed to list owners: mocked listing failure",
+            "[crate_2] failed to add owner 'bob': mocked invite failure",
+        ]
+    );
+
+    // the crates after the failing ones were still handled
+    assert_eq!(
+        added,
+        vec![
+            ("crate_0".to_string(), "bob".to_string()),
+            ("crate_3".to_string(), "bob".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn topological_sort_random_dags() {
+    use crate::crate_::topological_sort;
+    use std::collections::{HashMap, HashSet};
+
+    // a small linear congruential generator keeps the generated graphs reproducible
+    let mut seed: u64 = 0x5eed;
+    let mut next_random = move || {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        seed >> 33
+    };
+
+    for _ in 0..100 {
+        let num_nodes = (next_random() % 20) as usize + 1;
+        let nodes = (0..num_nodes)
+            .map(|i| format!("crate_{}", i))
+            .collect::<Vec<_>>();
+
+        // edges only point to nodes with a lower index which guarantees an acyclic graph
+        let mut dependencies = HashMap::<String, HashSet<String>>::new();
+        for (i, node) in nodes.iter().enumerate() {
+            let deps = dependencies.entry(node.clone()).or_default();
+            for dep in nodes.iter().take(i) {
+                if next_random() % 3 == 0 {
+                    deps.insert(dep.clone());
+                }
+            }
+        }
+
+        // present the nodes in a shuffled order
+        let mut shuffled = nodes.clone();
+        for i in (1..shuffled.len()).rev() {
+            let j = (next_random() as usize) % (i + 1);
+            shuffled.swap(i, j);
+        }
+
+        let sorted = topological_sort(&shuffled, &dependencies).unwrap();
+        assert_eq!(sorted.len(), nodes.len());
+
+        let positions = sorted
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.clone(), i))
+            .collect::<HashMap<_, _>>();
+
+        for (node, deps) in &dependencies {
+            for dep in deps {
+                assert!(
+                    positions[dep] < positions[node],
+                    "{} must come before {} in {:?}",
+                    dep,
+                    node,
+                    sorted
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn topological_sort_detects_cycle() {
+    use crate::crate_::topological_sort;
+    use std::collections::{HashMap, HashSet};
+
+    let nodes = ["crate_a", "crate_b", "crate_c", "crate_d"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+
+    let dependencies = [
+        ("crate_a", vec![]),
+        ("crate_b", vec!["crate_a", "crate_d"]),
+        ("crate_c", vec!["crate_b"]),
+        ("crate_d", vec!["crate_c"]),
+    ]
+    .iter()
+    .map(|(name, deps)| {
+        (
+            name.to_string(),
+            deps.iter().map(|s| s.to_string()).collect::<HashSet<_>>(),
+        )
+    })
+    .collect::<HashMap<_, _>>();
+
+    let err = topological_sort(&nodes, &dependencies)
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.contains("dependency cycle detected"), "{}", err);
+    for name in ["crate_b", "crate_c", "crate_d"] {
+        assert!(err.contains(name), "{}", err);
+    }
+    assert!(!err.contains("crate_a"), "{}", err);
+}
+
+#[test]
+fn verify_publish_order_ignores_dev_dependency_cycles() {
+    use crate::crate_::verify_publish_order;
+    use crate::tests::workspace_mocker::{MockProject, WorkspaceMocker};
+
+    // mirrors kitsune_p2p and kitsune_p2p_bootstrap which dev-depend on each other
+    let workspace_mocker = WorkspaceMocker::try_new(
+        None,
+        vec![
+            MockProject {
+                name: "crate_a".to_string(),
+                version: "0.1.0".to_string(),
+                dependencies: vec![
+                    r#"crate_c = { path = "../crate_c", version = "0.1.0" }"#.to_string()
+                ],
+                dev_dependencies: vec![r#"crate_b = { path = "../crate_b" }"#.to_string()],
+                ..Default::default()
+            },
+            MockProject {
+                name: "crate_b".to_string(),
+                version: "0.1.0".to_string(),
+                dev_dependencies: vec![r#"crate_a = { path = "../crate_a" }"#.to_string()],
+                ..Default::default()
+            },
+            MockProject {
+                name: "crate_c".to_string(),
+                version: "0.1.0".to_string(),
+                ..Default::default()
+            },
+        ],
+    )
+    .unwrap();
+    let workspace = crate::release::ReleaseWorkspace::try_new(workspace_mocker.root()).unwrap();
+
+    // `members()` would sort by all workspace dependencies including dev-dependencies
+    let members = workspace
+        .members_unsorted()
+        .unwrap()
+        .iter()
+        .collect::<Vec<_>>();
+    let sorted = verify_publish_order(&members)
+        .unwrap()
+        .into_iter()
+        .map(|crt| crt.name())
+        .collect::<Vec<_>>();
+
+    let position = |name: &str| sorted.iter().position(|n| n == name).unwrap();
+    assert_eq!(sorted.len(), 3);
+    assert!(position("crate_c") < position("crate_a"), "{:?}", sorted);
+}
+
+#[test]
+fn retry_file_records_failures() {
+    use crate::crate_::{RetryEntry, RetryFile};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("retry.toml");
+
+    // two subsequent runs that failed for one crate each
+    for (name, version) in [("crate_a", "0.0.2"), ("crate_b", "0.1.0-beta-rc.1")] {
+        RetryFile::append(
+            &path,
+            vec![RetryEntry {
+                name: name.to_string(),
+                version: version.to_string(),
+            }],
+        )
+        .unwrap();
+    }
+
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        indoc::indoc! {r#"
+            [[entry]]
+            name = "crate_a"
+            version = "0.0.2"
+
+            [[entry]]
+            name = "crate_b"
+            version = "0.1.0-beta-rc.1"
+        "#}
+    );
+
+    assert_eq!(RetryFile::load(&path).unwrap().entry.len(), 2);
+}
+
+#[test]
+fn retry_failed_reruns_only_failed_crates() {
+    use crate::crate_::{
+        fixup_releases_with, parse_fixup_releases, retry_failed_with, OutputFormat, RetryEntry,
+        RetryFile, DEFAULT_DEV_SUFFIX,
+    };
+    use crate::release::ReleaseWorkspace;
+    use crate::tests::workspace_mocker::example_workspace_1;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    let workspace_mocker = example_workspace_1().unwrap();
+    workspace_mocker.add_or_replace_file(
+        "CHANGELOG.md",
+        indoc::indoc! {r#"
+        # Changelog
+
+        # [Unreleased]
+
+        # [20210315.100000]
+
+        ## [crate_a-0.0.1](crates/crate_a/CHANGELOG.md#0.0.1)
+        ## [crate_b-0.0.0-alpha.1](crates/crate_b/CHANGELOG.md#0.0.0-alpha.1)
+
+        # [20210304.120604]
+
+        ## [crate_e-0.0.1](crates/crate_e/CHANGELOG.md#0.0.1)
+        "#,
+        },
+    );
+    workspace_mocker.commit(None);
+    workspace_mocker.tag("20210304.120604");
+    workspace_mocker.tag("20210315.100000");
+
+    let get_versions = || -> HashMap<String, String> {
+        let workspace = ReleaseWorkspace::try_new(workspace_mocker.root()).unwrap();
+        workspace
+            .members()
+            .unwrap()
+            .iter()
+            .map(|crt| (crt.name(), crt.version().to_string()))
+            .collect()
+    };
+    let versions_before = get_versions();
+
+    let dir = tempfile::tempdir().unwrap();
+    let retry_file = dir.path().join("retry.toml");
+
+    // the index can't be reached for crate_a and crate_e
+    let workspace = ReleaseWorkspace::try_new(workspace_mocker.root()).unwrap();
+    fixup_releases_with(
+        &workspace,
+        DEFAULT_DEV_SUFFIX,
+        &parse_fixup_releases("20210304.120604..20210315.100000").unwrap(),
+        false,
+        false,
+        true,
+        OutputFormat::Text,
+        Some(&retry_file),
+        |crt| match crt.name().as_str() {
+            "crate_b" => Ok(true),
+            name => anyhow::bail!("{} lookup failed", name),
+        },
+    )
+    .unwrap();
+
+    let entry = |name: &str| RetryEntry {
+        name: name.to_string(),
+        version: versions_before[name].clone(),
+    };
+    let mut recorded = RetryFile::load(&retry_file).unwrap().entry;
+    recorded.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(recorded, vec![entry("crate_a"), entry("crate_e")]);
+    assert_eq!(get_versions(), versions_before);
+
+    // on retry crate_a turns out to be unpublished while crate_e fails again
+    let looked_up = RefCell::new(vec![]);
+    let workspace = ReleaseWorkspace::try_new(workspace_mocker.root()).unwrap();
+    retry_failed_with(
+        &workspace,
+        &retry_file,
+        DEFAULT_DEV_SUFFIX,
+        false,
+        false,
+        true,
+        OutputFormat::Text,
+        |crt| {
+            looked_up.borrow_mut().push(crt.name());
+            match crt.name().as_str() {
+                "crate_a" => Ok(false),
+                name => anyhow::bail!("{} lookup failed", name),
+            }
+        },
+    )
+    .unwrap();
+
+    let mut looked_up = looked_up.into_inner();
+    looked_up.sort();
+    assert_eq!(looked_up, vec!["crate_a", "crate_e"]);
+
+    assert_eq!(
+        RetryFile::load(&retry_file).unwrap().entry,
+        vec![entry("crate_e")]
+    );
+
+    let versions_after = get_versions();
+    assert_eq!(versions_after["crate_a"], "0.0.2-dev.0");
+    assert_eq!(versions_after["crate_e"], versions_before["crate_e"]);
+    assert_eq!(versions_after["crate_b"], versions_before["crate_b"]);
+}
+
+#[test]
+fn list_unpublished_against_mocked_index() {
+    use crate::crate_::{list_unpublished_crates_with, unpublished_crates_json};
+    use crate::release::ReleaseWorkspace;
+    use crate::tests::workspace_mocker::example_workspace_1;
+    use std::collections::HashMap;
+
+    let workspace_mocker = example_workspace_1().unwrap();
+    let workspace = ReleaseWorkspace::try_new(workspace_mocker.root()).unwrap();
+
+    // crate_a and crate_b are published at their current version, crate_c only at an older one
+    let index = HashMap::from([
+        ("crate_a", vec!["0.0.1"]),
+        ("crate_b", vec!["0.0.0-alpha.1"]),
+        ("crate_c", vec!["0.0.0"]),
+    ]);
+
+    let mut unpublished = list_unpublished_crates_with(&workspace, false, |crt| {
+        Ok(index
+            .get(crt.name().as_str())
+            .map(|versions| versions.contains(&crt.version().to_string().as_str()))
+            .unwrap_or_default())
+    })
+    .unwrap();
+    unpublished.sort_by_key(|crt| crt.name());
+
+    assert_eq!(
+        unpublished_crates_json(&unpublished),
+        serde_json::json!([
+            { "name": "crate_c", "version": "0.0.1" },
+            { "name": "crate_e", "version": "0.0.1" },
+            { "name": "crate_f", "version": "0.1.0" },
+        ])
+    );
+}
+
+#[test]
+fn verify_publish_consistency_against_mocked_index() {
+    use crate::crate_::{verify_publish_consistency_with, ConsistencyError};
+    use crate::tests::workspace_mocker::{MockProject, WorkspaceMocker};
+    use std::collections::HashMap;
+
+    let workspace_mocker = WorkspaceMocker::try_new(
+        None,
+        vec![
+            MockProject {
+                name: "crate_a".to_string(),
+                version: "0.1.0".to_string(),
+                dependencies: vec![
+                    r#"crate_b = { path = "../crate_b", version = "0.1.0" }"#.to_string()
+                ],
+                ..Default::default()
+            },
+            MockProject {
+                name: "crate_b".to_string(),
+                version: "0.1.1".to_string(),
+                ..Default::default()
+            },
+        ],
+    )
+    .unwrap();
+    let workspace = crate::release::ReleaseWorkspace::try_new(workspace_mocker.root()).unwrap();
+
+    let verify = |published: &[&str]| {
+        let index = HashMap::from([(
+            "crate_b".to_string(),
+            published
+                .iter()
+                .map(|v| semver::Version::parse(v).unwrap())
+                .collect::<Vec<_>>(),
+        )]);
+
+        verify_publish_consistency_with(&workspace, |name| {
+            Ok(index.get(name).cloned().unwrap_or_default())
+        })
+        .unwrap()
+    };
+
+    assert_eq!(
+        verify(&[]),
+        vec![ConsistencyError::UnpublishedDep {
+            dependent: "crate_a".to_string(),
+            dependency: "crate_b".to_string(),
+            required_version: "^0.1.0".to_string(),
+        }]
+    );
+
+    assert_eq!(
+        verify(&["0.1.0"]),
+        vec![ConsistencyError::VersionMismatch {
+            dep: "crate_b".to_string(),
+            registry_version: "0.1.0".to_string(),
+            local_version: "0.1.1".to_string(),
+        }]
+    );
+
+    assert_eq!(verify(&["0.1.0", "0.1.1"]), vec![]);
+}
+
+#[test]
+fn parse_fixup_releases_range() {
+    use crate::crate_::{parse_fixup_releases, FixupReleases};
+
+    assert!(matches!(
+        parse_fixup_releases("20210304.120604..20210315.100000").unwrap(),
+        FixupReleases::Range(from, to) if from == "20210304.120604" && to == "20210315.100000"
+    ));
+
+    assert!(parse_fixup_releases("20210304.120604..").is_err());
+}
+
+#[test]
+fn fixup_releases_range_bumps_unpublished_crates_in_range() {
+    use crate::crate_::{
+        fixup_releases_with, parse_fixup_releases, OutputFormat, DEFAULT_DEV_SUFFIX,
+    };
+    use crate::release::ReleaseWorkspace;
+    use crate::tests::workspace_mocker::example_workspace_1;
+    use std::collections::HashMap;
+
+    let workspace_mocker = example_workspace_1().unwrap();
+    workspace_mocker.add_or_replace_file(
+        "CHANGELOG.md",
+        indoc::indoc! {r#"
+        # Changelog
+
+        # [Unreleased]
+
+        # [20210401.100000]
+
+        ## [crate_c-0.0.1](crates/crate_c/CHANGELOG.md#0.0.1)
+
+        # [20210315.100000]
+
+        ## [crate_a-0.0.1](crates/crate_a/CHANGELOG.md#0.0.1)
+        ## [crate_b-0.0.0-alpha.1](crates/crate_b/CHANGELOG.md#0.0.0-alpha.1)
+
+        # [20210304.120604]
+
+        ## [crate_e-0.0.1](crates/crate_e/CHANGELOG.md#0.0.1)
+        "#,
+        },
+    );
+    workspace_mocker.commit(None);
+    workspace_mocker.tag("20210304.120604");
+    workspace_mocker.tag("20210315.100000");
+
+    let get_versions = || -> HashMap<String, String> {
+        let workspace = ReleaseWorkspace::try_new(workspace_mocker.root()).unwrap();
+        workspace
+            .members()
+            .unwrap()
+            .iter()
+            .map(|crt| (crt.name(), crt.version().to_string()))
+            .collect()
+    };
+    let versions_before = get_versions();
+
+    let workspace = ReleaseWorkspace::try_new(workspace_mocker.root()).unwrap();
+    let fixup = parse_fixup_releases("20210304.120604..20210315.100000").unwrap();
+    fixup_releases_with(
+        &workspace,
+        DEFAULT_DEV_SUFFIX,
+        &fixup,
+        false,
+        false,
+        true,
+        OutputFormat::Text,
+        None,
+        // crate_b is the only one of the crates that made it to the index
+        |crt| Ok(crt.name() == "crate_b"),
+    )
+    .unwrap();
+
+    let versions_after = get_versions();
+
+    // the unpublished crates of both releases in the range are bumped together
+    assert_eq!(versions_after["crate_a"], "0.0.2-dev.0");
+    assert_eq!(versions_after["crate_e"], "0.0.2-dev.0");
+
+    // the published crate and the release outside of the range are left alone
+    assert_eq!(versions_after["crate_b"], versions_before["crate_b"]);
+    assert_eq!(versions_after["crate_c"], versions_before["crate_c"]);
+}
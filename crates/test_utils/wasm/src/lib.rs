@@ -9,6 +9,7 @@ pub enum TestWasm {
     Anchor,
     Bench,
     Capability,
+    CloneCell,
     CounterSigning,
     Create,
     Crd,
@@ -20,6 +21,7 @@ pub enum TestWasm {
     Foo,
     GenesisSelfCheckInvalid,
     GenesisSelfCheckValid,
+    GetPeers,
     HashPath,
     HdkExtern,
     InitFail,
@@ -58,6 +60,7 @@ impl From<TestWasm> for ZomeName {
             TestWasm::Anchor => "anchor",
             TestWasm::Bench => "bench",
             TestWasm::Capability => "capability",
+            TestWasm::CloneCell => "clone_cell",
             TestWasm::CounterSigning => "countersigning",
             TestWasm::Create => "create_entry",
             TestWasm::Crd => "crd",
@@ -69,6 +72,7 @@ impl From<TestWasm> for ZomeName {
             TestWasm::Foo => "foo",
             TestWasm::GenesisSelfCheckInvalid => "genesis_self_check_invalid",
             TestWasm::GenesisSelfCheckValid => "genesis_self_check_valid",
+            TestWasm::GetPeers => "get_peers",
             TestWasm::HashPath => "hash_path",
             TestWasm::HdkExtern => "hdk_extern",
             TestWasm::InitFail => "init_fail",
@@ -113,6 +117,9 @@ impl From<TestWasm> for DnaWasm {
             TestWasm::Capability => {
                 get_code("wasm32-unknown-unknown/release/test_wasm_capability.wasm")
             }
+            TestWasm::CloneCell => {
+                get_code("wasm32-unknown-unknown/release/test_wasm_clone_cell.wasm")
+            }
             TestWasm::CounterSigning => {
                 get_code("wasm32-unknown-unknown/release/test_wasm_countersigning.wasm")
             }
@@ -138,6 +145,9 @@ impl From<TestWasm> for DnaWasm {
             TestWasm::GenesisSelfCheckValid => {
                 get_code("wasm32-unknown-unknown/release/test_wasm_genesis_self_check_valid.wasm")
             }
+            TestWasm::GetPeers => {
+                get_code("wasm32-unknown-unknown/release/test_wasm_get_peers.wasm")
+            }
             TestWasm::HashPath => {
                 get_code("wasm32-unknown-unknown/release/test_wasm_hash_path.wasm")
             }
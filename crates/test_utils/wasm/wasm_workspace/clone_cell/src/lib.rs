@@ -0,0 +1,6 @@
+use hdk::prelude::*;
+
+#[hdk_extern]
+fn create_clone_cell(input: CreateCloneCellInput) -> ExternResult<CellId> {
+    hdk::prelude::create_clone_cell(input)
+}
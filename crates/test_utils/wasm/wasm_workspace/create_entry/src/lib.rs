@@ -232,3 +232,8 @@ fn call_create_entry_remotely_no_rec(agent: AgentPubKey) -> ExternResult<HeaderH
         ))),
     }
 }
+
+#[hdk_extern]
+fn get_validation_receipts(op_hash: DhtOpHash) -> ExternResult<Vec<ValidationReceipt>> {
+    hdk::prelude::get_validation_receipts(op_hash)
+}
@@ -6,6 +6,11 @@ fn emit(_: ()) -> ExternResult<()> {
     Ok(())
 }
 
+#[hdk_extern]
+fn emit_payload(payload: String) -> ExternResult<()> {
+    emit_signal(&payload)
+}
+
 #[hdk_extern]
 fn signal_others(signal: RemoteSignal) -> ExternResult<()> {
     remote_signal(&signal.signal, signal.agents)
@@ -0,0 +1,11 @@
+use hdk::prelude::*;
+
+#[hdk_extern]
+fn get_peers(_: ()) -> ExternResult<Vec<AgentPubKey>> {
+    hdk::prelude::get_peers(None)
+}
+
+#[hdk_extern]
+fn get_peers_of(dna_hash: DnaHash) -> ExternResult<Vec<AgentPubKey>> {
+    hdk::prelude::get_peers(Some(dna_hash))
+}
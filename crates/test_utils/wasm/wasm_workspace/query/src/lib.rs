@@ -1,6 +1,9 @@
 use hdk::prelude::*;
 
-entry_defs![PathEntry::entry_def()];
+#[hdk_entry(id = "post")]
+struct Post(String);
+
+entry_defs![PathEntry::entry_def(), Post::entry_def()];
 
 fn path(s: &str) -> ExternResult<EntryHash> {
     let path = Path::from(s);
@@ -13,7 +16,33 @@ fn query(args: QueryFilter) -> ExternResult<Vec<Element>> {
     hdk::prelude::query(args)
 }
 
+#[hdk_extern]
+fn query_count(args: QueryFilter) -> ExternResult<u32> {
+    hdk::prelude::query_count(args)
+}
+
+#[hdk_extern]
+fn query_after(input: QueryAfterInput) -> ExternResult<Vec<Element>> {
+    hdk::prelude::query_after(input.filter, input.after_hash, input.limit)
+}
+
+#[hdk_extern]
+fn get_activity(args: QueryFilter) -> ExternResult<AgentActivity> {
+    get_agent_activity(
+        agent_info()?.agent_latest_pubkey,
+        args,
+        ActivityRequest::Full,
+    )
+}
+
 #[hdk_extern]
 fn add_path(s: String) -> ExternResult<EntryHash> {
     path(&s)
 }
+
+#[hdk_extern]
+fn add_post(s: String) -> ExternResult<EntryHash> {
+    let post = Post(s);
+    create_entry(&post)?;
+    hash_entry(&post)
+}
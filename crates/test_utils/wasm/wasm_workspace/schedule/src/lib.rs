@@ -43,6 +43,18 @@ fn cron_scheduled_fn(_: Option<Schedule>) -> Option<Schedule> {
     Some(Schedule::Persisted("* * * * * * *".to_string()))
 }
 
+#[hdk_extern(infallible)]
+fn delayed_fn(schedule: Option<Schedule>) -> Option<Schedule> {
+    match schedule {
+        // The first run only sets the delay.
+        None => Some(Schedule::Ephemeral(std::time::Duration::from_millis(100))),
+        Some(_) => {
+            hdk::prelude::create_entry(Tick).ok();
+            None
+        }
+    }
+}
+
 #[hdk_extern]
 fn schedule(_: ()) -> ExternResult<()> {
     hdk::prelude::schedule("scheduled_fn")?;
@@ -50,6 +62,18 @@ fn schedule(_: ()) -> ExternResult<()> {
     Ok(())
 }
 
+#[hdk_extern]
+fn schedule_delayed(_: ()) -> ExternResult<()> {
+    hdk::prelude::schedule("delayed_fn")
+}
+
+#[hdk_extern]
+fn unschedule(_: ()) -> ExternResult<()> {
+    hdk::prelude::unschedule("scheduled_fn")?;
+    hdk::prelude::unschedule("cron_scheduled_fn")?;
+    Ok(())
+}
+
 #[hdk_extern]
 fn query_tick(_: ()) -> ExternResult<Vec<Element>> {
     hdk::prelude::query(ChainQueryFilter::default().entry_type(entry_type!(Tick).unwrap()))
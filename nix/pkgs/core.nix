@@ -30,6 +30,8 @@ rec {
     # run all the non-slow cargo tests
     cargo build --features 'build' -p holochain_wasm_test_utils
     cargo test ''${CARGO_TEST_ARGS:-} --workspace --exclude holochain --exclude release-automation --lib --tests --profile fast-test -- --nocapture
+    # features that are off by default still need to build and pass
+    cargo test ''${CARGO_TEST_ARGS:-} --manifest-path=crates/holochain_state/Cargo.toml --features persistent-dna-store --lib --profile fast-test -- --nocapture
   '';
 
   hcSlowTests = writeShellScriptBin "hc-test-slow" ''